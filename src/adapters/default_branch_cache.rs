@@ -0,0 +1,235 @@
+//! On-disk cache of remote default branches
+//!
+//! Looking up a default branch runs `git ls-remote`, a network round trip.
+//! Results are stored as TOML keyed by the normalized repository URL:
+//!
+//! ```toml
+//! ["github.com/user/repo"]
+//! branch = "main"
+//! fetched_at = 1760000000
+//! ```
+//!
+//! The cache is best effort: unreadable or unwritable files behave like a miss.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::RepoInfo;
+use crate::core::ports::{Clock, FileSystem};
+
+/// File name of the cache inside `{root}/.cache`
+pub const DEFAULT_BRANCH_CACHE_FILE: &str = "default-branches.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CacheEntry {
+    branch: String,
+    /// Seconds since the Unix epoch
+    fetched_at: u64,
+}
+
+pub struct DefaultBranchCache {
+    fs: Arc<dyn FileSystem + Send + Sync>,
+    clock: Arc<dyn Clock + Send + Sync>,
+    path: PathBuf,
+    ttl: Duration,
+    /// Serializes read-modify-write of the file between `grm clone -` workers
+    update: Mutex<()>,
+}
+
+impl DefaultBranchCache {
+    pub fn new(
+        fs: Arc<dyn FileSystem + Send + Sync>,
+        clock: Arc<dyn Clock + Send + Sync>,
+        path: PathBuf,
+        ttl: Duration,
+    ) -> Self {
+        Self {
+            fs,
+            clock,
+            path,
+            ttl,
+            update: Mutex::new(()),
+        }
+    }
+
+    /// Cached default branch of `url`, unless it is older than the TTL
+    pub fn get(&self, url: &str) -> Option<String> {
+        let entry = self.load().remove(&Self::key(url))?;
+        let age = self.now().saturating_sub(entry.fetched_at);
+
+        (age < self.ttl.as_secs()).then_some(entry.branch)
+    }
+
+    pub fn set(&self, url: &str, branch: &str) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        let _guard = self.update.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut entries = self.load();
+        entries.insert(
+            Self::key(url),
+            CacheEntry {
+                branch: branch.to_string(),
+                fetched_at: self.now(),
+            },
+        );
+        self.save(&entries);
+    }
+
+    pub fn forget(&self, url: &str) {
+        let _guard = self.update.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut entries = self.load();
+        if entries.remove(&Self::key(url)).is_some() {
+            self.save(&entries);
+        }
+    }
+
+    /// Seconds since the Unix epoch
+    fn now(&self) -> u64 {
+        self.clock
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs())
+    }
+
+    /// `host/user/repo`, so that different spellings of a URL share an entry
+    fn key(url: &str) -> String {
+        match RepoInfo::from_url(url) {
+            Ok(info) => format!("{}/{}/{}", info.host, info.user, info.repo),
+            Err(_) => url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn load(&self) -> BTreeMap<String, CacheEntry> {
+        if !self.fs.exists(&self.path) {
+            return BTreeMap::new();
+        }
+
+        self.fs
+            .read_to_string(&self.path)
+            .ok()
+            .and_then(|content| toml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, entries: &BTreeMap<String, CacheEntry>) {
+        let Ok(content) = toml::to_string(entries) else {
+            return;
+        };
+        if let Some(parent) = self.path.parent()
+            && self.fs.create_dir(parent).is_err()
+        {
+            return;
+        }
+        let _ = self.fs.write(&self.path, &content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockClock, MockFileSystem};
+    use std::path::Path;
+
+    const CACHE_PATH: &str = "/root/.cache/default-branches.toml";
+
+    fn cache(fs: &Arc<MockFileSystem>, ttl: u64) -> DefaultBranchCache {
+        cache_at(fs, &Arc::new(MockClock::new()), ttl)
+    }
+
+    fn cache_at(fs: &Arc<MockFileSystem>, clock: &Arc<MockClock>, ttl: u64) -> DefaultBranchCache {
+        DefaultBranchCache::new(
+            fs.clone(),
+            clock.clone(),
+            PathBuf::from(CACHE_PATH),
+            Duration::from_secs(ttl),
+        )
+    }
+
+    #[test]
+    fn test_set_and_get_normalizes_url() {
+        // 目的: キャッシュへの保存と取得
+        // 検証: 表記の異なる同一リポジトリの URL で同じエントリが参照される
+
+        let fs = Arc::new(MockFileSystem::new());
+        fs.add_dir("/root");
+        let cache = cache(&fs, 60);
+
+        cache.set("https://github.com/user/repo", "main");
+
+        assert_eq!(
+            cache.get("git@github.com:user/repo.git").as_deref(),
+            Some("main")
+        );
+        assert!(cache.get("https://github.com/user/other").is_none());
+        assert!(fs.exists(Path::new(CACHE_PATH)));
+    }
+
+    #[test]
+    fn test_get_expired_entry() {
+        // 目的: TTL を過ぎたエントリの扱い
+        // 検証: 期限切れのエントリは返されない
+
+        let fs = Arc::new(MockFileSystem::new());
+        fs.add_dir("/root/.cache");
+        fs.add_file_with_content(
+            CACHE_PATH,
+            "[\"github.com/user/repo\"]\nbranch = \"main\"\nfetched_at = 0\n",
+        );
+
+        assert!(cache(&fs, 60).get("https://github.com/user/repo").is_none());
+    }
+
+    #[test]
+    fn test_get_expires_after_ttl() {
+        // 目的: 保存からの経過時間による期限切れ
+        // 検証: TTL 未満では返され、TTL に達すると返されない
+
+        let fs = Arc::new(MockFileSystem::new());
+        fs.add_dir("/root");
+        let clock = Arc::new(MockClock::new());
+        let cache = cache_at(&fs, &clock, 60);
+        cache.set("https://github.com/user/repo", "main");
+
+        clock.advance(Duration::from_secs(59));
+        let fresh = cache.get("https://github.com/user/repo");
+        clock.advance(Duration::from_secs(1));
+        let expired = cache.get("https://github.com/user/repo");
+
+        assert_eq!(fresh.as_deref(), Some("main"));
+        assert!(expired.is_none());
+    }
+
+    #[test]
+    fn test_forget_and_zero_ttl() {
+        // 目的: エントリの削除と TTL 0 での無効化
+        // 検証: forget 後は取得できず、TTL 0 では保存されない
+
+        let fs = Arc::new(MockFileSystem::new());
+        fs.add_dir("/root");
+        let cache_60 = cache(&fs, 60);
+        cache_60.set("https://github.com/user/repo", "main");
+
+        cache_60.forget("https://github.com/user/repo");
+        cache(&fs, 0).set("https://github.com/user/repo", "main");
+
+        assert!(cache_60.get("https://github.com/user/repo").is_none());
+    }
+
+    #[test]
+    fn test_get_ignores_broken_file() {
+        // 目的: 壊れたキャッシュファイルの扱い
+        // 検証: エラーにならずキャッシュミスとして扱われる
+
+        let fs = Arc::new(MockFileSystem::new());
+        fs.add_dir("/root/.cache");
+        fs.add_file_with_content(CACHE_PATH, "not toml [");
+
+        assert!(cache(&fs, 60).get("https://github.com/user/repo").is_none());
+    }
+}
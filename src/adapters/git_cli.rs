@@ -1,39 +1,117 @@
+use std::io::{BufReader, IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::core::ports::{GitError, GitRepository};
+use crate::adapters::DefaultBranchCache;
+use crate::core::ports::{DEFAULT_REMOTE, GitError, GitRepository, WorktreeBranch, WorktreeInfo};
 
-pub struct GitCli;
+/// Longest pause between checks whether a git command with a timeout has exited
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Last lines of its output a failed `git clone` is reported with
+const CLONE_ERROR_LINES: usize = 5;
+
+pub struct GitCli {
+    default_branch_cache: Option<DefaultBranchCache>,
+    timeout: Option<Duration>,
+}
 
 impl GitCli {
     pub fn new() -> Self {
-        Self
+        Self {
+            default_branch_cache: None,
+            timeout: None,
+        }
     }
 
-    fn run_command(args: &[&str]) -> Result<String, GitError> {
-        match Command::new("git").args(args).output() {
-            Ok(output) if output.status.success() => {
-                let stdout = String::from_utf8_lossy(&output.stdout);
+    /// Consult `cache` before asking the remote for its default branch
+    #[must_use]
+    pub fn with_default_branch_cache(mut self, cache: DefaultBranchCache) -> Self {
+        self.default_branch_cache = Some(cache);
+        self
+    }
 
-                Ok(stdout.trim().to_string())
-            }
-            Ok(output) => {
-                let stderr = String::from_utf8_lossy(&output.stderr);
+    /// Kill git commands whose output is read once they run longer than `timeout`
+    ///
+    /// `Duration::ZERO` waits forever. Clones and other commands showing git's
+    /// own progress are never killed, as they can take long on purpose.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
 
-                Err(GitError::Failed {
-                    status: output.status.code().unwrap_or(-1),
-                    stderr: stderr.trim().to_string(),
-                })
-            }
+    fn run_command(&self, args: &[&str]) -> Result<String, GitError> {
+        let output = match self.timeout {
+            Some(timeout) => Self::output_with_timeout(args, timeout)?,
+            None => Command::new("git")
+                .args(args)
+                .output()
+                .map_err(|error| execution_error(args, &error))?,
+        };
 
-            Err(error) => {
-                let message = format!("Failed to execute git {}: {}", args.join(" "), error);
+        if output.status.success() {
+            let stdout = String::from_utf8_lossy(&output.stdout);
 
-                Err(GitError::Execution(message))
-            }
+            Ok(stdout.trim().to_string())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+
+            Err(GitError::Failed {
+                status: output.status.code().unwrap_or(-1),
+                stderr: stderr.trim().to_string(),
+            })
         }
     }
 
+    /// Like `Command::output`, but kill git once it runs longer than `timeout`
+    ///
+    /// stdout and stderr are drained on their own threads, so that git never
+    /// blocks on a full pipe while it is waited for.
+    fn output_with_timeout(args: &[&str], timeout: Duration) -> Result<Output, GitError> {
+        let mut child = Command::new("git")
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| execution_error(args, &error))?;
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let deadline = Instant::now() + timeout;
+        let mut interval = Duration::from_millis(1);
+        let status = loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|error| execution_error(args, &error))?
+            {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                // git may exit on its own in the meantime, which is fine as well.
+                // The drain threads are left behind, since a process started by
+                // git (e.g. ssh) can keep the pipes open.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(GitError::Timeout {
+                    command: args.join(" "),
+                    timeout,
+                });
+            }
+            thread::sleep(interval.min(deadline.saturating_duration_since(Instant::now())));
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        };
+
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
     fn run_command_inherit(args: &[&str]) -> Result<(), GitError> {
         match Command::new("git")
             .args(args)
@@ -53,6 +131,215 @@ impl GitCli {
             }
         }
     }
+
+    /// Run `git clone` with its output shown as usual, keeping the end of it for the error
+    ///
+    /// The output tells e.g. a dropped connection from a rejected login, see
+    /// `GitError::is_transient`. As git draws progress only to a terminal,
+    /// `--progress` is passed when stderr is one.
+    fn run_clone(args: &[&str]) -> Result<(), GitError> {
+        let execution_error = |error: std::io::Error| {
+            GitError::Execution(format!(
+                "Failed to execute git {}: {}",
+                args.join(" "),
+                error
+            ))
+        };
+
+        let mut terminal = std::io::stderr();
+        let mut command = Command::new("git");
+        command.args(args);
+        if terminal.is_terminal() {
+            command.arg("--progress");
+        }
+        let mut child = command
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(execution_error)?;
+
+        let mut output = Vec::new();
+        if let Some(mut stderr) = child.stderr.take() {
+            let mut chunk = [0; 4096];
+            loop {
+                let read = stderr.read(&mut chunk).map_err(execution_error)?;
+                if read == 0 {
+                    break;
+                }
+                // Showing the output is best effort, it is still kept for the error
+                let _ = terminal.write_all(&chunk[..read]);
+                output.extend_from_slice(&chunk[..read]);
+            }
+        }
+
+        let status = child.wait().map_err(execution_error)?;
+        if status.success() {
+            return Ok(());
+        }
+
+        let output = String::from_utf8_lossy(&output);
+        let lines = output
+            .split(['\r', '\n'])
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let stderr = if lines.is_empty() {
+            format!("git {} failed", args.join(" "))
+        } else {
+            lines[lines.len().saturating_sub(CLONE_ERROR_LINES)..].join("\n")
+        };
+        Err(GitError::Failed {
+            status: status.code().unwrap_or(-1),
+            stderr,
+        })
+    }
+
+    /// Run git with stderr piped, passing each line of it to `progress`
+    ///
+    /// git redraws progress with `\r`, so both `\r` and `\n` end a line.
+    fn run_command_progress(args: &[&str], progress: &mut dyn FnMut(&str)) -> Result<(), GitError> {
+        let execution_error = |error: std::io::Error| {
+            GitError::Execution(format!(
+                "Failed to execute git {}: {}",
+                args.join(" "),
+                error
+            ))
+        };
+
+        let mut child = Command::new("git")
+            .args(args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(execution_error)?;
+
+        let mut last_line = String::new();
+        if let Some(stderr) = child.stderr.take() {
+            let mut line = Vec::new();
+            for byte in BufReader::new(stderr).bytes() {
+                let byte = byte.map_err(execution_error)?;
+                if byte != b'\r' && byte != b'\n' {
+                    line.push(byte);
+                    continue;
+                }
+
+                let text = String::from_utf8_lossy(&line);
+                let text = text.trim();
+                if !text.is_empty() {
+                    progress(text);
+                    last_line = text.to_string();
+                }
+                line.clear();
+            }
+        }
+
+        let status = child.wait().map_err(execution_error)?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(GitError::Failed {
+                status: status.code().unwrap_or(-1),
+                stderr: last_line,
+            })
+        }
+    }
+
+    fn clone_args(
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
+    ) -> Vec<String> {
+        let mut args = vec![
+            "clone".to_string(),
+            url.to_string(),
+            destination.to_string_lossy().to_string(),
+        ];
+
+        if let Some(b) = branch {
+            args.extend(["--branch".to_string(), b.to_string()]);
+        }
+
+        if let Some(d) = depth {
+            // --depth implies --single-branch, which would hide the branches other worktrees need
+            args.extend([
+                "--depth".to_string(),
+                d.to_string(),
+                "--no-single-branch".to_string(),
+            ]);
+        }
+
+        if recurse_submodules {
+            args.push("--recurse-submodules".to_string());
+        }
+
+        args
+    }
+}
+
+fn execution_error(args: &[&str], error: &std::io::Error) -> GitError {
+    GitError::Execution(format!(
+        "Failed to execute git {}: {}",
+        args.join(" "),
+        error
+    ))
+}
+
+/// Read `pipe` to the end on a new thread
+fn drain(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            // A read error only cuts the output short, the exit status still tells
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Parse the output of `git worktree list --porcelain`
+///
+/// Each worktree is a block of `<label> [<value>]` lines, and blocks are
+/// separated by blank lines. Unknown labels are ignored.
+pub(crate) fn parse_worktree_porcelain(output: &str) -> Result<Vec<WorktreeInfo>, GitError> {
+    let mut worktrees = Vec::new();
+    let mut current: Option<WorktreeInfo> = None;
+
+    for line in output.lines() {
+        if line.is_empty() {
+            worktrees.extend(current.take());
+            continue;
+        }
+
+        let (label, value) = line.split_once(' ').unwrap_or((line, ""));
+        if label == "worktree" {
+            worktrees.extend(current.take());
+            current = Some(WorktreeInfo {
+                path: PathBuf::from(value),
+                ..WorktreeInfo::default()
+            });
+            continue;
+        }
+
+        let info = current
+            .as_mut()
+            .ok_or_else(|| GitError::Parse(format!("Unexpected worktree line: {line}")))?;
+        match label {
+            "HEAD" => info.head = Some(value.to_string()),
+            "branch" => {
+                let branch = value.strip_prefix("refs/heads/").unwrap_or(value);
+                info.branch = Some(branch.to_string());
+            }
+            "bare" => info.bare = true,
+            "locked" => info.locked = true,
+            "prunable" => info.prunable = true,
+            _ => {}
+        }
+    }
+    worktrees.extend(current);
+
+    Ok(worktrees)
 }
 
 impl Default for GitCli {
@@ -62,8 +349,24 @@ impl Default for GitCli {
 }
 
 impl GitRepository for GitCli {
+    fn version(&self) -> Result<String, GitError> {
+        let output = self.run_command(&["--version"])?;
+        Ok(output
+            .strip_prefix("git version ")
+            .unwrap_or(&output)
+            .to_string())
+    }
+
     fn get_default_branch(&self, url: &str) -> Result<String, GitError> {
-        let output = Self::run_command(&["ls-remote", "--symref", url, "HEAD"])?;
+        if let Some(branch) = self
+            .default_branch_cache
+            .as_ref()
+            .and_then(|cache| cache.get(url))
+        {
+            return Ok(branch);
+        }
+
+        let output = self.run_command(&["ls-remote", "--symref", url, "HEAD"])?;
 
         for line in output.lines() {
             // expected: ref: refs/heads/main HEAD
@@ -72,6 +375,9 @@ impl GitRepository for GitCli {
                 if parts.len() >= 2
                     && let Some(branch) = parts[1].strip_prefix("refs/heads/")
                 {
+                    if let Some(cache) = &self.default_branch_cache {
+                        cache.set(url, branch);
+                    }
                     return Ok(branch.to_string());
                 }
             }
@@ -82,8 +388,14 @@ impl GitRepository for GitCli {
         ))
     }
 
+    fn forget_default_branch(&self, url: &str) {
+        if let Some(cache) = &self.default_branch_cache {
+            cache.forget(url);
+        }
+    }
+
     fn get_repository_root(&self) -> Result<PathBuf, GitError> {
-        let output = Self::run_command(&["rev-parse", "--show-toplevel"])?;
+        let output = self.run_command(&["rev-parse", "--show-toplevel"])?;
 
         if output.is_empty() {
             return Err(GitError::Parse(
@@ -94,15 +406,28 @@ impl GitRepository for GitCli {
         Ok(PathBuf::from(output))
     }
 
-    fn get_remote_url(&self, repo_path: &Path) -> Result<String, GitError> {
-        let output = Self::run_command(&[
+    fn get_repository_root_at(&self, path: &Path) -> Result<PathBuf, GitError> {
+        let output = self.run_command(&[
             "-C",
-            &repo_path.to_string_lossy(),
-            "config",
-            "--get",
-            "remote.origin.url",
+            &path.to_string_lossy(),
+            "rev-parse",
+            "--show-toplevel",
         ])?;
 
+        if output.is_empty() {
+            return Err(GitError::Parse(
+                "Could not determine repository root".to_string(),
+            ));
+        }
+
+        Ok(PathBuf::from(output))
+    }
+
+    fn get_remote_url(&self, repo_path: &Path, remote: Option<&str>) -> Result<String, GitError> {
+        let key = format!("remote.{}.url", remote.unwrap_or(DEFAULT_REMOTE));
+        let output =
+            self.run_command(&["-C", &repo_path.to_string_lossy(), "config", "--get", &key])?;
+
         if output.is_empty() {
             return Err(GitError::Parse("No remote URL found".to_string()));
         }
@@ -110,9 +435,31 @@ impl GitRepository for GitCli {
         Ok(output)
     }
 
-    fn local_branch_exists(&self, branch: &str) -> Result<bool, GitError> {
+    fn get_current_branch(&self, repo_path: &Path) -> Result<String, GitError> {
+        self.run_command(&[
+            "-C",
+            &repo_path.to_string_lossy(),
+            "rev-parse",
+            "--abbrev-ref",
+            "HEAD",
+        ])
+    }
+
+    fn is_dirty(&self, repo_path: &Path) -> Result<bool, GitError> {
+        let output =
+            self.run_command(&["-C", &repo_path.to_string_lossy(), "status", "--porcelain"])?;
+        Ok(!output.is_empty())
+    }
+
+    fn local_branch_exists(&self, repo_path: &Path, branch: &str) -> Result<bool, GitError> {
         let ref_name = format!("refs/heads/{branch}");
-        let result = Self::run_command(&["rev-parse", "--verify", &ref_name]);
+        let result = self.run_command(&[
+            "-C",
+            &repo_path.to_string_lossy(),
+            "rev-parse",
+            "--verify",
+            &ref_name,
+        ]);
 
         match result {
             Ok(_) => Ok(true),
@@ -123,7 +470,7 @@ impl GitRepository for GitCli {
 
     fn remote_branch_exists(&self, remote_url: &str, branch: &str) -> Result<bool, GitError> {
         let ref_name = format!("refs/heads/{branch}");
-        let output = Self::run_command(&["ls-remote", "--heads", remote_url, &ref_name])?;
+        let output = self.run_command(&["ls-remote", "--heads", remote_url, &ref_name])?;
 
         for line in output.lines() {
             if line.contains(&ref_name) {
@@ -139,39 +486,180 @@ impl GitRepository for GitCli {
         url: &str,
         destination: &Path,
         branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
     ) -> Result<(), GitError> {
-        let dest_path = destination.to_string_lossy();
-        let mut args = vec!["clone", url, dest_path.as_ref()];
+        let args = Self::clone_args(url, destination, branch, depth, recurse_submodules);
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
 
-        if let Some(b) = branch {
-            args.extend_from_slice(&["--branch", b]);
-        }
+        Self::run_clone(&args)
+    }
 
-        Self::run_command_inherit(&args)
+    fn clone_sparse(
+        &self,
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        paths: &[String],
+    ) -> Result<(), GitError> {
+        let mut args = Self::clone_args(url, destination, branch, depth, false);
+        args.extend([
+            "--filter=blob:none".to_string(),
+            "--no-checkout".to_string(),
+        ]);
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+        Self::run_clone(&args)?;
+
+        let destination = destination.to_string_lossy();
+        let mut args = vec![
+            "-C",
+            destination.as_ref(),
+            "sparse-checkout",
+            "set",
+            "--cone",
+            "--",
+        ];
+        args.extend(paths.iter().map(String::as_str));
+        self.run_command(&args)?;
+
+        Self::run_command_inherit(&["-C", destination.as_ref(), "checkout"])
+    }
+
+    fn clone_bare(&self, url: &str, destination: &Path, mirror: bool) -> Result<(), GitError> {
+        let mode = if mirror { "--mirror" } else { "--bare" };
+        Self::run_clone(&["clone", mode, url, &destination.to_string_lossy()])
+    }
+
+    fn clone_repository_with_progress(
+        &self,
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
+        progress: &mut dyn FnMut(&str),
+    ) -> Result<(), GitError> {
+        let mut args = Self::clone_args(url, destination, branch, depth, recurse_submodules);
+        // git only reports progress to a terminal unless asked to
+        args.push("--progress".to_string());
+        let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+        Self::run_command_progress(&args, progress)
     }
 
     fn add_worktree(
         &self,
+        repo_path: &Path,
         worktree_path: &Path,
         branch: &str,
-        create_new: bool,
+        source: WorktreeBranch,
     ) -> Result<(), GitError> {
+        let repo_path_str = repo_path.to_string_lossy();
         let worktree_path_str = worktree_path.to_string_lossy();
-        let mut args = vec!["worktree", "add"];
+        let mut args = vec!["-C", repo_path_str.as_ref(), "worktree", "add"];
 
-        if create_new {
-            args.extend_from_slice(&["-b", branch, worktree_path_str.as_ref()]);
-        } else {
-            args.extend_from_slice(&[worktree_path_str.as_ref(), branch]);
+        let upstream;
+        match source {
+            WorktreeBranch::Existing => {
+                args.extend_from_slice(&[worktree_path_str.as_ref(), branch]);
+            }
+            WorktreeBranch::New => {
+                args.extend_from_slice(&["-b", branch, worktree_path_str.as_ref()]);
+            }
+            WorktreeBranch::Track(remote) => {
+                upstream = format!("{remote}/{branch}");
+                args.extend_from_slice(&[
+                    "--track",
+                    "-b",
+                    branch,
+                    worktree_path_str.as_ref(),
+                    &upstream,
+                ]);
+            }
         }
 
         Self::run_command_inherit(&args)
     }
 
+    fn add_worktree_detached(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        commitish: &str,
+    ) -> Result<(), GitError> {
+        Self::run_command_inherit(&[
+            "-C",
+            &repo_path.to_string_lossy(),
+            "worktree",
+            "add",
+            "--detach",
+            &worktree_path.to_string_lossy(),
+            commitish,
+        ])
+    }
+
     fn remove_worktree(&self, worktree_path: &Path) -> Result<(), GitError> {
         let worktree_path_str = worktree_path.to_string_lossy();
         Self::run_command_inherit(&["worktree", "remove", worktree_path_str.as_ref()])
     }
+
+    fn fetch(&self, repo_path: &Path) -> Result<(), GitError> {
+        self.run_command(&["-C", &repo_path.to_string_lossy(), "fetch", "--all"])?;
+        Ok(())
+    }
+
+    fn pull(&self, repo_path: &Path) -> Result<(), GitError> {
+        match self.run_command(&["-C", &repo_path.to_string_lossy(), "pull", "--ff-only"]) {
+            // e.g. "fatal: Not possible to fast-forward, aborting."
+            Err(GitError::Failed { stderr, .. }) if stderr.contains("fast-forward") => {
+                Err(GitError::NotFastForward(stderr))
+            }
+            result => result.map(|_| ()),
+        }
+    }
+
+    fn rename_branch(&self, repo_path: &Path, old: &str, new: &str) -> Result<(), GitError> {
+        self.run_command(&["-C", &repo_path.to_string_lossy(), "branch", "-m", old, new])?;
+        Ok(())
+    }
+
+    fn delete_branch(&self, repo_path: &Path, branch: &str) -> Result<(), GitError> {
+        self.run_command(&["-C", &repo_path.to_string_lossy(), "branch", "-D", branch])?;
+        Ok(())
+    }
+
+    fn last_commit_timestamp(&self, repo_path: &Path) -> Result<SystemTime, GitError> {
+        let output = self.run_command(&[
+            "-C",
+            &repo_path.to_string_lossy(),
+            "log",
+            "-1",
+            "--format=%ct",
+        ])?;
+        let seconds = output
+            .parse()
+            .map_err(|_| GitError::Parse(format!("Unexpected commit timestamp: {output}")))?;
+
+        Ok(UNIX_EPOCH + Duration::from_secs(seconds))
+    }
+
+    fn repair_worktrees(&self, worktree_path: &Path) -> Result<(), GitError> {
+        self.run_command(&["-C", &worktree_path.to_string_lossy(), "worktree", "repair"])?;
+        Ok(())
+    }
+
+    fn worktree_details(&self, repo_path: &Path) -> Result<Vec<WorktreeInfo>, GitError> {
+        let output = self.run_command(&[
+            "-C",
+            &repo_path.to_string_lossy(),
+            "worktree",
+            "list",
+            "--porcelain",
+        ])?;
+
+        parse_worktree_porcelain(&output)
+    }
 }
 
 #[cfg(test)]
@@ -228,6 +716,78 @@ mod tests {
         assert_eq!(branch, "test");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_get_default_branch_cached() {
+        use crate::adapters::{SystemClock, UnixFs};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let url = format!("file://{}", repo_dir.display());
+        let cache_path = temp_dir.path().join(".cache/default-branches.toml");
+        let cache = || {
+            DefaultBranchCache::new(
+                Arc::new(UnixFs::new()),
+                Arc::new(SystemClock::new()),
+                cache_path.clone(),
+                Duration::from_mins(1),
+            )
+        };
+        let adapter = GitCli::new().with_default_branch_cache(cache());
+        assert_eq!(adapter.get_default_branch(&url).unwrap(), "test");
+
+        // The cached answer is returned even after the remote has changed
+        Command::new("git")
+            .args(["branch", "-m", "test", "renamed"])
+            .current_dir(&repo_dir)
+            .output()
+            .expect("Failed to rename branch");
+        let adapter = GitCli::new().with_default_branch_cache(cache());
+        assert_eq!(adapter.get_default_branch(&url).unwrap(), "test");
+
+        adapter.forget_default_branch(&url);
+        assert_eq!(adapter.get_default_branch(&url).unwrap(), "renamed");
+    }
+
+    #[test]
+    fn test_clone_args() {
+        let plain = GitCli::clone_args(
+            "https://example.com/repo",
+            Path::new("dest"),
+            None,
+            None,
+            false,
+        );
+        let full = GitCli::clone_args(
+            "https://example.com/repo",
+            Path::new("dest"),
+            Some("main"),
+            Some(1),
+            true,
+        );
+
+        assert_eq!(plain, vec!["clone", "https://example.com/repo", "dest"]);
+        assert_eq!(
+            full,
+            vec![
+                "clone",
+                "https://example.com/repo",
+                "dest",
+                "--branch",
+                "main",
+                "--depth",
+                "1",
+                "--no-single-branch",
+                "--recurse-submodules",
+            ]
+        );
+    }
+
     #[test]
     fn test_clone_repository_local() {
         let temp_dir = TempDir::new().unwrap();
@@ -240,10 +800,527 @@ mod tests {
 
         let adapter = GitCli::new();
         adapter
-            .clone_repository(&url, &clone_dest, None)
+            .clone_repository(&url, &clone_dest, None, None, false)
             .expect("Failed to clone repo");
 
         assert!(clone_dest.join(".git").exists());
         assert!(clone_dest.join("README.md").exists());
     }
+
+    #[test]
+    fn test_clone_sparse_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir_all(repo_dir.join("services/api")).unwrap();
+        std::fs::create_dir_all(repo_dir.join("services/web")).unwrap();
+        std::fs::write(repo_dir.join("services/api/main.rs"), "").unwrap();
+        std::fs::write(repo_dir.join("services/web/index.html"), "").unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let clone_dest = temp_dir.path().join("clone");
+        let url = format!("file://{}", repo_dir.display());
+
+        let adapter = GitCli::new();
+        adapter
+            .clone_sparse(
+                &url,
+                &clone_dest,
+                Some("test"),
+                None,
+                &["services/api".to_string()],
+            )
+            .expect("Failed to clone repo");
+
+        // Files at the top level are always checked out in cone mode
+        assert!(clone_dest.join("README.md").exists());
+        assert!(clone_dest.join("services/api/main.rs").exists());
+        assert!(!clone_dest.join("services/web").exists());
+        assert!(!adapter.is_dirty(&clone_dest).unwrap());
+    }
+
+    #[test]
+    fn test_clone_bare_and_mirror_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+        let url = format!("file://{}", repo_dir.display());
+        let adapter = GitCli::new();
+
+        let bare_dest = temp_dir.path().join("bare.git");
+        adapter
+            .clone_bare(&url, &bare_dest, false)
+            .expect("Failed to clone bare repo");
+        let mirror_dest = temp_dir.path().join("mirror.git");
+        adapter
+            .clone_bare(&url, &mirror_dest, true)
+            .expect("Failed to clone mirror");
+
+        let config = |dest: &Path, key: &str| {
+            GitCli::new().run_command(&["-C", &dest.to_string_lossy(), "config", "--get", key])
+        };
+        assert!(bare_dest.join("HEAD").exists());
+        assert!(!bare_dest.join("README.md").exists());
+        assert_eq!(config(&bare_dest, "core.bare").unwrap(), "true");
+        assert!(config(&bare_dest, "remote.origin.mirror").is_err());
+        assert_eq!(config(&mirror_dest, "core.bare").unwrap(), "true");
+        assert_eq!(
+            config(&mirror_dest, "remote.origin.mirror").unwrap(),
+            "true"
+        );
+    }
+
+    #[test]
+    fn test_clone_repository_with_progress_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let clone_dest = temp_dir.path().join("clone");
+        let url = format!("file://{}", repo_dir.display());
+
+        let adapter = GitCli::new();
+        let mut lines = Vec::new();
+        adapter
+            .clone_repository_with_progress(&url, &clone_dest, None, None, false, &mut |line| {
+                lines.push(line.to_string());
+            })
+            .expect("Failed to clone repo");
+
+        assert!(clone_dest.join("README.md").exists());
+        assert!(lines.iter().any(|line| line.starts_with("Cloning into")));
+        assert!(lines.iter().all(|line| !line.contains(['\r', '\n'])));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_timeout() {
+        let adapter = GitCli::new().with_timeout(Duration::from_millis(200));
+
+        let version = adapter.run_command(&["--version"]);
+        let started = Instant::now();
+        let stalled = adapter.run_command(&["-c", "alias.nap=!sleep 5", "nap"]);
+
+        assert!(version.is_ok(), "{version:?}");
+        match stalled {
+            Err(GitError::Timeout { command, timeout }) => {
+                assert_eq!(command, "-c alias.nap=!sleep 5 nap");
+                assert_eq!(timeout, Duration::from_millis(200));
+            }
+            other => panic!("Expected Timeout error, got {other:?}"),
+        }
+        assert!(started.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_clone_repository_with_progress_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let url = format!("file://{}", temp_dir.path().join("missing").display());
+
+        let adapter = GitCli::new();
+        let result = adapter.clone_repository_with_progress(
+            &url,
+            &temp_dir.path().join("clone"),
+            None,
+            None,
+            false,
+            &mut |_| {},
+        );
+
+        match result {
+            Err(GitError::Failed { stderr, .. }) => assert!(!stderr.is_empty()),
+            other => panic!("Expected Failed error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_move_and_repair_worktree_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo+test");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let adapter = GitCli::new();
+        let old_path = temp_dir.path().join("repo+old");
+        adapter
+            .add_worktree(&repo_dir, &old_path, "old", WorktreeBranch::New)
+            .expect("Failed to add worktree");
+
+        let new_path = temp_dir.path().join("repo+new");
+        std::fs::rename(&old_path, &new_path).unwrap();
+        adapter
+            .repair_worktrees(&new_path)
+            .expect("Failed to repair worktrees");
+        adapter
+            .rename_branch(&new_path, "old", "new")
+            .expect("Failed to rename branch");
+
+        let worktrees = adapter.worktree_details(&repo_dir).unwrap();
+        let moved = worktrees
+            .iter()
+            .find(|worktree| worktree.path.ends_with("repo+new"))
+            .expect("moved worktree not listed");
+        assert_eq!(moved.branch.as_deref(), Some("new"));
+        assert!(!moved.prunable);
+    }
+
+    #[test]
+    fn test_add_worktree_tracking_remote_branch_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let origin = temp_dir.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        setup_dummy_repo(&origin);
+        Command::new("git")
+            .args(["branch", "release"])
+            .current_dir(&origin)
+            .output()
+            .expect("Failed to create branch");
+
+        let adapter = GitCli::new();
+        let clone = temp_dir.path().join("repo+test");
+        adapter
+            .clone_repository(
+                &format!("file://{}", origin.display()),
+                &clone,
+                Some("test"),
+                None,
+                false,
+            )
+            .expect("Failed to clone");
+        assert!(!adapter.local_branch_exists(&clone, "release").unwrap());
+
+        let worktree = temp_dir.path().join("repo+release");
+        adapter
+            .add_worktree(
+                &clone,
+                &worktree,
+                "release",
+                WorktreeBranch::Track(DEFAULT_REMOTE.to_string()),
+            )
+            .expect("Failed to add tracking worktree");
+
+        let upstream = Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "@{upstream}"])
+            .current_dir(&worktree)
+            .output()
+            .expect("Failed to read upstream");
+        assert_eq!(adapter.get_current_branch(&worktree).unwrap(), "release");
+        assert_eq!(
+            String::from_utf8_lossy(&upstream.stdout).trim(),
+            "origin/release"
+        );
+    }
+
+    #[test]
+    fn test_add_worktree_detached_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo+test");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+        Command::new("git")
+            .args(["tag", "v1.0"])
+            .current_dir(&repo_dir)
+            .output()
+            .expect("Failed to tag");
+
+        let adapter = GitCli::new();
+        let worktree = temp_dir.path().join("repo+v1.0");
+        adapter
+            .add_worktree_detached(&repo_dir, &worktree, "v1.0")
+            .expect("Failed to add detached worktree");
+
+        assert_eq!(adapter.get_current_branch(&worktree).unwrap(), "HEAD");
+        assert!(!adapter.local_branch_exists(&repo_dir, "v1.0").unwrap());
+    }
+
+    #[test]
+    fn test_version() {
+        let version = GitCli::new().version().expect("Failed to get git version");
+
+        assert!(!version.starts_with("git version"));
+        assert!(version.chars().next().is_some_and(|c| c.is_ascii_digit()));
+    }
+
+    #[test]
+    fn test_get_current_branch_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        Command::new("git")
+            .args(["checkout", "-b", "drifted"])
+            .current_dir(&repo_dir)
+            .output()
+            .expect("Failed to create branch");
+
+        let branch = GitCli::new()
+            .get_current_branch(&repo_dir)
+            .expect("Failed to get current branch");
+
+        assert_eq!(branch, "drifted");
+    }
+
+    #[test]
+    fn test_delete_branch_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        Command::new("git")
+            .args(["branch", "unmerged"])
+            .current_dir(&repo_dir)
+            .output()
+            .expect("Failed to create branch");
+
+        let adapter = GitCli::new();
+        adapter
+            .delete_branch(&repo_dir, "unmerged")
+            .expect("Failed to delete branch");
+
+        assert!(!adapter.local_branch_exists(&repo_dir, "unmerged").unwrap());
+        assert!(adapter.delete_branch(&repo_dir, "test").is_err());
+    }
+
+    #[test]
+    fn test_pull_fast_forward_and_diverged_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let origin = temp_dir.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        setup_dummy_repo(&origin);
+        let clone = temp_dir.path().join("clone");
+        let adapter = GitCli::new();
+        adapter
+            .clone_repository(
+                &format!("file://{}", origin.display()),
+                &clone,
+                Some("test"),
+                None,
+                false,
+            )
+            .expect("Failed to clone");
+        let commit = |dir: &Path, file: &str| {
+            std::fs::write(dir.join(file), file).unwrap();
+            for args in [
+                vec!["add", "."],
+                vec![
+                    "-c",
+                    "user.email=you@example.com",
+                    "-c",
+                    "user.name=Your Name",
+                    "commit",
+                    "-m",
+                    file,
+                ],
+            ] {
+                Command::new("git")
+                    .args(args)
+                    .current_dir(dir)
+                    .output()
+                    .expect("Failed to commit");
+            }
+        };
+
+        commit(&origin, "upstream.txt");
+        adapter.pull(&clone).expect("Failed to fast-forward");
+        assert!(clone.join("upstream.txt").exists());
+
+        commit(&origin, "theirs.txt");
+        commit(&clone, "ours.txt");
+        let diverged = adapter.pull(&clone);
+        assert!(
+            matches!(diverged, Err(GitError::NotFastForward(_))),
+            "{diverged:?}"
+        );
+        assert!(!clone.join("theirs.txt").exists());
+    }
+
+    #[test]
+    fn test_get_remote_url_named_remote() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+        for (name, url) in [
+            ("origin", "https://github.com/fork/repo.git"),
+            ("upstream", "https://github.com/canonical/repo.git"),
+        ] {
+            Command::new("git")
+                .args(["remote", "add", name, url])
+                .current_dir(&repo_dir)
+                .output()
+                .expect("Failed to add remote");
+        }
+        let adapter = GitCli::new();
+
+        let origin = adapter.get_remote_url(&repo_dir, None).unwrap();
+        let upstream = adapter.get_remote_url(&repo_dir, Some("upstream")).unwrap();
+
+        assert_eq!(origin, "https://github.com/fork/repo.git");
+        assert_eq!(upstream, "https://github.com/canonical/repo.git");
+        assert!(adapter.get_remote_url(&repo_dir, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_is_dirty_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+        let adapter = GitCli::new();
+
+        let clean = adapter.is_dirty(&repo_dir).expect("Failed to get status");
+        std::fs::write(repo_dir.join("untracked.txt"), "new").unwrap();
+        let dirty = adapter.is_dirty(&repo_dir).expect("Failed to get status");
+
+        assert!(!clean);
+        assert!(dirty);
+    }
+
+    #[test]
+    fn test_fetch_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let clone_dest = temp_dir.path().join("clone");
+        let url = format!("file://{}", repo_dir.display());
+
+        let adapter = GitCli::new();
+        adapter
+            .clone_repository(&url, &clone_dest, None, None, false)
+            .expect("Failed to clone repo");
+
+        Command::new("git")
+            .args(["branch", "pushed"])
+            .current_dir(&repo_dir)
+            .output()
+            .expect("Failed to create branch");
+
+        adapter.fetch(&clone_dest).expect("Failed to fetch");
+
+        let fetched = GitCli::new().run_command(&[
+            "-C",
+            &clone_dest.to_string_lossy(),
+            "rev-parse",
+            "--verify",
+            "refs/remotes/origin/pushed",
+        ]);
+        assert!(fetched.is_ok(), "branch not fetched: {:?}", fetched.err());
+    }
+
+    #[test]
+    fn test_parse_worktree_porcelain() {
+        let output = "\
+worktree /grm/github.com/user/repo+main
+HEAD 1111111111111111111111111111111111111111
+branch refs/heads/main
+
+worktree /grm/github.com/user/repo+feature/x
+HEAD 2222222222222222222222222222222222222222
+branch refs/heads/feature/x
+locked
+
+worktree /grm/github.com/user/repo+detached
+HEAD 3333333333333333333333333333333333333333
+detached
+prunable gitdir file points to non-existent location
+";
+
+        let worktrees = parse_worktree_porcelain(output).unwrap();
+
+        assert_eq!(
+            worktrees,
+            vec![
+                WorktreeInfo {
+                    path: PathBuf::from("/grm/github.com/user/repo+main"),
+                    branch: Some("main".to_string()),
+                    head: Some("1111111111111111111111111111111111111111".to_string()),
+                    ..WorktreeInfo::default()
+                },
+                WorktreeInfo {
+                    path: PathBuf::from("/grm/github.com/user/repo+feature/x"),
+                    branch: Some("feature/x".to_string()),
+                    head: Some("2222222222222222222222222222222222222222".to_string()),
+                    locked: true,
+                    ..WorktreeInfo::default()
+                },
+                WorktreeInfo {
+                    path: PathBuf::from("/grm/github.com/user/repo+detached"),
+                    branch: None,
+                    head: Some("3333333333333333333333333333333333333333".to_string()),
+                    prunable: true,
+                    ..WorktreeInfo::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_worktree_porcelain_bare_and_locked_reason() {
+        let output = "\
+worktree /srv/repo.git
+bare
+
+worktree /srv/repo+main
+HEAD 4444444444444444444444444444444444444444
+branch refs/heads/main
+locked on removable drive";
+
+        let worktrees = parse_worktree_porcelain(output).unwrap();
+
+        assert_eq!(worktrees.len(), 2);
+        assert!(worktrees[0].bare);
+        assert_eq!(worktrees[0].head, None);
+        assert!(worktrees[1].locked);
+        assert!(!worktrees[1].bare);
+    }
+
+    #[test]
+    fn test_parse_worktree_porcelain_rejects_orphan_line() {
+        let result = parse_worktree_porcelain("HEAD 5555555555555555555555555555555555555555\n");
+
+        assert!(matches!(result, Err(GitError::Parse(_))));
+    }
+
+    #[test]
+    fn test_worktree_details_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        std::fs::create_dir(&repo_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+
+        let adapter = GitCli::new();
+        let worktrees = adapter.worktree_details(&repo_dir).unwrap();
+
+        assert_eq!(worktrees.len(), 1);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("test"));
+        assert!(worktrees[0].head.is_some());
+    }
+
+    #[test]
+    fn test_last_commit_timestamp_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_dir = temp_dir.path().join("repo");
+        let empty_dir = temp_dir.path().join("empty");
+        std::fs::create_dir(&repo_dir).unwrap();
+        std::fs::create_dir(&empty_dir).unwrap();
+        setup_dummy_repo(&repo_dir);
+        Command::new("git")
+            .arg("init")
+            .current_dir(&empty_dir)
+            .output()
+            .expect("Failed to init git repo");
+
+        let adapter = GitCli::new();
+        let committed = adapter.last_commit_timestamp(&repo_dir).unwrap();
+        let empty = adapter.last_commit_timestamp(&empty_dir);
+
+        let age = SystemTime::now().duration_since(committed).unwrap();
+        assert!(age < Duration::from_mins(10), "{age:?}");
+        assert!(matches!(empty, Err(GitError::Failed { .. })));
+    }
 }
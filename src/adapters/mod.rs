@@ -1,10 +1,33 @@
+pub mod default_branch_cache;
 pub mod git_cli;
+pub mod process_runner;
+pub mod quiet_interaction;
+pub mod stderr_interaction;
+pub mod system_clock;
 pub mod terminal_interaction;
+#[cfg(unix)]
 pub mod unix_fs;
+#[cfg(windows)]
+pub mod windows_fs;
 
 #[cfg(test)]
 pub mod test_helpers;
 
+pub use default_branch_cache::{DEFAULT_BRANCH_CACHE_FILE, DefaultBranchCache};
 pub use git_cli::GitCli;
+pub use process_runner::ProcessRunner;
+pub use quiet_interaction::QuietInteraction;
+pub use stderr_interaction::StderrInteraction;
+pub use system_clock::SystemClock;
 pub use terminal_interaction::TerminalInteraction;
+#[cfg(unix)]
 pub use unix_fs::UnixFs;
+#[cfg(windows)]
+pub use windows_fs::WindowsFs;
+
+/// The `FileSystem` adapter of the platform grm is built for
+#[cfg(unix)]
+pub type NativeFs = UnixFs;
+/// The `FileSystem` adapter of the platform grm is built for
+#[cfg(windows)]
+pub type NativeFs = WindowsFs;
@@ -0,0 +1,197 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::core::ports::{CommandError, CommandRunner, OutputStream};
+
+pub struct ProcessRunner;
+
+impl ProcessRunner {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Send each line of `reader` to `sender` from a background thread
+    fn forward_lines<R: Read + Send + 'static>(
+        reader: R,
+        stream: OutputStream,
+        sender: Sender<(OutputStream, String)>,
+    ) -> JoinHandle<()> {
+        thread::spawn(move || {
+            let mut reader = BufReader::new(reader);
+            let mut buffer = Vec::new();
+            while reader
+                .read_until(b'\n', &mut buffer)
+                .is_ok_and(|read| read > 0)
+            {
+                let line = String::from_utf8_lossy(&buffer);
+                let line = line.trim_end_matches(['\r', '\n']).to_string();
+                if sender.send((stream, line)).is_err() {
+                    break;
+                }
+                buffer.clear();
+            }
+        })
+    }
+}
+
+impl Default for ProcessRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandRunner for ProcessRunner {
+    fn run(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        envs: &[(String, String)],
+    ) -> Result<i32, CommandError> {
+        let status = Command::new(program)
+            .args(args)
+            .current_dir(current_dir)
+            .envs(envs.iter().map(|(k, v)| (k, v)))
+            .status()
+            .map_err(|e| CommandError::Execution {
+                program: program.to_string(),
+                message: e.to_string(),
+            })?;
+
+        Ok(status.code().unwrap_or(-1))
+    }
+
+    fn run_captured(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> Result<i32, CommandError> {
+        let execution_error = |e: std::io::Error| CommandError::Execution {
+            program: program.to_string(),
+            message: e.to_string(),
+        };
+
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(current_dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(execution_error)?;
+
+        // Both pipes are drained concurrently so that neither can fill up and block the child
+        let (sender, receiver) = mpsc::channel();
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(Self::forward_lines(
+                stdout,
+                OutputStream::Stdout,
+                sender.clone(),
+            ));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(Self::forward_lines(
+                stderr,
+                OutputStream::Stderr,
+                sender.clone(),
+            ));
+        }
+        drop(sender);
+
+        for (stream, line) in receiver {
+            on_output(stream, &line);
+        }
+        for reader in readers {
+            let _ = reader.join();
+        }
+
+        let status = child.wait().map_err(execution_error)?;
+        Ok(status.code().unwrap_or(-1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_run_exit_code() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = ProcessRunner::new();
+
+        let args = vec!["-c".to_string(), "exit 3".to_string()];
+        let code = runner.run("sh", &args, temp_dir.path(), &[]).unwrap();
+
+        assert_eq!(code, 3);
+    }
+
+    #[test]
+    fn test_run_with_env_and_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = ProcessRunner::new();
+
+        let args = vec![
+            "-c".to_string(),
+            "test \"$GRM_TEST\" = ok && test \"$(pwd -P)\" = \"$EXPECTED\"".to_string(),
+        ];
+        let envs = vec![
+            ("GRM_TEST".to_string(), "ok".to_string()),
+            (
+                "EXPECTED".to_string(),
+                temp_dir
+                    .path()
+                    .canonicalize()
+                    .unwrap()
+                    .display()
+                    .to_string(),
+            ),
+        ];
+        let code = runner.run("sh", &args, temp_dir.path(), &envs).unwrap();
+
+        assert_eq!(code, 0);
+    }
+
+    #[test]
+    fn test_run_captured_collects_both_streams() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = ProcessRunner::new();
+
+        let args = vec![
+            "-c".to_string(),
+            "echo out; echo err >&2; exit 2".to_string(),
+        ];
+        let mut lines = Vec::new();
+        let code = runner
+            .run_captured("sh", &args, temp_dir.path(), &mut |stream, line| {
+                lines.push((stream, line.to_string()));
+            })
+            .unwrap();
+
+        assert_eq!(code, 2);
+        lines.sort_by_key(|(stream, _)| *stream == OutputStream::Stderr);
+        assert_eq!(
+            lines,
+            vec![
+                (OutputStream::Stdout, "out".to_string()),
+                (OutputStream::Stderr, "err".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_missing_program() {
+        let temp_dir = TempDir::new().unwrap();
+        let runner = ProcessRunner::new();
+
+        let result = runner.run("grm-nonexistent-program", &[], temp_dir.path(), &[]);
+
+        assert!(matches!(result, Err(CommandError::Execution { .. })));
+    }
+}
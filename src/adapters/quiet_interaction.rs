@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::core::ports::{InteractionError, UserInteraction};
+
+/// Decorator for `--quiet` which drops `print` but keeps results, errors and prompts
+pub struct QuietInteraction(Arc<dyn UserInteraction + Send + Sync>);
+
+impl QuietInteraction {
+    pub fn new(inner: Arc<dyn UserInteraction + Send + Sync>) -> Self {
+        Self(inner)
+    }
+}
+
+impl UserInteraction for QuietInteraction {
+    fn confirm(&self, message: &str) -> Result<bool, InteractionError> {
+        self.0.confirm(message)
+    }
+
+    fn confirm_strict(&self, message: &str) -> Result<bool, InteractionError> {
+        self.0.confirm_strict(message)
+    }
+
+    fn input(&self, message: &str, default: Option<&str>) -> Result<String, InteractionError> {
+        self.0.input(message, default)
+    }
+
+    fn select(&self, message: &str, options: &[String]) -> Result<Option<usize>, InteractionError> {
+        self.0.select(message, options)
+    }
+
+    fn print(&self, _message: &str) {}
+
+    fn print_result(&self, message: &str) {
+        self.0.print_result(message);
+    }
+
+    fn print_error(&self, message: &str) {
+        self.0.print_error(message);
+    }
+
+    fn print_warning(&self, message: &str) {
+        self.0.print_warning(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockUserInteraction;
+
+    #[test]
+    fn test_quiet_drops_print_only() {
+        // 目的: --quiet 時の出力の抑制
+        // 検証: print は捨てられ、print_result・print_error・print_warning と confirm は委譲される
+
+        let inner = Arc::new(MockUserInteraction::new());
+        inner.set_confirm(true);
+        let quiet = QuietInteraction::new(inner.clone());
+
+        quiet.print("Repository cloned to: /grm/github.com/user/repo+main");
+        quiet.print_error("Failed to clone not-a-url");
+        quiet.print_warning("something happened");
+        quiet.print_result("/grm/github.com/user/repo+main");
+        let confirmed = quiet.confirm("Continue?").unwrap();
+
+        assert_eq!(
+            inner.get_printed_messages(),
+            vec!["/grm/github.com/user/repo+main".to_string()]
+        );
+        assert_eq!(
+            inner.get_error_messages(),
+            vec!["Failed to clone not-a-url".to_string()]
+        );
+        assert_eq!(
+            inner.get_warning_messages(),
+            vec!["something happened".to_string()]
+        );
+        assert!(confirmed);
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use crate::core::ports::{InteractionError, UserInteraction};
+
+/// Decorator for `clone --open` which moves `print` to stderr,
+/// leaving stdout to `print_result` for the path consumed by command substitution
+pub struct StderrInteraction(Arc<dyn UserInteraction + Send + Sync>);
+
+impl StderrInteraction {
+    pub fn new(inner: Arc<dyn UserInteraction + Send + Sync>) -> Self {
+        Self(inner)
+    }
+}
+
+impl UserInteraction for StderrInteraction {
+    fn confirm(&self, message: &str) -> Result<bool, InteractionError> {
+        self.0.confirm(message)
+    }
+
+    fn confirm_strict(&self, message: &str) -> Result<bool, InteractionError> {
+        self.0.confirm_strict(message)
+    }
+
+    fn input(&self, message: &str, default: Option<&str>) -> Result<String, InteractionError> {
+        self.0.input(message, default)
+    }
+
+    fn select(&self, message: &str, options: &[String]) -> Result<Option<usize>, InteractionError> {
+        self.0.select(message, options)
+    }
+
+    fn print(&self, message: &str) {
+        self.0.print_error(message);
+    }
+
+    fn print_result(&self, message: &str) {
+        self.0.print_result(message);
+    }
+
+    fn print_error(&self, message: &str) {
+        self.0.print_error(message);
+    }
+
+    fn print_warning(&self, message: &str) {
+        self.0.print_warning(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockUserInteraction;
+
+    #[test]
+    fn test_print_goes_to_stderr() {
+        // 目的: --open 時の出力先の切り替え
+        // 検証: print も print_error も inner の print_error に委譲され、警告と結果はそのまま委譲される
+
+        let inner = Arc::new(MockUserInteraction::new());
+        let stderr = StderrInteraction::new(inner.clone());
+
+        stderr.print("Repository cloned to: /grm/github.com/user/repo+main");
+        stderr.print_error("Failed to clone not-a-url");
+        stderr.print_warning("something happened");
+        stderr.print_result("/grm/github.com/user/repo+main");
+
+        assert_eq!(
+            inner.get_printed_messages(),
+            vec!["/grm/github.com/user/repo+main".to_string()]
+        );
+        assert_eq!(
+            inner.get_error_messages(),
+            vec![
+                "Repository cloned to: /grm/github.com/user/repo+main".to_string(),
+                "Failed to clone not-a-url".to_string(),
+            ]
+        );
+        assert_eq!(
+            inner.get_warning_messages(),
+            vec!["something happened".to_string()]
+        );
+    }
+}
@@ -0,0 +1,27 @@
+use std::time::{Duration, SystemTime};
+
+use crate::core::ports::Clock;
+
+pub struct SystemClock;
+
+impl SystemClock {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
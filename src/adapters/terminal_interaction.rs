@@ -1,7 +1,10 @@
-use std::io::{self, BufRead, Write};
+use std::io::{self, BufRead, IsTerminal, Write};
 
 use crate::core::ports::{InteractionError, UserInteraction};
 
+/// Number of prompts `confirm_strict` shows before giving up
+const STRICT_CONFIRM_ATTEMPTS: usize = 3;
+
 pub struct TerminalInteraction;
 
 impl TerminalInteraction {
@@ -9,6 +12,15 @@ impl TerminalInteraction {
         Self
     }
 
+    /// Refuse to prompt when stdin is not a terminal, rather than read EOF as "no"
+    fn ensure_interactive() -> Result<(), InteractionError> {
+        if io::stdin().is_terminal() {
+            Ok(())
+        } else {
+            Err(InteractionError::NotInteractive)
+        }
+    }
+
     fn confirm_stream<R, W>(
         read: &mut R,
         write: &mut W,
@@ -27,6 +39,93 @@ impl TerminalInteraction {
         let answer = input.trim().to_lowercase();
         Ok(answer.starts_with('y'))
     }
+
+    fn confirm_strict_stream<R, W>(
+        read: &mut R,
+        write: &mut W,
+        message: &str,
+    ) -> Result<bool, InteractionError>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        for _ in 0..STRICT_CONFIRM_ATTEMPTS {
+            write!(write, "{message} [y/n]: ")?;
+            write.flush()?;
+
+            let mut input = String::new();
+            // Nothing more will come after EOF, so asking again would only repeat the prompt
+            if read.read_line(&mut input)? == 0 {
+                writeln!(write)?;
+                return Ok(false);
+            }
+
+            match input.trim().to_lowercase().as_str() {
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => writeln!(write, "Please answer y or n.")?,
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn input_stream<R, W>(
+        read: &mut R,
+        write: &mut W,
+        message: &str,
+        default: Option<&str>,
+    ) -> Result<String, InteractionError>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        match default {
+            Some(value) => write!(write, "{message} [{value}]: ")?,
+            None => write!(write, "{message}: ")?,
+        }
+        write.flush()?;
+
+        let mut input = String::new();
+        read.read_line(&mut input)?;
+
+        let answer = input.trim();
+        if answer.is_empty() {
+            return Ok(default.unwrap_or_default().to_string());
+        }
+
+        Ok(answer.to_string())
+    }
+
+    fn select_stream<R, W>(
+        read: &mut R,
+        write: &mut W,
+        message: &str,
+        options: &[String],
+    ) -> Result<Option<usize>, InteractionError>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        for (index, option) in options.iter().enumerate() {
+            writeln!(write, "  {}) {option}", index + 1)?;
+        }
+        write!(write, "{message} [1-{}, q to cancel]: ", options.len())?;
+        write.flush()?;
+
+        let mut input = String::new();
+        read.read_line(&mut input)?;
+
+        let answer = input.trim();
+        if answer.is_empty() || answer.eq_ignore_ascii_case("q") {
+            return Ok(None);
+        }
+
+        match answer.parse::<usize>() {
+            Ok(n) if (1..=options.len()).contains(&n) => Ok(Some(n - 1)),
+            _ => Err(InteractionError::InvalidSelection(answer.to_string())),
+        }
+    }
 }
 
 impl Default for TerminalInteraction {
@@ -37,9 +136,25 @@ impl Default for TerminalInteraction {
 
 impl UserInteraction for TerminalInteraction {
     fn confirm(&self, message: &str) -> Result<bool, InteractionError> {
+        Self::ensure_interactive()?;
         Self::confirm_stream(&mut io::stdin().lock(), &mut io::stdout(), message)
     }
 
+    fn confirm_strict(&self, message: &str) -> Result<bool, InteractionError> {
+        Self::ensure_interactive()?;
+        Self::confirm_strict_stream(&mut io::stdin().lock(), &mut io::stdout(), message)
+    }
+
+    fn input(&self, message: &str, default: Option<&str>) -> Result<String, InteractionError> {
+        Self::ensure_interactive()?;
+        Self::input_stream(&mut io::stdin().lock(), &mut io::stdout(), message, default)
+    }
+
+    fn select(&self, message: &str, options: &[String]) -> Result<Option<usize>, InteractionError> {
+        Self::ensure_interactive()?;
+        Self::select_stream(&mut io::stdin().lock(), &mut io::stdout(), message, options)
+    }
+
     fn print(&self, message: &str) {
         println!("{message}");
     }
@@ -47,6 +162,10 @@ impl UserInteraction for TerminalInteraction {
     fn print_error(&self, message: &str) {
         eprintln!("{message}");
     }
+
+    fn print_warning(&self, message: &str) {
+        eprintln!("warning: {message}");
+    }
 }
 
 #[cfg(test)]
@@ -104,4 +223,147 @@ mod tests {
         let result = TerminalInteraction::confirm_stream(&mut reader, &mut writer, "Continue?");
         assert!(!result.unwrap());
     }
+
+    #[test]
+    fn test_confirm_strict_retries_on_typo() {
+        let input = b"yez\nyes\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result =
+            TerminalInteraction::confirm_strict_stream(&mut reader, &mut writer, "Delete?");
+        assert!(result.unwrap());
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "Delete? [y/n]: Please answer y or n.\nDelete? [y/n]: "
+        );
+    }
+
+    #[test]
+    fn test_confirm_strict_no() {
+        let input = b"\nNo\ny\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result =
+            TerminalInteraction::confirm_strict_stream(&mut reader, &mut writer, "Delete?");
+        assert!(!result.unwrap());
+    }
+
+    #[test]
+    fn test_confirm_strict_gives_up() {
+        let input = b"a\nb\nc\ny\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result =
+            TerminalInteraction::confirm_strict_stream(&mut reader, &mut writer, "Delete?");
+        assert!(!result.unwrap());
+        assert_eq!(
+            String::from_utf8(writer)
+                .unwrap()
+                .matches("Delete? [y/n]: ")
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_confirm_strict_eof() {
+        let input = b"";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result =
+            TerminalInteraction::confirm_strict_stream(&mut reader, &mut writer, "Delete?");
+        assert!(!result.unwrap());
+        assert_eq!(String::from_utf8(writer).unwrap(), "Delete? [y/n]: \n");
+    }
+
+    #[test]
+    fn test_input_value() {
+        let input = b"  hello  \n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result = TerminalInteraction::input_stream(&mut reader, &mut writer, "Name", None);
+        assert_eq!(result.unwrap(), "hello");
+        assert_eq!(String::from_utf8(writer).unwrap(), "Name: ");
+    }
+
+    #[test]
+    fn test_input_default_on_empty() {
+        let input = b"\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result =
+            TerminalInteraction::input_stream(&mut reader, &mut writer, "Root", Some("~/grm"));
+        assert_eq!(result.unwrap(), "~/grm");
+        assert_eq!(String::from_utf8(writer).unwrap(), "Root [~/grm]: ");
+    }
+
+    #[test]
+    fn test_input_empty_without_default() {
+        let input = b"";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+
+        let result = TerminalInteraction::input_stream(&mut reader, &mut writer, "Name", None);
+        assert_eq!(result.unwrap(), "");
+    }
+
+    #[test]
+    fn test_select_valid() {
+        let input = b"2\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+        let options = vec!["a".to_string(), "b".to_string()];
+
+        let result =
+            TerminalInteraction::select_stream(&mut reader, &mut writer, "Select", &options);
+        assert_eq!(result.unwrap(), Some(1));
+        assert_eq!(
+            String::from_utf8(writer).unwrap(),
+            "  1) a\n  2) b\nSelect [1-2, q to cancel]: "
+        );
+    }
+
+    #[test]
+    fn test_select_cancel() {
+        let options = vec!["a".to_string(), "b".to_string()];
+
+        for input in [&b"\n"[..], b"q\n", b"Q\n", b""] {
+            let mut reader = Cursor::new(input);
+            let mut writer = Vec::new();
+
+            let result =
+                TerminalInteraction::select_stream(&mut reader, &mut writer, "Select", &options);
+            assert_eq!(result.unwrap(), None);
+        }
+    }
+
+    #[test]
+    fn test_select_out_of_range() {
+        let input = b"3\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+        let options = vec!["a".to_string(), "b".to_string()];
+
+        let result =
+            TerminalInteraction::select_stream(&mut reader, &mut writer, "Select", &options);
+        assert!(matches!(result, Err(InteractionError::InvalidSelection(_))));
+    }
+
+    #[test]
+    fn test_select_not_a_number() {
+        let input = b"a\n";
+        let mut reader = Cursor::new(&input[..]);
+        let mut writer = Vec::new();
+        let options = vec!["a".to_string()];
+
+        let result =
+            TerminalInteraction::select_stream(&mut reader, &mut writer, "Select", &options);
+        assert!(matches!(result, Err(InteractionError::InvalidSelection(_))));
+    }
 }
@@ -0,0 +1,44 @@
+//! Mock clock for testing
+//!
+//! Returns a fixed time which only moves when a test advances it or sleeps.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::core::ports::Clock;
+
+/// Mock clock for testing
+pub struct MockClock {
+    now: Mutex<SystemTime>,
+}
+
+impl MockClock {
+    /// Start at 2025-10-09 08:53:20 UTC (`1760000000` seconds since the Unix epoch)
+    pub fn new() -> Self {
+        Self {
+            now: Mutex::new(UNIX_EPOCH + Duration::from_secs(1_760_000_000)),
+        }
+    }
+
+    /// Move the time returned by `now` forward
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+
+    /// Advance the time instead of blocking
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
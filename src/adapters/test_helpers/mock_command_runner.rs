@@ -0,0 +1,114 @@
+//! Mock command runner for testing
+//!
+//! Records spawned programs instead of executing them.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::core::ports::{CommandError, CommandRunner, OutputStream};
+
+/// A program invocation recorded by `MockCommandRunner`
+#[derive(Debug, Clone)]
+pub struct RecordedCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub current_dir: PathBuf,
+    pub envs: Vec<(String, String)>,
+}
+
+/// Mock command runner for testing
+pub struct MockCommandRunner {
+    exit_code: Mutex<i32>,
+    exit_codes_at: Mutex<HashMap<PathBuf, i32>>,
+    output: Mutex<Vec<(OutputStream, String)>>,
+    commands: Mutex<Vec<RecordedCommand>>,
+}
+
+impl MockCommandRunner {
+    pub fn new() -> Self {
+        Self {
+            exit_code: Mutex::new(0),
+            exit_codes_at: Mutex::new(HashMap::new()),
+            output: Mutex::new(Vec::new()),
+            commands: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set the exit code returned by subsequent runs
+    pub fn set_exit_code(&self, code: i32) {
+        *self.exit_code.lock().unwrap() = code;
+    }
+
+    /// Set the exit code returned by runs in `current_dir`
+    pub fn set_exit_code_at(&self, current_dir: impl AsRef<Path>, code: i32) {
+        self.exit_codes_at
+            .lock()
+            .unwrap()
+            .insert(current_dir.as_ref().to_path_buf(), code);
+    }
+
+    /// Add a line reported by every captured run
+    pub fn add_output(&self, stream: OutputStream, line: impl Into<String>) {
+        self.output.lock().unwrap().push((stream, line.into()));
+    }
+
+    fn record(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        envs: &[(String, String)],
+    ) -> i32 {
+        self.commands.lock().unwrap().push(RecordedCommand {
+            program: program.to_string(),
+            args: args.to_vec(),
+            current_dir: current_dir.to_path_buf(),
+            envs: envs.to_vec(),
+        });
+
+        self.exit_codes_at
+            .lock()
+            .unwrap()
+            .get(current_dir)
+            .copied()
+            .unwrap_or(*self.exit_code.lock().unwrap())
+    }
+
+    /// Get the recorded invocations (for assertions)
+    pub fn get_commands(&self) -> Vec<RecordedCommand> {
+        self.commands.lock().unwrap().clone()
+    }
+}
+
+impl Default for MockCommandRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandRunner for MockCommandRunner {
+    fn run(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        envs: &[(String, String)],
+    ) -> Result<i32, CommandError> {
+        Ok(self.record(program, args, current_dir, envs))
+    }
+
+    fn run_captured(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> Result<i32, CommandError> {
+        for (stream, line) in self.output.lock().unwrap().iter() {
+            on_output(*stream, line);
+        }
+
+        Ok(self.record(program, args, current_dir, &[]))
+    }
+}
@@ -2,9 +2,10 @@
 //!
 //! Provides an in-memory filesystem simulation with basic operations.
 
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::core::ports::{FileSystem, FileSystemError};
 
@@ -14,16 +15,21 @@ struct MockFsEntry {
     is_symlink: bool,
     is_dir: bool,
     target: Option<PathBuf>, // For symlinks
+    size: u64,
+    content: String,
 }
 
 /// Mock filesystem for testing
 ///
 /// Provides an in-memory filesystem simulation with basic operations.
 pub struct MockFileSystem {
-    entries: RefCell<HashMap<PathBuf, MockFsEntry>>,
+    entries: Mutex<HashMap<PathBuf, MockFsEntry>>,
     home_dir: PathBuf,
-    current_dir: RefCell<PathBuf>,
-    force_error: RefCell<Option<FileSystemError>>,
+    current_dir: Mutex<PathBuf>,
+    canonical: Mutex<HashMap<PathBuf, PathBuf>>,
+    modified: Mutex<HashMap<PathBuf, SystemTime>>,
+    force_error: Mutex<VecDeque<Option<FileSystemError>>>,
+    symlink_error: Mutex<VecDeque<Option<FileSystemError>>>,
 }
 
 impl MockFileSystem {
@@ -35,14 +41,19 @@ impl MockFileSystem {
             is_symlink: false,
             is_dir: true,
             target: None,
+            size: 0,
+            content: String::new(),
         };
         entries.insert(PathBuf::from("/"), root_entry);
 
         Self {
-            entries: RefCell::new(entries),
+            entries: Mutex::new(entries),
             home_dir: PathBuf::from("/home/testuser"),
-            current_dir: RefCell::new(PathBuf::from("/home/testuser/work")),
-            force_error: RefCell::new(None),
+            current_dir: Mutex::new(PathBuf::from("/home/testuser/work")),
+            canonical: Mutex::new(HashMap::new()),
+            modified: Mutex::new(HashMap::new()),
+            force_error: Mutex::new(VecDeque::new()),
+            symlink_error: Mutex::new(VecDeque::new()),
         }
     }
 
@@ -53,8 +64,39 @@ impl MockFileSystem {
             is_symlink: false,
             is_dir: false,
             target: None,
+            size: 0,
+            content: String::new(),
         };
-        self.entries.borrow_mut().insert(path, entry);
+        self.entries.lock().unwrap().insert(path, entry);
+    }
+
+    /// Add a file with the given size in bytes to the mock filesystem
+    pub fn add_file_with_size(&self, path: impl AsRef<Path>, size: u64) {
+        let path = path.as_ref().to_path_buf();
+        let entry = MockFsEntry {
+            is_symlink: false,
+            is_dir: false,
+            target: None,
+            size,
+            content: String::new(),
+        };
+        self.entries.lock().unwrap().insert(path, entry);
+    }
+
+    /// Add a file with the given text content to the mock filesystem
+    pub fn add_file_with_content(&self, path: impl AsRef<Path>, content: impl Into<String>) {
+        let content = content.into();
+        let entry = MockFsEntry {
+            is_symlink: false,
+            is_dir: false,
+            target: None,
+            size: content.len() as u64,
+            content,
+        };
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), entry);
     }
 
     /// Add a directory to the mock filesystem
@@ -64,8 +106,18 @@ impl MockFileSystem {
             is_symlink: false,
             is_dir: true,
             target: None,
+            size: 0,
+            content: String::new(),
         };
-        self.entries.borrow_mut().insert(path, entry);
+        self.entries.lock().unwrap().insert(path, entry);
+    }
+
+    /// Set the time `modified` returns for `path`, the Unix epoch unless set
+    pub fn set_modified(&self, path: impl AsRef<Path>, time: SystemTime) {
+        self.modified
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), time);
     }
 
     /// Add a git repository to the mock filesystem
@@ -76,6 +128,24 @@ impl MockFileSystem {
         self.add_dir(&git_path);
     }
 
+    /// Add a bare repository, which has `HEAD` and `objects` instead of `.git`
+    pub fn add_bare_repo(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref().to_path_buf();
+        self.add_dir(&path);
+        self.add_file_with_content(path.join("HEAD"), "ref: refs/heads/main\n");
+        self.add_dir(path.join("objects"));
+    }
+
+    /// Add a linked worktree whose `.git` is a file pointing at `gitdir`
+    pub fn add_git_worktree(&self, path: impl AsRef<Path>, gitdir: impl AsRef<Path>) {
+        let path = path.as_ref().to_path_buf();
+        self.add_dir(&path);
+        self.add_file_with_content(
+            path.join(".git"),
+            format!("gitdir: {}\n", gitdir.as_ref().display()),
+        );
+    }
+
     /// Add a symlink to the mock filesystem
     pub fn add_symlink(&self, link: impl AsRef<Path>, target: impl AsRef<Path>) {
         let link = link.as_ref().to_path_buf();
@@ -84,13 +154,25 @@ impl MockFileSystem {
             is_symlink: true,
             is_dir: false,
             target: Some(target),
+            size: 0,
+            content: String::new(),
         };
-        self.entries.borrow_mut().insert(link, entry);
+        self.entries.lock().unwrap().insert(link, entry);
+    }
+
+    /// Make `canonicalize` resolve `path` to `canonical` although it is no symlink
+    ///
+    /// This stands in for a bind mount or a Windows junction.
+    pub fn set_canonical(&self, path: impl AsRef<Path>, canonical: impl AsRef<Path>) {
+        self.canonical.lock().unwrap().insert(
+            path.as_ref().to_path_buf(),
+            canonical.as_ref().to_path_buf(),
+        );
     }
 
     /// Set the current directory for testing
     pub fn set_current_dir(&self, path: impl AsRef<Path>) {
-        *self.current_dir.borrow_mut() = path.as_ref().to_path_buf();
+        *self.current_dir.lock().unwrap() = path.as_ref().to_path_buf();
     }
 
     /// Set the home directory for testing
@@ -99,12 +181,30 @@ impl MockFileSystem {
     }
 
     /// Inject an error to be returned on the next operation
+    ///
+    /// Errors injected one after another are returned by consecutive operations.
     pub fn inject_error(&self, error: FileSystemError) {
-        *self.force_error.borrow_mut() = Some(error);
+        self.force_error.lock().unwrap().push_back(Some(error));
+    }
+
+    /// Inject an error to be returned after `skip` more operations succeed
+    pub fn inject_error_after(&self, skip: usize, error: FileSystemError) {
+        let mut queue = self.force_error.lock().unwrap();
+        queue.extend(std::iter::repeat_with(|| None).take(skip));
+        queue.push_back(Some(error));
+    }
+
+    /// Inject an error to be returned by `create_symlink` after `skip` more symlinks are created
+    ///
+    /// Unlike `inject_error_after`, other operations do not consume the queue.
+    pub fn inject_symlink_error_after(&self, skip: usize, error: FileSystemError) {
+        let mut queue = self.symlink_error.lock().unwrap();
+        queue.extend(std::iter::repeat_with(|| None).take(skip));
+        queue.push_back(Some(error));
     }
 
     fn check_error(&self) -> Result<(), FileSystemError> {
-        if let Some(err) = self.force_error.borrow_mut().take() {
+        if let Some(Some(err)) = self.force_error.lock().unwrap().pop_front() {
             return Err(err);
         }
         Ok(())
@@ -119,23 +219,36 @@ impl Default for MockFileSystem {
 
 impl FileSystem for MockFileSystem {
     fn exists(&self, path: &Path) -> bool {
-        self.entries.borrow().contains_key(path)
+        self.entries.lock().unwrap().contains_key(path)
     }
 
     fn is_symlink(&self, path: &Path) -> bool {
         self.entries
-            .borrow()
+            .lock()
+            .unwrap()
             .get(path)
             .is_some_and(|e| e.is_symlink)
     }
 
     fn is_dir(&self, path: &Path) -> bool {
-        self.entries.borrow().get(path).is_some_and(|e| e.is_dir)
+        self.entries
+            .lock()
+            .unwrap()
+            .get(path)
+            .is_some_and(|e| e.is_dir)
     }
 
     fn is_git_repository(&self, path: &Path) -> bool {
-        let git_path = path.join(".git");
-        self.exists(&git_path)
+        // Like UnixFs, a `.git` directory or file counts and a dangling symlink does not
+        let entries = self.entries.lock().unwrap();
+        match entries.get(&path.join(".git")) {
+            Some(entry) if entry.is_symlink => entry
+                .target
+                .as_ref()
+                .is_some_and(|target| entries.contains_key(&path.join(target))),
+            Some(_) => true,
+            None => false,
+        }
     }
 
     fn home_dir(&self) -> Result<PathBuf, FileSystemError> {
@@ -145,13 +258,13 @@ impl FileSystem for MockFileSystem {
 
     fn current_dir(&self) -> Result<PathBuf, FileSystemError> {
         self.check_error()?;
-        Ok(self.current_dir.borrow().clone())
+        Ok(self.current_dir.lock().unwrap().clone())
     }
 
     fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FileSystemError> {
         self.check_error()?;
 
-        let entries = self.entries.borrow();
+        let entries = self.entries.lock().unwrap();
 
         // Check if the path exists and is a directory
         if !entries.contains_key(path) {
@@ -194,14 +307,81 @@ impl FileSystem for MockFileSystem {
 
     fn create_symlink(&self, target: &Path, link: &Path) -> Result<(), FileSystemError> {
         self.check_error()?;
+        if let Some(Some(err)) = self.symlink_error.lock().unwrap().pop_front() {
+            return Err(err);
+        }
         self.add_symlink(link, target);
         Ok(())
     }
 
+    fn read_link(&self, link: &Path) -> Result<PathBuf, FileSystemError> {
+        self.check_error()?;
+
+        self.entries
+            .lock()
+            .unwrap()
+            .get(link)
+            .and_then(|e| e.target.clone())
+            .ok_or_else(|| FileSystemError::PathError(format!("Not a symlink: {}", link.display())))
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, FileSystemError> {
+        // Not subject to injected errors, as scanners call it for every directory
+        let entries = self.entries.lock().unwrap();
+        let canonical = self.canonical.lock().unwrap();
+        let mut current = path.to_path_buf();
+        // Follow at most as many links as there are entries, anything longer is a loop
+        for _ in 0..=entries.len() {
+            if let Some(resolved) = canonical.get(&current) {
+                return Ok(resolved.clone());
+            }
+            match entries.get(&current) {
+                Some(entry) if entry.is_symlink => {
+                    let target = entry.target.clone().unwrap_or_default();
+                    current = current.parent().unwrap_or(Path::new("/")).join(target);
+                }
+                Some(_) => return Ok(current),
+                None => {
+                    return Err(FileSystemError::Io(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("No such file or directory: {}", path.display()),
+                    )));
+                }
+            }
+        }
+
+        Err(FileSystemError::PathError(format!(
+            "Too many levels of symbolic links: {}",
+            path.display()
+        )))
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, FileSystemError> {
+        self.check_error()?;
+
+        match self.entries.lock().unwrap().get(path) {
+            Some(entry) if !entry.is_dir => Ok(entry.content.clone()),
+            Some(_) => Err(FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::IsADirectory,
+                "Is a directory",
+            ))),
+            None => Err(FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "File not found",
+            ))),
+        }
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<(), FileSystemError> {
+        self.check_error()?;
+        self.add_file_with_content(path, content);
+        Ok(())
+    }
+
     fn copy(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
         self.check_error()?;
 
-        let entries = self.entries.borrow();
+        let entries = self.entries.lock().unwrap();
         let entry = entries
             .get(from)
             .ok_or_else(|| {
@@ -214,7 +394,11 @@ impl FileSystem for MockFileSystem {
 
         drop(entries);
 
-        if entry.is_dir {
+        if entry.is_symlink {
+            // Copy the link itself like UnixFs, never what it points to
+            let target = entry.target.unwrap_or_default();
+            self.create_symlink(&target, to)?;
+        } else if entry.is_dir {
             // Recursive copy for directories
             self.create_dir(to)?;
             let children = self.read_dir(from)?;
@@ -225,7 +409,7 @@ impl FileSystem for MockFileSystem {
             }
         } else {
             // File copy
-            self.entries.borrow_mut().insert(to.to_path_buf(), entry);
+            self.entries.lock().unwrap().insert(to.to_path_buf(), entry);
         }
 
         Ok(())
@@ -234,7 +418,7 @@ impl FileSystem for MockFileSystem {
     fn rename(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
         self.check_error()?;
 
-        let mut entries = self.entries.borrow_mut();
+        let mut entries = self.entries.lock().unwrap();
 
         // For directories, we need to rename all children as well
         let is_dir = entries.get(from).is_some_and(|e| e.is_dir);
@@ -274,7 +458,7 @@ impl FileSystem for MockFileSystem {
     fn remove(&self, path: &Path) -> Result<(), FileSystemError> {
         self.check_error()?;
 
-        let mut entries = self.entries.borrow_mut();
+        let mut entries = self.entries.lock().unwrap();
 
         // Remove path and all children
         let to_remove: Vec<PathBuf> = entries
@@ -290,6 +474,64 @@ impl FileSystem for MockFileSystem {
         Ok(())
     }
 
+    fn remove_empty_dir(&self, path: &Path) -> Result<(), FileSystemError> {
+        self.check_error()?;
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.keys().any(|p| p.parent() == Some(path)) {
+            return Err(FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::DirectoryNotEmpty,
+                "Directory not empty",
+            )));
+        }
+
+        entries.remove(path).ok_or_else(|| {
+            FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Directory not found",
+            ))
+        })?;
+
+        Ok(())
+    }
+
+    fn size(&self, path: &Path) -> Result<u64, FileSystemError> {
+        self.check_error()?;
+
+        let entries = self.entries.lock().unwrap();
+        if !entries.contains_key(path) {
+            return Err(FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Path not found",
+            )));
+        }
+
+        Ok(entries
+            .iter()
+            .filter(|(p, _)| p.starts_with(path))
+            .map(|(_, e)| e.size)
+            .sum())
+    }
+
+    fn modified(&self, path: &Path) -> Result<SystemTime, FileSystemError> {
+        self.check_error()?;
+
+        if !self.entries.lock().unwrap().contains_key(path) {
+            return Err(FileSystemError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Path not found",
+            )));
+        }
+
+        Ok(self
+            .modified
+            .lock()
+            .unwrap()
+            .get(path)
+            .copied()
+            .unwrap_or(UNIX_EPOCH))
+    }
+
     fn normalize(&self, path: &Path, base: &Path) -> Result<PathBuf, FileSystemError> {
         self.check_error()?;
 
@@ -299,21 +541,43 @@ impl FileSystem for MockFileSystem {
             ));
         }
 
-        let path_str = path.to_string_lossy();
+        // Walks the components exactly like `UnixFs::normalize`, so that tests
+        // see the same `~`, `.` and `..` handling as the real adapter
+        let mut normalized = PathBuf::new();
+        let mut first = true;
 
-        // Tilde expansion
-        if path_str.starts_with('~') {
-            let home = self.home_dir()?;
-            let without_tilde = path_str.trim_start_matches("~/").trim_start_matches('~');
-            return Ok(home.join(without_tilde));
+        for component in path.components() {
+            match component {
+                Component::RootDir => normalized.push(component),
+                Component::Normal(stem) if stem == "~" => {
+                    normalized.clear();
+                    normalized.push(self.home_dir()?);
+                }
+                Component::Normal(_) => {
+                    if first {
+                        normalized.push(base);
+                    }
+                    normalized.push(component);
+                }
+                Component::Prefix(_) | Component::CurDir => continue,
+                Component::ParentDir => {
+                    if first {
+                        normalized.push(base);
+                    }
+                    normalized.pop();
+                }
+            }
+            first = false;
         }
 
-        // Absolute path
-        if path.is_absolute() {
-            return Ok(path.to_path_buf());
+        if first {
+            return Ok(base.to_path_buf());
         }
 
-        // Relative path
-        Ok(base.join(path))
+        Ok(normalized)
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        Some(self)
     }
 }
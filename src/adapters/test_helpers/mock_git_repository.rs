@@ -2,60 +2,164 @@
 //!
 //! Provides a mock implementation of Git operations for testing.
 
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
-use crate::core::ports::{GitError, GitRepository};
+use super::MockFileSystem;
+use crate::core::ports::{
+    DEFAULT_REMOTE, FileSystem, GitError, GitRepository, WorktreeBranch, WorktreeInfo,
+};
 
 /// Mock Git repository for testing
 pub struct MockGitRepository {
-    repo_root: RefCell<Option<PathBuf>>,
-    default_branches: RefCell<HashMap<String, String>>,
-    remote_urls: RefCell<HashMap<PathBuf, String>>,
-    local_branches: RefCell<Vec<String>>,
-    remote_branches: RefCell<HashMap<String, Vec<String>>>,
-    cloned_repos: RefCell<Vec<(String, PathBuf)>>,
-    worktrees: RefCell<Vec<PathBuf>>,
-    force_error: RefCell<Option<GitError>>,
+    repo_root: Mutex<Option<PathBuf>>,
+    repo_roots_at: Mutex<HashMap<PathBuf, PathBuf>>,
+    default_branches: Mutex<HashMap<String, String>>,
+    forgotten_default_branches: Mutex<Vec<String>>,
+    remote_urls: Mutex<HashMap<(PathBuf, String), String>>,
+    current_branches: Mutex<HashMap<PathBuf, String>>,
+    dirty: Mutex<Vec<PathBuf>>,
+    last_commits: Mutex<HashMap<PathBuf, SystemTime>>,
+    local_branches: Mutex<Vec<String>>,
+    remote_branches: Mutex<HashMap<String, Vec<String>>>,
+    cloned_repos: Mutex<Vec<(String, PathBuf)>>,
+    clone_depths: Mutex<Vec<Option<u32>>>,
+    submodule_clones: Mutex<Vec<PathBuf>>,
+    sparse_paths: Mutex<HashMap<PathBuf, Vec<String>>>,
+    bare_clones: Mutex<Vec<(String, PathBuf, bool)>>,
+    worktrees: Mutex<Vec<PathBuf>>,
+    worktree_branches: Mutex<HashMap<PathBuf, String>>,
+    detached_worktrees: Mutex<Vec<(PathBuf, String)>>,
+    tracked_worktrees: Mutex<Vec<(PathBuf, String)>>,
+    fetched: Mutex<Vec<PathBuf>>,
+    pulled: Mutex<Vec<PathBuf>>,
+    diverged: Mutex<Vec<PathBuf>>,
+    renamed_branches: Mutex<Vec<(String, String)>>,
+    deleted_branches: Mutex<Vec<String>>,
+    repaired: Mutex<Vec<PathBuf>>,
+    worktree_details: Mutex<Vec<WorktreeInfo>>,
+    worktree_details_at: Mutex<HashMap<PathBuf, Vec<WorktreeInfo>>>,
+    version: Mutex<Option<String>>,
+    partial_clone_fs: Mutex<Option<Arc<MockFileSystem>>>,
+    clone_errors: Mutex<VecDeque<GitError>>,
+    force_error: Mutex<Option<GitError>>,
 }
 
 impl MockGitRepository {
     pub fn new() -> Self {
         Self {
-            repo_root: RefCell::new(None),
-            default_branches: RefCell::new(HashMap::new()),
-            remote_urls: RefCell::new(HashMap::new()),
-            local_branches: RefCell::new(Vec::new()),
-            remote_branches: RefCell::new(HashMap::new()),
-            cloned_repos: RefCell::new(Vec::new()),
-            worktrees: RefCell::new(Vec::new()),
-            force_error: RefCell::new(None),
+            repo_root: Mutex::new(None),
+            repo_roots_at: Mutex::new(HashMap::new()),
+            default_branches: Mutex::new(HashMap::new()),
+            forgotten_default_branches: Mutex::new(Vec::new()),
+            remote_urls: Mutex::new(HashMap::new()),
+            current_branches: Mutex::new(HashMap::new()),
+            dirty: Mutex::new(Vec::new()),
+            last_commits: Mutex::new(HashMap::new()),
+            local_branches: Mutex::new(Vec::new()),
+            remote_branches: Mutex::new(HashMap::new()),
+            cloned_repos: Mutex::new(Vec::new()),
+            clone_depths: Mutex::new(Vec::new()),
+            submodule_clones: Mutex::new(Vec::new()),
+            sparse_paths: Mutex::new(HashMap::new()),
+            bare_clones: Mutex::new(Vec::new()),
+            worktrees: Mutex::new(Vec::new()),
+            worktree_branches: Mutex::new(HashMap::new()),
+            detached_worktrees: Mutex::new(Vec::new()),
+            tracked_worktrees: Mutex::new(Vec::new()),
+            fetched: Mutex::new(Vec::new()),
+            pulled: Mutex::new(Vec::new()),
+            diverged: Mutex::new(Vec::new()),
+            renamed_branches: Mutex::new(Vec::new()),
+            deleted_branches: Mutex::new(Vec::new()),
+            repaired: Mutex::new(Vec::new()),
+            worktree_details: Mutex::new(Vec::new()),
+            worktree_details_at: Mutex::new(HashMap::new()),
+            version: Mutex::new(Some("2.43.0".to_string())),
+            partial_clone_fs: Mutex::new(None),
+            clone_errors: Mutex::new(VecDeque::new()),
+            force_error: Mutex::new(None),
         }
     }
 
     /// Set the repository root for testing
     pub fn set_repo_root(&self, path: impl AsRef<Path>) {
-        *self.repo_root.borrow_mut() = Some(path.as_ref().to_path_buf());
+        *self.repo_root.lock().unwrap() = Some(path.as_ref().to_path_buf());
+    }
+
+    /// Set the repository root resolved from a given directory
+    pub fn set_repo_root_at(&self, path: impl AsRef<Path>, root: impl AsRef<Path>) {
+        self.repo_roots_at
+            .lock()
+            .unwrap()
+            .insert(path.as_ref().to_path_buf(), root.as_ref().to_path_buf());
     }
 
     /// Set the default branch for a URL
     pub fn set_default_branch(&self, url: impl Into<String>, branch: impl Into<String>) {
         self.default_branches
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .insert(url.into(), branch.into());
     }
 
-    /// Set the remote URL for a repository
+    /// Set the URL of `origin` for a repository
     pub fn set_remote_url(&self, repo_path: impl AsRef<Path>, url: impl Into<String>) {
-        self.remote_urls
-            .borrow_mut()
-            .insert(repo_path.as_ref().to_path_buf(), url.into());
+        self.set_named_remote_url(repo_path, DEFAULT_REMOTE, url);
+    }
+
+    /// Set the URL of a named remote for a repository
+    pub fn set_named_remote_url(
+        &self,
+        repo_path: impl AsRef<Path>,
+        remote: &str,
+        url: impl Into<String>,
+    ) {
+        self.remote_urls.lock().unwrap().insert(
+            (repo_path.as_ref().to_path_buf(), remote.to_string()),
+            url.into(),
+        );
+    }
+
+    /// Set the branch checked out at a repository path
+    pub fn set_current_branch(&self, repo_path: impl AsRef<Path>, branch: impl Into<String>) {
+        self.current_branches
+            .lock()
+            .unwrap()
+            .insert(repo_path.as_ref().to_path_buf(), branch.into());
+    }
+
+    /// Mark the working tree at a repository path as having uncommitted changes
+    pub fn set_dirty(&self, repo_path: impl AsRef<Path>) {
+        self.dirty
+            .lock()
+            .unwrap()
+            .push(repo_path.as_ref().to_path_buf());
+    }
+
+    /// Set the time `last_commit_timestamp` returns for a repository path
+    ///
+    /// Repositories without one fail as if they had no commits.
+    pub fn set_last_commit(&self, repo_path: impl AsRef<Path>, time: SystemTime) {
+        self.last_commits
+            .lock()
+            .unwrap()
+            .insert(repo_path.as_ref().to_path_buf(), time);
+    }
+
+    /// Make `pull` at a repository path fail as if its branch had diverged from upstream
+    pub fn set_diverged(&self, repo_path: impl AsRef<Path>) {
+        self.diverged
+            .lock()
+            .unwrap()
+            .push(repo_path.as_ref().to_path_buf());
     }
 
     /// Add a local branch
     pub fn add_local_branch(&self, branch: impl Into<String>) {
-        self.local_branches.borrow_mut().push(branch.into());
+        self.local_branches.lock().unwrap().push(branch.into());
     }
 
     /// Add a remote branch
@@ -63,29 +167,143 @@ impl MockGitRepository {
         let url = url.into();
         let branch = branch.into();
         self.remote_branches
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .entry(url)
             .or_default()
             .push(branch);
     }
 
+    /// Make `version` fail as if git were not installed
+    pub fn set_git_missing(&self) {
+        *self.version.lock().unwrap() = None;
+    }
+
+    /// Make clones create their destination in `fs` before checking for an injected error
+    ///
+    /// Simulates a `git clone` which fails after it has started writing the destination.
+    pub fn set_partial_clone(&self, fs: Arc<MockFileSystem>) {
+        *self.partial_clone_fs.lock().unwrap() = Some(fs);
+    }
+
     /// Inject an error to be returned on the next operation
     pub fn inject_error(&self, error: GitError) {
-        *self.force_error.borrow_mut() = Some(error);
+        *self.force_error.lock().unwrap() = Some(error);
+    }
+
+    /// Make the next clones fail with `errors`, one error per clone
+    ///
+    /// Unlike `inject_error`, other operations such as the default-branch lookup are unaffected.
+    pub fn fail_next_clones(&self, errors: impl IntoIterator<Item = GitError>) {
+        self.clone_errors.lock().unwrap().extend(errors);
     }
 
     /// Get the list of cloned repositories (for assertions)
     pub fn get_cloned_repos(&self) -> Vec<(String, PathBuf)> {
-        self.cloned_repos.borrow().clone()
+        self.cloned_repos.lock().unwrap().clone()
+    }
+
+    /// Get the URLs `forget_default_branch` was called for (for assertions)
+    pub fn get_forgotten_default_branches(&self) -> Vec<String> {
+        self.forgotten_default_branches.lock().unwrap().clone()
+    }
+
+    /// Get the `(url, destination, mirror)` of each bare clone (for assertions)
+    pub fn get_bare_clones(&self) -> Vec<(String, PathBuf, bool)> {
+        self.bare_clones.lock().unwrap().clone()
+    }
+
+    /// Get the sparse-checkout paths `destination` was cloned with (for assertions)
+    pub fn get_sparse_paths(&self, destination: &Path) -> Option<Vec<String>> {
+        self.sparse_paths.lock().unwrap().get(destination).cloned()
+    }
+
+    /// Get the depth passed to each clone (for assertions)
+    pub fn get_clone_depths(&self) -> Vec<Option<u32>> {
+        self.clone_depths.lock().unwrap().clone()
+    }
+
+    /// Get the destinations of clones made with `recurse_submodules` (for assertions)
+    pub fn get_submodule_clones(&self) -> Vec<PathBuf> {
+        self.submodule_clones.lock().unwrap().clone()
+    }
+
+    /// Add a worktree entry returned by `worktree_details`
+    ///
+    /// Without any, `worktree_details` reports the repository root and the
+    /// worktrees added through the port, as git would.
+    pub fn add_worktree_detail(&self, info: WorktreeInfo) {
+        self.worktree_details.lock().unwrap().push(info);
+    }
+
+    /// Add a worktree entry returned by `worktree_details` for `repo_path` only
+    ///
+    /// Repositories with such entries ignore the ones added by `add_worktree_detail`.
+    pub fn add_worktree_detail_at(&self, repo_path: impl AsRef<Path>, info: WorktreeInfo) {
+        self.worktree_details_at
+            .lock()
+            .unwrap()
+            .entry(repo_path.as_ref().to_path_buf())
+            .or_default()
+            .push(info);
     }
 
     /// Get the list of worktrees (for assertions)
     pub fn get_worktrees(&self) -> Vec<PathBuf> {
-        self.worktrees.borrow().clone()
+        self.worktrees.lock().unwrap().clone()
+    }
+
+    /// Get the `(path, commitish)` of worktrees added with a detached `HEAD` (for assertions)
+    ///
+    /// They are not part of `get_worktrees`.
+    pub fn get_detached_worktrees(&self) -> Vec<(PathBuf, String)> {
+        self.detached_worktrees.lock().unwrap().clone()
+    }
+
+    /// Get the `(path, upstream)` of worktrees whose branch was created to track a remote branch (for assertions)
+    ///
+    /// They are also part of `get_worktrees`.
+    pub fn get_tracked_worktrees(&self) -> Vec<(PathBuf, String)> {
+        self.tracked_worktrees.lock().unwrap().clone()
+    }
+
+    /// Get the repositories `fetch` was called for (for assertions)
+    pub fn get_fetched(&self) -> Vec<PathBuf> {
+        self.fetched.lock().unwrap().clone()
+    }
+
+    /// Get the `(old, new)` branch renames (for assertions)
+    pub fn get_renamed_branches(&self) -> Vec<(String, String)> {
+        self.renamed_branches.lock().unwrap().clone()
+    }
+
+    /// Get the branches `delete_branch` was called for (for assertions)
+    pub fn get_deleted_branches(&self) -> Vec<String> {
+        self.deleted_branches.lock().unwrap().clone()
+    }
+
+    /// Get the paths fast-forwarded by `pull` (for assertions)
+    pub fn get_pulled(&self) -> Vec<PathBuf> {
+        self.pulled.lock().unwrap().clone()
+    }
+
+    /// Get the worktrees `repair_worktrees` was called for (for assertions)
+    pub fn get_repaired(&self) -> Vec<PathBuf> {
+        self.repaired.lock().unwrap().clone()
+    }
+
+    fn start_clone(&self, destination: &Path) -> Result<(), GitError> {
+        if let Some(fs) = self.partial_clone_fs.lock().unwrap().as_ref() {
+            fs.create_dir(destination).unwrap();
+        }
+        match self.clone_errors.lock().unwrap().pop_front() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     fn check_error(&self) -> Result<(), GitError> {
-        if let Some(err) = self.force_error.borrow_mut().take() {
+        if let Some(err) = self.force_error.lock().unwrap().take() {
             return Err(err);
         }
         Ok(())
@@ -99,44 +317,110 @@ impl Default for MockGitRepository {
 }
 
 impl GitRepository for MockGitRepository {
+    fn version(&self) -> Result<String, GitError> {
+        self.check_error()?;
+
+        self.version
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| GitError::Execution("git: command not found".to_string()))
+    }
+
     fn get_default_branch(&self, url: &str) -> Result<String, GitError> {
         self.check_error()?;
 
         self.default_branches
-            .borrow()
+            .lock()
+            .unwrap()
             .get(url)
             .cloned()
-            .ok_or_else(|| GitError::Parse(format!("No default branch configured for {}", url)))
+            .ok_or_else(|| GitError::Parse(format!("No default branch configured for {url}")))
+    }
+
+    fn forget_default_branch(&self, url: &str) {
+        self.forgotten_default_branches
+            .lock()
+            .unwrap()
+            .push(url.to_string());
     }
 
     fn get_repository_root(&self) -> Result<PathBuf, GitError> {
         self.check_error()?;
 
         self.repo_root
-            .borrow()
+            .lock()
+            .unwrap()
             .clone()
             .ok_or_else(|| GitError::Parse("No repository root configured".into()))
     }
 
-    fn get_remote_url(&self, repo_path: &Path) -> Result<String, GitError> {
+    fn get_repository_root_at(&self, path: &Path) -> Result<PathBuf, GitError> {
+        self.check_error()?;
+
+        self.repo_roots_at
+            .lock()
+            .unwrap()
+            .get(path)
+            .cloned()
+            .ok_or_else(|| {
+                GitError::Parse(format!(
+                    "No repository root configured for {}",
+                    path.display()
+                ))
+            })
+    }
+
+    fn get_remote_url(&self, repo_path: &Path, remote: Option<&str>) -> Result<String, GitError> {
         self.check_error()?;
 
+        let remote = remote.unwrap_or(DEFAULT_REMOTE);
         self.remote_urls
-            .borrow()
-            .get(repo_path)
+            .lock()
+            .unwrap()
+            .get(&(repo_path.to_path_buf(), remote.to_string()))
             .cloned()
             .ok_or_else(|| {
                 GitError::Parse(format!(
-                    "No remote URL configured for {}",
+                    "No URL configured for remote {remote} of {}",
                     repo_path.display()
                 ))
             })
     }
 
-    fn local_branch_exists(&self, branch: &str) -> Result<bool, GitError> {
+    fn get_current_branch(&self, repo_path: &Path) -> Result<String, GitError> {
         self.check_error()?;
 
-        Ok(self.local_branches.borrow().contains(&branch.to_string()))
+        self.current_branches
+            .lock()
+            .unwrap()
+            .get(repo_path)
+            .cloned()
+            .ok_or_else(|| GitError::Failed {
+                status: 128,
+                stderr: format!("not a git repository: {}", repo_path.display()),
+            })
+    }
+
+    fn is_dirty(&self, repo_path: &Path) -> Result<bool, GitError> {
+        self.check_error()?;
+
+        Ok(self
+            .dirty
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|path| path == repo_path))
+    }
+
+    fn local_branch_exists(&self, _repo_path: &Path, branch: &str) -> Result<bool, GitError> {
+        self.check_error()?;
+
+        Ok(self
+            .local_branches
+            .lock()
+            .unwrap()
+            .contains(&branch.to_string()))
     }
 
     fn remote_branch_exists(&self, remote_url: &str, branch: &str) -> Result<bool, GitError> {
@@ -144,10 +428,10 @@ impl GitRepository for MockGitRepository {
 
         Ok(self
             .remote_branches
-            .borrow()
+            .lock()
+            .unwrap()
             .get(remote_url)
-            .map(|branches| branches.contains(&branch.to_string()))
-            .unwrap_or(false))
+            .is_some_and(|branches| branches.contains(&branch.to_string())))
     }
 
     fn clone_repository(
@@ -155,41 +439,235 @@ impl GitRepository for MockGitRepository {
         url: &str,
         destination: &Path,
         _branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
     ) -> Result<(), GitError> {
+        self.start_clone(destination)?;
         self.check_error()?;
 
         self.cloned_repos
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .push((url.to_string(), destination.to_path_buf()));
+        self.clone_depths.lock().unwrap().push(depth);
+        if recurse_submodules {
+            self.submodule_clones
+                .lock()
+                .unwrap()
+                .push(destination.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    fn clone_sparse(
+        &self,
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        paths: &[String],
+    ) -> Result<(), GitError> {
+        self.clone_repository(url, destination, branch, depth, false)?;
+
+        self.sparse_paths
+            .lock()
+            .unwrap()
+            .insert(destination.to_path_buf(), paths.to_vec());
+
+        Ok(())
+    }
+
+    fn clone_bare(&self, url: &str, destination: &Path, mirror: bool) -> Result<(), GitError> {
+        self.start_clone(destination)?;
+        self.check_error()?;
+
+        self.bare_clones
+            .lock()
+            .unwrap()
+            .push((url.to_string(), destination.to_path_buf(), mirror));
 
         Ok(())
     }
 
     fn add_worktree(
         &self,
+        _repo_path: &Path,
         worktree_path: &Path,
         branch: &str,
-        create_new: bool,
+        source: WorktreeBranch,
     ) -> Result<(), GitError> {
         self.check_error()?;
 
         self.worktrees
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .push(worktree_path.to_path_buf());
-
-        if create_new {
-            self.add_local_branch(branch);
+        self.worktree_branches
+            .lock()
+            .unwrap()
+            .insert(worktree_path.to_path_buf(), branch.to_string());
+
+        match source {
+            WorktreeBranch::Existing => {}
+            WorktreeBranch::New => self.add_local_branch(branch),
+            WorktreeBranch::Track(remote) => {
+                self.tracked_worktrees
+                    .lock()
+                    .unwrap()
+                    .push((worktree_path.to_path_buf(), format!("{remote}/{branch}")));
+                self.add_local_branch(branch);
+            }
         }
 
         Ok(())
     }
 
+    fn add_worktree_detached(
+        &self,
+        _repo_path: &Path,
+        worktree_path: &Path,
+        commitish: &str,
+    ) -> Result<(), GitError> {
+        self.check_error()?;
+
+        self.detached_worktrees
+            .lock()
+            .unwrap()
+            .push((worktree_path.to_path_buf(), commitish.to_string()));
+        Ok(())
+    }
+
     fn remove_worktree(&self, worktree_path: &Path) -> Result<(), GitError> {
         self.check_error()?;
 
-        let mut worktrees = self.worktrees.borrow_mut();
+        let mut worktrees = self.worktrees.lock().unwrap();
         worktrees.retain(|p| p != worktree_path);
+        self.detached_worktrees
+            .lock()
+            .unwrap()
+            .retain(|(p, _)| p != worktree_path);
 
         Ok(())
     }
+
+    fn fetch(&self, repo_path: &Path) -> Result<(), GitError> {
+        self.check_error()?;
+
+        self.fetched.lock().unwrap().push(repo_path.to_path_buf());
+
+        Ok(())
+    }
+
+    fn pull(&self, repo_path: &Path) -> Result<(), GitError> {
+        self.check_error()?;
+
+        if self.diverged.lock().unwrap().iter().any(|p| p == repo_path) {
+            return Err(GitError::NotFastForward(
+                "fatal: Not possible to fast-forward, aborting.".to_string(),
+            ));
+        }
+        self.pulled.lock().unwrap().push(repo_path.to_path_buf());
+
+        Ok(())
+    }
+
+    fn rename_branch(&self, _repo_path: &Path, old: &str, new: &str) -> Result<(), GitError> {
+        self.check_error()?;
+
+        self.renamed_branches
+            .lock()
+            .unwrap()
+            .push((old.to_string(), new.to_string()));
+
+        Ok(())
+    }
+
+    fn delete_branch(&self, _repo_path: &Path, branch: &str) -> Result<(), GitError> {
+        self.check_error()?;
+
+        self.deleted_branches
+            .lock()
+            .unwrap()
+            .push(branch.to_string());
+
+        Ok(())
+    }
+
+    fn repair_worktrees(&self, worktree_path: &Path) -> Result<(), GitError> {
+        self.check_error()?;
+
+        self.repaired
+            .lock()
+            .unwrap()
+            .push(worktree_path.to_path_buf());
+
+        Ok(())
+    }
+
+    fn last_commit_timestamp(&self, repo_path: &Path) -> Result<SystemTime, GitError> {
+        self.check_error()?;
+
+        self.last_commits
+            .lock()
+            .unwrap()
+            .get(repo_path)
+            .copied()
+            .ok_or_else(|| GitError::Failed {
+                status: 128,
+                stderr: "your current branch does not have any commits yet".to_string(),
+            })
+    }
+
+    fn worktree_details(&self, repo_path: &Path) -> Result<Vec<WorktreeInfo>, GitError> {
+        self.check_error()?;
+
+        if let Some(details) = self.worktree_details_at.lock().unwrap().get(repo_path) {
+            return Ok(details.clone());
+        }
+
+        let details = self.worktree_details.lock().unwrap().clone();
+        if !details.is_empty() {
+            return Ok(details);
+        }
+
+        // Nothing seeded: the main worktree first, then the added ones
+        let main = self
+            .repo_root
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| repo_path.to_path_buf());
+        let current_branches = self.current_branches.lock().unwrap();
+        let worktree_branches = self.worktree_branches.lock().unwrap();
+        let mut details = vec![WorktreeInfo {
+            branch: current_branches.get(&main).cloned(),
+            path: main,
+            ..WorktreeInfo::default()
+        }];
+        details.extend(
+            self.worktrees
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|path| WorktreeInfo {
+                    path: path.clone(),
+                    branch: worktree_branches.get(path).cloned(),
+                    ..WorktreeInfo::default()
+                }),
+        );
+        details.extend(
+            self.detached_worktrees
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|(path, commitish)| WorktreeInfo {
+                    path: path.clone(),
+                    head: Some(commitish.clone()),
+                    ..WorktreeInfo::default()
+                }),
+        );
+
+        Ok(details)
+    }
 }
@@ -2,20 +2,27 @@
 //!
 //! Provides a mock implementation of user interaction for testing.
 
-use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::Mutex;
 
 use crate::core::ports::{InteractionError, UserInteraction};
 
 /// Mock user interaction for testing
 pub struct MockUserInteraction {
-    confirm_responses: RefCell<Vec<bool>>,
-    printed_messages: RefCell<Vec<String>>,
-    error_messages: RefCell<Vec<String>>,
+    confirm_responses: Mutex<Vec<bool>>,
+    select_responses: Mutex<Vec<Option<usize>>>,
+    input_responses: Mutex<VecDeque<String>>,
+    input_prompts: Mutex<Vec<String>>,
+    printed_messages: Mutex<Vec<String>>,
+    error_messages: Mutex<Vec<String>>,
+    warning_messages: Mutex<Vec<String>>,
+    non_interactive: Mutex<bool>,
 }
 
 impl UserInteraction for MockUserInteraction {
     fn confirm(&self, _message: &str) -> Result<bool, InteractionError> {
-        let mut responses = self.confirm_responses.borrow_mut();
+        self.check_interactive()?;
+        let mut responses = self.confirm_responses.lock().unwrap();
 
         if let Some(response) = responses.pop() {
             Ok(response)
@@ -24,43 +31,134 @@ impl UserInteraction for MockUserInteraction {
         }
     }
 
+    fn input(&self, message: &str, default: Option<&str>) -> Result<String, InteractionError> {
+        self.check_interactive()?;
+        self.input_prompts.lock().unwrap().push(message.to_string());
+
+        match self.input_responses.lock().unwrap().pop_front() {
+            Some(answer) if !answer.is_empty() => Ok(answer),
+            _ => Ok(default.unwrap_or_default().to_string()),
+        }
+    }
+
+    fn select(
+        &self,
+        _message: &str,
+        options: &[String],
+    ) -> Result<Option<usize>, InteractionError> {
+        let Some(index) = self
+            .select_responses
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or(Some(0))
+        else {
+            return Ok(None);
+        };
+
+        if index < options.len() {
+            Ok(Some(index))
+        } else {
+            Err(InteractionError::InvalidSelection(index.to_string()))
+        }
+    }
+
     fn print(&self, message: &str) {
-        self.printed_messages.borrow_mut().push(message.to_string());
+        self.printed_messages
+            .lock()
+            .unwrap()
+            .push(message.to_string());
     }
 
     fn print_error(&self, message: &str) {
-        self.error_messages.borrow_mut().push(message.to_string());
+        self.error_messages
+            .lock()
+            .unwrap()
+            .push(message.to_string());
+    }
+
+    fn print_warning(&self, message: &str) {
+        self.warning_messages
+            .lock()
+            .unwrap()
+            .push(message.to_string());
     }
 }
 
 impl MockUserInteraction {
     pub fn new() -> Self {
         Self {
-            confirm_responses: RefCell::new(Vec::new()),
-            printed_messages: RefCell::new(Vec::new()),
-            error_messages: RefCell::new(Vec::new()),
+            confirm_responses: Mutex::new(Vec::new()),
+            select_responses: Mutex::new(Vec::new()),
+            input_responses: Mutex::new(VecDeque::new()),
+            input_prompts: Mutex::new(Vec::new()),
+            printed_messages: Mutex::new(Vec::new()),
+            error_messages: Mutex::new(Vec::new()),
+            warning_messages: Mutex::new(Vec::new()),
+            non_interactive: Mutex::new(false),
         }
     }
 
+    /// Make `confirm` and `input` fail as if stdin were not a terminal
+    pub fn set_non_interactive(&self) {
+        *self.non_interactive.lock().unwrap() = true;
+    }
+
+    fn check_interactive(&self) -> Result<(), InteractionError> {
+        if *self.non_interactive.lock().unwrap() {
+            return Err(InteractionError::NotInteractive);
+        }
+        Ok(())
+    }
+
     /// Set the next confirm response
     pub fn set_confirm(&self, response: bool) {
-        self.confirm_responses.borrow_mut().push(response);
+        self.confirm_responses.lock().unwrap().push(response);
+    }
+
+    /// Queue a scripted answer for `input` (answers are consumed in order)
+    pub fn push_input(&self, answer: impl Into<String>) {
+        self.input_responses
+            .lock()
+            .unwrap()
+            .push_back(answer.into());
+    }
+
+    /// Get the prompts passed to `input` (for assertions)
+    pub fn get_input_prompts(&self) -> Vec<String> {
+        self.input_prompts.lock().unwrap().clone()
+    }
+
+    /// Set the next select response
+    pub fn set_select(&self, index: usize) {
+        self.select_responses.lock().unwrap().push(Some(index));
+    }
+
+    /// Make the next select cancelled, as with an empty answer or `q`
+    pub fn set_select_cancel(&self) {
+        self.select_responses.lock().unwrap().push(None);
     }
 
     /// Get printed messages (for assertions)
     pub fn get_printed_messages(&self) -> Vec<String> {
-        self.printed_messages.borrow().clone()
+        self.printed_messages.lock().unwrap().clone()
     }
 
     /// Get error messages (for assertions)
     pub fn get_error_messages(&self) -> Vec<String> {
-        self.error_messages.borrow().clone()
+        self.error_messages.lock().unwrap().clone()
+    }
+
+    /// Get warning messages, which are not part of the error messages (for assertions)
+    pub fn get_warning_messages(&self) -> Vec<String> {
+        self.warning_messages.lock().unwrap().clone()
     }
 
     /// Check if a message was printed
     pub fn has_printed(&self, expected: &str) -> bool {
         self.printed_messages
-            .borrow()
+            .lock()
+            .unwrap()
             .iter()
             .any(|msg| msg.contains(expected))
     }
@@ -1,5 +1,6 @@
 use std::fs;
 use std::path::{Component, Path, PathBuf, absolute};
+use std::time::SystemTime;
 
 use crate::core::ports::{FileSystem, FileSystemError};
 
@@ -72,21 +73,43 @@ impl FileSystem for UnixFs {
         Ok(())
     }
 
+    fn read_link(&self, link: &Path) -> Result<PathBuf, FileSystemError> {
+        Ok(fs::read_link(link)?)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, FileSystemError> {
+        Ok(fs::canonicalize(path)?)
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, FileSystemError> {
+        Ok(fs::read_to_string(path)?)
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<(), FileSystemError> {
+        fs::write(path, content)?;
+        Ok(())
+    }
+
     fn copy(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
-        if self.is_dir(from) {
+        if self.is_symlink(from) {
+            self.create_symlink(&fs::read_link(from)?, to)?;
+        } else if self.is_dir(from) {
+            use std::os::unix::fs::PermissionsExt;
+
+            let permissions = fs::metadata(from)?.permissions();
             self.create_dir(to)?;
+            // Restrict the new directory before filling it so that e.g. a 0700
+            // directory of secrets is never readable by others, while keeping
+            // it writable for the copy. The exact mode is applied afterwards.
+            fs::set_permissions(to, fs::Permissions::from_mode(permissions.mode() | 0o700))?;
             for entry in self.read_dir(from)? {
                 let file_name = entry
                     .file_name()
                     .ok_or_else(|| FileSystemError::PathError("Invalid filename".into()))?;
 
-                let dest_path = to.join(file_name);
-                if self.is_dir(&entry) {
-                    self.copy(&entry, &dest_path)?;
-                } else {
-                    fs::copy(&entry, &dest_path)?;
-                }
+                self.copy(&entry, &to.join(file_name))?;
             }
+            fs::set_permissions(to, permissions)?;
         } else {
             fs::copy(from, to)?;
         }
@@ -108,6 +131,29 @@ impl FileSystem for UnixFs {
         Ok(())
     }
 
+    fn remove_empty_dir(&self, path: &Path) -> Result<(), FileSystemError> {
+        fs::remove_dir(path)?;
+        Ok(())
+    }
+
+    fn size(&self, path: &Path) -> Result<u64, FileSystemError> {
+        let metadata = path.symlink_metadata()?;
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+
+        let mut total = 0;
+        for entry in self.read_dir(path)? {
+            total += self.size(&entry)?;
+        }
+
+        Ok(total)
+    }
+
+    fn modified(&self, path: &Path) -> Result<SystemTime, FileSystemError> {
+        Ok(path.symlink_metadata()?.modified()?)
+    }
+
     fn normalize(&self, path: &Path, base: &Path) -> Result<PathBuf, FileSystemError> {
         if path.as_os_str().is_empty() {
             return Err(FileSystemError::PathError(
@@ -145,19 +191,35 @@ impl FileSystem for UnixFs {
                     continue;
                 }
                 Component::ParentDir => {
+                    // `../path` is relative to the parent of the base
+                    if first {
+                        for base_comp in base.components() {
+                            normalized_path.push(base_comp);
+                        }
+                    }
                     normalized_path.pop();
                 }
             }
             first = false;
         }
 
+        // A path of only `.` is the base itself
+        if first {
+            return Ok(base.to_path_buf());
+        }
+
         Ok(normalized_path)
     }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
     use tempfile::TempDir;
 
     #[test]
@@ -205,6 +267,17 @@ mod tests {
         assert!(adapter.is_git_repository(&repo_dir));
     }
 
+    #[test]
+    fn test_is_git_repository_dangling_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let repo_dir = temp_dir.path().join("repo");
+        fs::create_dir(&repo_dir).unwrap();
+
+        std::os::unix::fs::symlink(temp_dir.path().join("missing"), repo_dir.join(".git")).unwrap();
+        assert!(!adapter.is_git_repository(&repo_dir));
+    }
+
     #[test]
     fn test_is_symlink() {
         let temp_dir = TempDir::new().unwrap();
@@ -219,6 +292,20 @@ mod tests {
         assert!(!adapter.is_symlink(&target));
     }
 
+    #[test]
+    fn test_read_link() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let target = temp_dir.path().join("target");
+        fs::File::create(&target).unwrap();
+
+        let link = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert_eq!(adapter.read_link(&link).unwrap(), target);
+        assert!(adapter.read_link(&target).is_err());
+    }
+
     #[test]
     fn test_read_dir() {
         let temp_dir = TempDir::new().unwrap();
@@ -258,6 +345,39 @@ mod tests {
         assert!(!adapter.exists(&parent));
     }
 
+    #[test]
+    fn test_remove_empty_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let parent = temp_dir.path().join("dir");
+        let child = parent.join("nested");
+        adapter.create_dir(&child).unwrap();
+
+        assert!(adapter.remove_empty_dir(&parent).is_err());
+        assert!(adapter.exists(&child));
+
+        adapter.remove_empty_dir(&child).unwrap();
+        adapter.remove_empty_dir(&parent).unwrap();
+        assert!(!adapter.exists(&parent));
+    }
+
+    #[test]
+    fn test_write_and_read_to_string() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let file = temp_dir.path().join("file.txt");
+
+        adapter.write(&file, "first").unwrap();
+        adapter.write(&file, "second").unwrap();
+
+        assert_eq!(adapter.read_to_string(&file).unwrap(), "second");
+        assert!(
+            adapter
+                .read_to_string(&temp_dir.path().join("missing"))
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_remove_file() {
         let temp_dir = TempDir::new().unwrap();
@@ -286,6 +406,130 @@ mod tests {
         assert!(adapter.exists(&to));
     }
 
+    #[test]
+    fn test_copy_preserves_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let from = temp_dir.path().join("from");
+        let to = temp_dir.path().join("to");
+
+        adapter.create_dir(&from).unwrap();
+        fs::write(from.join("run.sh"), "#!/bin/sh").unwrap();
+        fs::set_permissions(from.join("run.sh"), fs::Permissions::from_mode(0o755)).unwrap();
+        std::os::unix::fs::symlink("run.sh", from.join("link")).unwrap();
+
+        adapter.copy(&from, &to).unwrap();
+
+        assert!(adapter.is_symlink(&to.join("link")));
+        assert_eq!(fs::read_link(to.join("link")).unwrap(), Path::new("run.sh"));
+        let mode = fs::metadata(to.join("run.sh"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_copy_keeps_internal_directory_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let from = temp_dir.path().join("from");
+        let to = temp_dir.path().join("to");
+
+        adapter.create_dir(&from.join("bin")).unwrap();
+        adapter.create_dir(&from.join("lib")).unwrap();
+        fs::write(from.join("lib/tool.sh"), "#!/bin/sh").unwrap();
+        std::os::unix::fs::symlink("../lib", from.join("bin/lib")).unwrap();
+        // Following this one would recurse forever
+        std::os::unix::fs::symlink("..", from.join("lib/up")).unwrap();
+
+        adapter.copy(&from, &to).unwrap();
+
+        assert_eq!(
+            fs::read_link(to.join("bin/lib")).unwrap(),
+            Path::new("../lib")
+        );
+        assert_eq!(fs::read_link(to.join("lib/up")).unwrap(), Path::new(".."));
+        assert!(
+            fs::canonicalize(to.join("bin/lib/tool.sh"))
+                .unwrap()
+                .starts_with(fs::canonicalize(&to).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_copy_preserves_directory_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let from = temp_dir.path().join("secrets");
+        let to = temp_dir.path().join("copied");
+
+        adapter.create_dir(&from.join("nested")).unwrap();
+        fs::write(from.join("nested/token"), "secret").unwrap();
+        fs::set_permissions(from.join("nested"), fs::Permissions::from_mode(0o500)).unwrap();
+        fs::set_permissions(&from, fs::Permissions::from_mode(0o700)).unwrap();
+
+        adapter.copy(&from, &to).unwrap();
+
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&to), 0o700);
+        assert_eq!(mode(&to.join("nested")), 0o500);
+        assert_eq!(
+            fs::read_to_string(to.join("nested/token")).unwrap(),
+            "secret"
+        );
+
+        // Let TempDir clean up the read-only directories
+        for dir in [from.join("nested"), to.join("nested")] {
+            fs::set_permissions(dir, fs::Permissions::from_mode(0o700)).unwrap();
+        }
+    }
+
+    #[test]
+    #[ignore = "requires GRM_TEST_OTHER_DEVICE_DIR on a different filesystem"]
+    fn test_move_path_across_devices() {
+        let other_device = std::env::var("GRM_TEST_OTHER_DEVICE_DIR").unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let other_dir = TempDir::new_in(other_device).unwrap();
+        let adapter = UnixFs::new();
+        let from = temp_dir.path().join("dir");
+        let to = other_dir.path().join("dir");
+
+        adapter.create_dir(&from.join("nested")).unwrap();
+        fs::write(from.join("nested/file.txt"), "content").unwrap();
+        std::os::unix::fs::symlink("nested/file.txt", from.join("link")).unwrap();
+
+        adapter.move_path(&from, &to).unwrap();
+
+        assert!(!adapter.exists(&from));
+        assert_eq!(
+            fs::read_to_string(to.join("nested/file.txt")).unwrap(),
+            "content"
+        );
+        assert!(adapter.is_symlink(&to.join("link")));
+    }
+
+    #[test]
+    fn test_size() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = UnixFs::new();
+        let dir = temp_dir.path().join("dir");
+
+        adapter.create_dir(&dir.join("nested")).unwrap();
+        fs::write(dir.join("a.txt"), "12345").unwrap();
+        fs::write(dir.join("nested/b.txt"), "123").unwrap();
+        std::os::unix::fs::symlink(dir.join("a.txt"), dir.join("link")).unwrap();
+        let link_size = fs::symlink_metadata(dir.join("link")).unwrap().len();
+
+        assert_eq!(adapter.size(&dir.join("a.txt")).unwrap(), 5);
+        assert_eq!(adapter.size(&dir).unwrap(), 8 + link_size);
+        assert!(adapter.size(&dir.join("missing")).is_err());
+    }
+
     #[test]
     fn test_normalize_absolute_path() {
         let adapter = UnixFs::new();
@@ -380,4 +624,57 @@ mod tests {
 
         assert_eq!(normalized, home.join("foo/bar"));
     }
+
+    #[test]
+    fn test_normalize_current_dir() {
+        let adapter = UnixFs::new();
+        let base = PathBuf::from("/base/sub");
+        let path = PathBuf::from(".");
+
+        let normalized = adapter.normalize(&path, &base).unwrap();
+
+        assert_eq!(normalized, base);
+    }
+
+    #[test]
+    fn test_normalize_leading_parent_dir() {
+        let adapter = UnixFs::new();
+        let base = PathBuf::from("/base/sub");
+        let path = PathBuf::from("../foo");
+
+        let normalized = adapter.normalize(&path, &base).unwrap();
+
+        assert_eq!(normalized, PathBuf::from("/base/foo"));
+    }
+
+    #[test]
+    fn test_normalize_matches_mock() {
+        // The mock must resolve paths like the real adapter, or tests hide bugs
+        let adapter = UnixFs::new();
+        let mut mock = MockFileSystem::new();
+        mock.set_home_dir(adapter.home_dir().unwrap());
+        let base = PathBuf::from("/base/sub");
+
+        for input in [
+            "foo/../bar",
+            ".",
+            "./foo",
+            "../foo",
+            "../../../foo",
+            "foo/./bar/..",
+            "/absolute/./foo/../bar",
+            "/..",
+            "~",
+            "~/foo/../bar",
+            "~foo",
+            "foo/~/bar",
+        ] {
+            let path = PathBuf::from(input);
+            assert_eq!(
+                adapter.normalize(&path, &base).unwrap(),
+                mock.normalize(&path, &base).unwrap(),
+                "normalize({input:?}) differs"
+            );
+        }
+    }
 }
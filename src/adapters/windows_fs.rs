@@ -0,0 +1,329 @@
+use std::fs;
+use std::os::windows::fs::FileTypeExt;
+use std::path::{Component, Path, PathBuf, absolute};
+use std::time::SystemTime;
+
+use crate::core::ports::{FileSystem, FileSystemError};
+
+#[derive(Debug)]
+pub struct WindowsFs;
+
+impl WindowsFs {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for WindowsFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for WindowsFs {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_symlink(&self, path: &Path) -> bool {
+        match path.symlink_metadata() {
+            Ok(metadata) => metadata.is_symlink(),
+            Err(_) => false,
+        }
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn is_git_repository(&self, path: &Path) -> bool {
+        let git_path = path.join(".git");
+        git_path.exists() && (git_path.is_dir() || git_path.is_file())
+    }
+
+    fn current_dir(&self) -> Result<PathBuf, FileSystemError> {
+        let dir = std::env::current_dir()?;
+        Ok(dir)
+    }
+
+    fn home_dir(&self) -> Result<PathBuf, FileSystemError> {
+        dirs::home_dir()
+            .and_then(|path| absolute(&path).ok())
+            .ok_or_else(|| FileSystemError::PathError("Home directory not found".into()))
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, FileSystemError> {
+        let entries = fs::read_dir(path)?;
+        let mut paths = Vec::new();
+
+        for entry in entries {
+            let entry = entry?;
+            paths.push(entry.path());
+        }
+
+        Ok(paths)
+    }
+
+    fn create_dir(&self, path: &Path) -> Result<(), FileSystemError> {
+        fs::create_dir_all(path)?;
+        Ok(())
+    }
+
+    fn create_symlink(&self, target: &Path, link: &Path) -> Result<(), FileSystemError> {
+        // Windows distinguishes file and directory links, so look at what the link will point to
+        let resolved = match link.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target.to_path_buf(),
+        };
+
+        if resolved.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)?;
+        } else {
+            std::os::windows::fs::symlink_file(target, link)?;
+        }
+        Ok(())
+    }
+
+    fn read_link(&self, link: &Path) -> Result<PathBuf, FileSystemError> {
+        Ok(fs::read_link(link)?)
+    }
+
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, FileSystemError> {
+        Ok(fs::canonicalize(path)?)
+    }
+
+    fn read_to_string(&self, path: &Path) -> Result<String, FileSystemError> {
+        Ok(fs::read_to_string(path)?)
+    }
+
+    fn write(&self, path: &Path, content: &str) -> Result<(), FileSystemError> {
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    fn copy(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
+        if self.is_symlink(from) {
+            // Take the link kind from the source, as a relative target inside
+            // the copied tree may not have been copied yet
+            let target = fs::read_link(from)?;
+            if self.is_dir(from) {
+                std::os::windows::fs::symlink_dir(target, to)?;
+            } else {
+                std::os::windows::fs::symlink_file(target, to)?;
+            }
+        } else if self.is_dir(from) {
+            self.create_dir(to)?;
+            for entry in self.read_dir(from)? {
+                let file_name = entry
+                    .file_name()
+                    .ok_or_else(|| FileSystemError::PathError("Invalid filename".into()))?;
+
+                self.copy(&entry, &to.join(file_name))?;
+            }
+            fs::set_permissions(to, fs::metadata(from)?.permissions())?;
+        } else {
+            fs::copy(from, to)?;
+        }
+        Ok(())
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
+        fs::rename(from, to)?;
+        Ok(())
+    }
+
+    fn remove(&self, path: &Path) -> Result<(), FileSystemError> {
+        let file_type = path.symlink_metadata()?.file_type();
+        if file_type.is_symlink_dir() {
+            // Directory links are removed like empty directories, without touching the target
+            fs::remove_dir(path)?;
+        } else if file_type.is_dir() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_empty_dir(&self, path: &Path) -> Result<(), FileSystemError> {
+        fs::remove_dir(path)?;
+        Ok(())
+    }
+
+    fn size(&self, path: &Path) -> Result<u64, FileSystemError> {
+        let metadata = path.symlink_metadata()?;
+        if !metadata.is_dir() {
+            return Ok(metadata.len());
+        }
+
+        let mut total = 0;
+        for entry in self.read_dir(path)? {
+            total += self.size(&entry)?;
+        }
+
+        Ok(total)
+    }
+
+    fn modified(&self, path: &Path) -> Result<SystemTime, FileSystemError> {
+        Ok(path.symlink_metadata()?.modified()?)
+    }
+
+    fn normalize(&self, path: &Path, base: &Path) -> Result<PathBuf, FileSystemError> {
+        if path.as_os_str().is_empty() {
+            return Err(FileSystemError::PathError(
+                "Cannot normalize an empty path".into(),
+            ));
+        }
+
+        let components = path.components();
+        let mut normalized_path = PathBuf::new();
+        let mut first = true;
+
+        for component in components {
+            match component {
+                // Unlike Unix, the drive prefix is part of an absolute path
+                Component::Prefix(_) | Component::RootDir => {
+                    normalized_path.push(component);
+                }
+                Component::Normal(stem) if stem == "~" => {
+                    normalized_path.clear();
+                    let home = self.home_dir()?;
+                    let home_components = home.components();
+                    for home_comp in home_components {
+                        normalized_path.push(home_comp);
+                    }
+                }
+                Component::Normal(_) => {
+                    if first {
+                        let base_components = base.components();
+                        for base_comp in base_components {
+                            normalized_path.push(base_comp);
+                        }
+                    }
+                    normalized_path.push(component);
+                }
+                Component::CurDir => {
+                    continue;
+                }
+                Component::ParentDir => {
+                    // `../path` is relative to the parent of the base
+                    if first {
+                        for base_comp in base.components() {
+                            normalized_path.push(base_comp);
+                        }
+                    }
+                    normalized_path.pop();
+                }
+            }
+            first = false;
+        }
+
+        // A path of only `.` is the base itself
+        if first {
+            return Ok(base.to_path_buf());
+        }
+
+        Ok(normalized_path)
+    }
+
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_symlink_to_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = WindowsFs::new();
+        let target = temp_dir.path().join("target.txt");
+        fs::write(&target, "content").unwrap();
+        let link = temp_dir.path().join("link.txt");
+
+        adapter.create_symlink(&target, &link).unwrap();
+
+        assert!(adapter.is_symlink(&link));
+        assert_eq!(fs::read_to_string(&link).unwrap(), "content");
+    }
+
+    #[test]
+    fn test_create_symlink_to_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = WindowsFs::new();
+        let target = temp_dir.path().join("target");
+        adapter.create_dir(&target).unwrap();
+        fs::write(target.join("file.txt"), "content").unwrap();
+        let link = temp_dir.path().join("link");
+
+        adapter.create_symlink(&target, &link).unwrap();
+
+        assert!(adapter.is_symlink(&link));
+        assert!(adapter.is_dir(&link));
+    }
+
+    #[test]
+    fn test_remove_directory_symlink_keeps_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = WindowsFs::new();
+        let target = temp_dir.path().join("target");
+        adapter.create_dir(&target).unwrap();
+        fs::write(target.join("file.txt"), "content").unwrap();
+        let link = temp_dir.path().join("link");
+        adapter.create_symlink(&target, &link).unwrap();
+
+        adapter.remove(&link).unwrap();
+
+        assert!(!adapter.exists(&link));
+        assert!(adapter.exists(&target.join("file.txt")));
+    }
+
+    #[test]
+    fn test_copy_keeps_internal_directory_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let adapter = WindowsFs::new();
+        let from = temp_dir.path().join("from");
+        let to = temp_dir.path().join("to");
+        adapter.create_dir(&from.join("bin")).unwrap();
+        adapter.create_dir(&from.join("lib")).unwrap();
+        fs::write(from.join("lib").join("tool.txt"), "content").unwrap();
+        // `bin` is copied before the `lib` it points to
+        std::os::windows::fs::symlink_dir(r"..\lib", from.join("bin").join("lib")).unwrap();
+
+        adapter.copy(&from, &to).unwrap();
+
+        let link = to.join("bin").join("lib");
+        assert!(adapter.is_symlink(&link));
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new(r"..\lib"));
+        assert_eq!(
+            fs::read_to_string(link.join("tool.txt")).unwrap(),
+            "content"
+        );
+    }
+
+    #[test]
+    fn test_normalize_keeps_drive_prefix() {
+        let adapter = WindowsFs::new();
+        let base = PathBuf::from(r"C:\base");
+        let path = PathBuf::from(r"D:\absolute\hoge\..\foo");
+
+        let normalized = adapter.normalize(&path, &base).unwrap();
+
+        assert_eq!(normalized, PathBuf::from(r"D:\absolute\foo"));
+    }
+
+    #[test]
+    fn test_normalize_relative_path() {
+        let adapter = WindowsFs::new();
+        let base = PathBuf::from(r"C:\base");
+        let path = PathBuf::from(r"relative\.\path");
+
+        let normalized = adapter.normalize(&path, &base).unwrap();
+
+        assert_eq!(normalized, PathBuf::from(r"C:\base\relative\path"));
+    }
+}
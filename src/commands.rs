@@ -1,98 +1,355 @@
-use clap::{CommandFactory, Parser, Subcommand};
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+
+use crate::adapters::StderrInteraction;
 use crate::configs::Config;
-use crate::errors::GrmError;
 use crate::container::AppContainer;
+use crate::core::ports::UserInteraction;
+use crate::core::shared_resource::CopyMode;
+use crate::errors::GrmError;
+use crate::usecases::prune_repositories::parse_age;
 use crate::usecases::{
-    CloneRepositoryUseCase, IsolateFilesUseCase, ListRepositoriesUseCase, RemoveRepositoryUseCase,
-    RemoveWorktreeUseCase, ShareFilesUseCase, ShowRootUseCase, SplitWorktreeUseCase,
-    UnshareFilesUseCase,
+    CloneBatchUseCase, CloneOptions, CloneRepositoryUseCase, CloneTarget, DoctorUseCase,
+    EditConfigUseCase, ExecUseCase, GetRepositoryUseCase, InitRootUseCase, IsolateFilesUseCase,
+    ListOptions, ListRepositoriesUseCase, ListSharedUseCase, ListStyle, ListWorktreesUseCase,
+    LookRepositoryUseCase, MoveWorktreeUseCase, PruneRepositoriesUseCase, PruneWorktreesUseCase,
+    PullUseCase, RemoveRepositoryUseCase, RemoveWorktreeUseCase, ResolvePathUseCase,
+    ShareFilesUseCase, ShowRootUseCase, ShowStatsUseCase, SplitWorktreeUseCase, StatsFormat,
+    StatusUseCase, SwitchWorktreeUseCase, UnshareFilesUseCase, WhereisUseCase,
 };
 
 #[derive(Debug, Parser)]
 #[command(name = "grm", about = "Git Repository Manager", long_about = None)]
 pub struct Cli {
+    #[arg(short, long, global = true)]
+    #[arg(help = "Suppress informational output (errors and prompts are still shown)")]
+    quiet: bool,
+
+    #[arg(long, global = true, value_name = "PATH")]
+    #[arg(help = "Root directory to use instead of the configured one")]
+    root: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
 impl Cli {
+    // One match arm per subcommand
+    #[allow(clippy::too_many_lines)]
     pub fn execute() -> Result<(), GrmError> {
         let args = Cli::parse();
-        let container = AppContainer::new();
-        let config = Config::load()?;
+
+        // Completions only depend on the command definition, not on the config
+        if let Some(Commands::Completions { shell }) = args.command {
+            // Generated into a buffer so a closed stdout is an error rather than a panic
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut Cli::command(), "grm", &mut script);
+            std::io::stdout().write_all(&script)?;
+            return Ok(());
+        }
+
+        let loaded = Config::load().and_then(|config| match &args.root {
+            Some(root) => config.with_root(root),
+            None => Ok(config),
+        });
+
+        // A broken config is one of the things doctor reports, so it must not abort here
+        if matches!(args.command, Some(Commands::Doctor)) {
+            let fallback = Config::new(PathBuf::new());
+            let container = AppContainer::new(loaded.as_ref().unwrap_or(&fallback), args.quiet);
+            let usecase = DoctorUseCase::new(
+                container.git.clone(),
+                container.fs.clone(),
+                container.ui.clone(),
+            );
+            return usecase.execute(loaded.as_ref());
+        }
+
+        let config = loaded?;
+        let container = AppContainer::new(&config, args.quiet);
 
         match &args.command {
-            Some(Commands::Root) => {
-                let usecase = ShowRootUseCase::new(container.ui.clone());
-                usecase.execute(&config);
+            Some(Commands::Root { create, verbose }) => {
+                let usecase = ShowRootUseCase::new(container.fs.clone(), container.ui.clone());
+                usecase.execute(&config, *create, *verbose)?;
                 Ok(())
             }
-            Some(Commands::Clone { url, branch }) => {
-                let usecase = CloneRepositoryUseCase::new(
+            Some(Commands::Init { adopt }) => {
+                let usecase = InitRootUseCase::new(
                     container.git.clone(),
                     container.fs.clone(),
                     container.ui.clone(),
                 );
+                usecase.execute(&config, args.root.as_deref(), adopt)?;
+                Ok(())
+            }
+            Some(Commands::Clone {
+                url,
+                branch,
+                link,
+                no_branch_suffix,
+                depth,
+                recurse_submodules,
+                refresh,
+                post_clone,
+                open,
+                jobs,
+                bare,
+                mirror,
+                sparse,
+                retries,
+            }) if url == "-" => {
+                let urls = read_urls(std::io::stdin().lock())?;
+                let ui = clone_ui(container.ui.clone(), *open, args.quiet);
+                let usecase = CloneBatchUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    ui,
+                    container.runner.clone(),
+                )
+                .with_retries(
+                    retries.unwrap_or(config.clone_retries),
+                    container.clock.clone(),
+                );
+                let options = CloneOptions {
+                    target: clone_target(*no_branch_suffix, *bare, *mirror),
+                    branch: branch.as_deref(),
+                    link: *link || config.default_link,
+                    depth: *depth,
+                    recurse_submodules: *recurse_submodules,
+                    refresh: *refresh,
+                    post_clone: post_clone.as_deref(),
+                    sparse,
+                };
+                let summary = usecase.execute(&urls, *jobs, |clone, url| {
+                    clone.execute(&config, url, &options)
+                });
+                if *open {
+                    for (_, path) in &summary.cloned {
+                        container.ui.print_result(&path.display().to_string());
+                    }
+                }
+                if !summary.failed.is_empty() {
+                    return Err(GrmError::CloneFailed {
+                        failed: summary.failed.len(),
+                        total: urls.len(),
+                    });
+                }
+                Ok(())
+            }
+            Some(Commands::Clone {
+                url,
+                branch,
+                link,
+                no_branch_suffix,
+                depth,
+                recurse_submodules,
+                refresh,
+                post_clone,
+                open,
+                bare,
+                mirror,
+                sparse,
+                retries,
+                ..
+            }) => {
+                let ui = clone_ui(container.ui.clone(), *open, args.quiet);
+                let usecase = CloneRepositoryUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    ui,
+                    container.runner.clone(),
+                )
+                .with_retries(
+                    retries.unwrap_or(config.clone_retries),
+                    container.clock.clone(),
+                );
+                let options = CloneOptions {
+                    target: clone_target(*no_branch_suffix, *bare, *mirror),
+                    branch: branch.as_deref(),
+                    link: *link || config.default_link,
+                    depth: *depth,
+                    recurse_submodules: *recurse_submodules,
+                    refresh: *refresh,
+                    post_clone: post_clone.as_deref(),
+                    sparse,
+                };
+                let path = usecase.execute(&config, url, &options)?;
+                if *open {
+                    container.ui.print_result(&path.display().to_string());
+                }
+                Ok(())
+            }
+            Some(Commands::Get { url, branch }) => {
+                // Like `clone --open`, stdout is kept for the path
+                let usecase = GetRepositoryUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    clone_ui(container.ui.clone(), true, args.quiet),
+                    container.runner.clone(),
+                )
+                .with_retries(config.clone_retries, container.clock.clone());
                 usecase.execute(&config, url, branch.as_deref())?;
                 Ok(())
             }
-            Some(Commands::List { full_path }) => {
-                let usecase =
-                    ListRepositoriesUseCase::new(container.fs.clone(), container.ui.clone());
-                usecase.execute(&config, *full_path)?;
+            Some(Commands::List {
+                full_path,
+                json,
+                show_branch,
+                size,
+                filter,
+                format,
+                branches,
+            }) => {
+                let usecase = ListRepositoriesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                let style = if *json {
+                    ListStyle::Json
+                } else if *branches {
+                    ListStyle::Branches
+                } else {
+                    ListStyle::Paths
+                };
+                usecase.execute(
+                    &config,
+                    &ListOptions {
+                        style,
+                        full_path: *full_path,
+                        show_branch: *show_branch,
+                        size: *size,
+                        filter: filter.as_deref(),
+                        format: format.as_deref(),
+                    },
+                )?;
                 Ok(())
             }
-            Some(Commands::Remove { url, force }) => {
-                let usecase =
-                    RemoveRepositoryUseCase::new(container.fs.clone(), container.ui.clone());
-                usecase.execute(&config, url, *force)?;
+            Some(Commands::Path { query }) => {
+                let usecase = ResolvePathUseCase::new(container.fs.clone(), container.ui.clone());
+                usecase.execute(&config, query)?;
                 Ok(())
             }
-            Some(Commands::Worktree { command }) => match command {
-                WorktreeCommands::Split { branch } => {
-                    let usecase = SplitWorktreeUseCase::new(
-                        container.git.clone(),
-                        container.fs.clone(),
-                        container.ui.clone(),
-                    );
-                    usecase.execute(&config, branch)?;
-                    Ok(())
-                }
-                WorktreeCommands::Remove { branch } => {
-                    let usecase =
-                        RemoveWorktreeUseCase::new(container.git.clone(), container.ui.clone());
-                    usecase.execute(&config, branch)?;
-                    Ok(())
-                }
-                WorktreeCommands::Share { path } => {
-                    let usecase = ShareFilesUseCase::new(
-                        container.git.clone(),
-                        container.fs.clone(),
-                        container.ui.clone(),
-                    );
-                    usecase.execute(&config, path)?;
-                    Ok(())
-                }
-                WorktreeCommands::Unshare { path } => {
-                    let usecase = UnshareFilesUseCase::new(
-                        container.git.clone(),
-                        container.fs.clone(),
-                        container.ui.clone(),
-                    );
-                    usecase.execute(&config, path)?;
-                    Ok(())
+            Some(Commands::Whereis { url, branch }) => {
+                let usecase = WhereisUseCase::new(container.fs.clone(), container.ui.clone());
+                usecase.execute(&config, url, branch.as_deref())?;
+                Ok(())
+            }
+            Some(Commands::Look { query }) => {
+                let usecase = LookRepositoryUseCase::new(
+                    container.fs.clone(),
+                    container.ui.clone(),
+                    container.runner.clone(),
+                );
+                let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+                let code = usecase.execute(&config, query, &shell)?;
+                if code != 0 {
+                    std::process::exit(code);
                 }
-                WorktreeCommands::Isolate { path } => {
-                    let usecase = IsolateFilesUseCase::new(
-                        container.git.clone(),
-                        container.fs.clone(),
-                        container.ui.clone(),
-                    );
-                    usecase.execute(&config, path)?;
-                    Ok(())
+                Ok(())
+            }
+            Some(Commands::Exec {
+                url,
+                fail_fast,
+                command,
+            }) => {
+                let usecase = ExecUseCase::new(
+                    container.fs.clone(),
+                    container.ui.clone(),
+                    container.runner.clone(),
+                );
+                usecase.execute(&config, url, command, *fail_fast)?;
+                Ok(())
+            }
+            Some(Commands::Pull { url }) => {
+                let usecase = PullUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(&config, url)?;
+                Ok(())
+            }
+            Some(Commands::Remove {
+                url,
+                branch,
+                force,
+                dry_run,
+                interactive,
+            }) => {
+                let usecase = RemoveRepositoryUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(
+                    &config,
+                    url,
+                    branch.as_deref(),
+                    *force,
+                    *dry_run,
+                    *interactive,
+                )?;
+                Ok(())
+            }
+            Some(Commands::Prune { since, force }) => {
+                let usecase = PruneRepositoriesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                    container.clock.clone(),
+                );
+                usecase.execute(&config, *since, *force)?;
+                Ok(())
+            }
+            Some(Commands::Move {
+                url,
+                old_branch,
+                new_branch,
+                rename_branch,
+            }) => {
+                let usecase = MoveWorktreeUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(&config, url, old_branch, new_branch, *rename_branch)?;
+                Ok(())
+            }
+            Some(Commands::Stats { format, no_size }) => {
+                let usecase = ShowStatsUseCase::new(container.fs.clone(), container.ui.clone());
+                usecase.execute(&config, (*format).into(), !*no_size)?;
+                Ok(())
+            }
+            Some(Commands::Status { all }) => {
+                let usecase = StatusUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(&config, *all)?;
+                Ok(())
+            }
+            Some(Commands::Config { command }) => {
+                let usecase = EditConfigUseCase::new(container.fs.clone(), container.ui.clone());
+                match command {
+                    ConfigCommands::Get { key } => usecase.get(&config, key),
+                    ConfigCommands::Set { key, value } => usecase.set(key, value),
                 }
-            },
+            }
+            Some(Commands::Worktree { command }) => {
+                Self::execute_worktree(command, &container, &config)
+            }
+            Some(Commands::Doctor) => unreachable!("doctor runs before the config is required"),
+            Some(Commands::Completions { .. }) => {
+                unreachable!("completions run before the config is loaded")
+            }
             None => {
                 Cli::command()
                     .print_help()
@@ -101,37 +358,433 @@ impl Cli {
             }
         }
     }
+
+    // One match arm per subcommand
+    #[allow(clippy::too_many_lines)]
+    fn execute_worktree(
+        command: &WorktreeCommands,
+        container: &AppContainer,
+        config: &Config,
+    ) -> Result<(), GrmError> {
+        match command {
+            WorktreeCommands::Split {
+                branch,
+                group,
+                fetch,
+                at,
+                remote,
+            } => {
+                let usecase = SplitWorktreeUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(
+                    config,
+                    branch,
+                    group,
+                    *fetch,
+                    remote.as_deref(),
+                    at.as_deref(),
+                )?;
+                Ok(())
+            }
+            WorktreeCommands::List {
+                full_path,
+                managed_only,
+            } => {
+                let usecase = ListWorktreesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config, *full_path, *managed_only)?;
+                Ok(())
+            }
+            WorktreeCommands::Switch {
+                branch,
+                create,
+                remote,
+            } => {
+                let usecase = SwitchWorktreeUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config, branch, *create, remote.as_deref())?;
+                Ok(())
+            }
+            WorktreeCommands::Remove {
+                branch,
+                remote,
+                delete_branch,
+            } => {
+                let usecase = RemoveWorktreeUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config, branch, remote.as_deref(), *delete_branch)?;
+                Ok(())
+            }
+            WorktreeCommands::Prune { force, remote } => {
+                let usecase = PruneWorktreesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config, *force, remote.as_deref())?;
+                Ok(())
+            }
+            WorktreeCommands::Share {
+                path,
+                allow_sensitive,
+                group,
+                copy,
+            } => {
+                let usecase = ShareFilesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                let mode = if *copy {
+                    CopyMode::Copy
+                } else {
+                    CopyMode::Move
+                };
+                usecase.execute(config, path, *allow_sensitive, group.as_deref(), mode)?;
+                Ok(())
+            }
+            WorktreeCommands::Unshare { path } => {
+                let usecase = UnshareFilesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config, path)?;
+                Ok(())
+            }
+            WorktreeCommands::Shared => {
+                let usecase = ListSharedUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                usecase.execute(config)?;
+                Ok(())
+            }
+            WorktreeCommands::Isolate { path, all: _ } => {
+                let usecase = IsolateFilesUseCase::new(
+                    container.git.clone(),
+                    container.fs.clone(),
+                    container.ui.clone(),
+                );
+                // clap requires <PATH> unless --all is given
+                if let Some(path) = path {
+                    usecase.execute(config, path)?;
+                } else {
+                    usecase.execute_all(config)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// UI for `grm clone` and `grm get`, which moves progress to stderr under `--open`
+/// so that stdout only has the paths for command substitution
+///
+/// `--quiet` already drops the progress, so it is not moved there.
+fn clone_ui(
+    ui: Arc<dyn UserInteraction + Send + Sync>,
+    open: bool,
+    quiet: bool,
+) -> Arc<dyn UserInteraction + Send + Sync> {
+    if open && !quiet {
+        Arc::new(StderrInteraction::new(ui))
+    } else {
+        ui
+    }
+}
+
+/// `CloneTarget` for the mutually exclusive `grm clone` flags
+fn clone_target(no_branch_suffix: bool, bare: bool, mirror: bool) -> CloneTarget {
+    if mirror {
+        CloneTarget::Mirror
+    } else if bare {
+        CloneTarget::Bare
+    } else if no_branch_suffix {
+        CloneTarget::Plain
+    } else {
+        CloneTarget::Worktree
+    }
+}
+
+/// URLs for `grm clone -`, one per line, ignoring blank lines and `#` comments
+fn read_urls(reader: impl BufRead) -> Result<Vec<String>, GrmError> {
+    let mut urls = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let url = line.trim();
+        if !url.is_empty() && !url.starts_with('#') {
+            urls.push(url.to_string());
+        }
+    }
+    Ok(urls)
 }
 
 #[derive(Subcommand, Debug)]
 enum Commands {
     #[command(about = "Show the root directory for managed repositories")]
-    Root,
+    Root {
+        #[arg(long)]
+        #[arg(help = "Create the root directory if it does not exist")]
+        create: bool,
+
+        #[arg(short, long)]
+        #[arg(help = "Also print which config source supplied the root (to stderr)")]
+        verbose: bool,
+    },
+
+    #[command(about = "Check the git installation, config and root directory")]
+    Doctor,
+
+    #[command(about = "Create the root directory and config, and adopt existing repositories")]
+    Init {
+        #[arg(long)]
+        #[arg(help = "Directory to search for repositories to move into the root (repeatable)")]
+        adopt: Vec<String>,
+    },
 
     #[command(about = "Clone a repository into the managed structure")]
     Clone {
-        #[arg(help = "Git repository URL")]
+        #[arg(help = "Git repository URL, or `-` to read newline-separated URLs from stdin")]
         url: String,
 
         #[arg(short, long)]
         #[arg(help = "Branch to clone (queries remote if not specified)")]
         branch: Option<String>,
+
+        #[arg(
+            long,
+            help = "Link <host>/<user>/<repo> to the default-branch worktree"
+        )]
+        link: bool,
+
+        #[arg(long, conflicts_with = "link")]
+        #[arg(help = "Clone into <host>/<user>/<repo> without the +<branch> suffix")]
+        no_branch_suffix: bool,
+
+        #[arg(long)]
+        #[arg(help = "Create a shallow clone with this many commits (defaults to clone_depth)")]
+        depth: Option<u32>,
+
+        #[arg(long, help = "Also clone and check out the submodules")]
+        recurse_submodules: bool,
+
+        #[arg(long, value_name = "N")]
+        #[arg(
+            help = "Retry a clone which failed on the network up to N times (defaults to clone_retries)"
+        )]
+        retries: Option<u32>,
+
+        #[arg(long, num_args = 1.., value_name = "PATH", conflicts_with = "recurse_submodules")]
+        #[arg(help = "Check out only these directories (sparse-checkout in cone mode)")]
+        sparse: Vec<String>,
+
+        #[arg(long)]
+        #[arg(help = "Ask the remote for its default branch instead of using the cache")]
+        refresh: bool,
+
+        #[arg(long, value_name = "COMMAND")]
+        #[arg(help = "Shell command to run in the new clone (defaults to post_clone)")]
+        post_clone: Option<String>,
+
+        #[arg(long)]
+        #[arg(help = "Print only the clone path on stdout, other messages go to stderr")]
+        open: bool,
+
+        #[arg(short, long, default_value_t = 4)]
+        #[arg(help = "Number of repositories cloned at once when reading URLs from stdin")]
+        jobs: usize,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["branch", "link", "no_branch_suffix", "depth", "recurse_submodules", "sparse", "refresh", "post_clone"]
+        )]
+        #[arg(help = "Clone without a working tree into <host>/<user>/<repo>.git")]
+        bare: bool,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["bare", "branch", "link", "no_branch_suffix", "depth", "recurse_submodules", "sparse", "refresh", "post_clone"]
+        )]
+        #[arg(help = "Like --bare, but mirror all refs of the remote")]
+        mirror: bool,
+    },
+
+    #[command(about = "Clone a repository or add a worktree, then print its path")]
+    Get {
+        #[arg(help = "Git repository URL")]
+        url: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Branch to get (queries remote if not specified)")]
+        branch: Option<String>,
     },
 
     #[command(about = "List managed repositories")]
     List {
         #[arg(short, long, help = "Show full absolute paths")]
         full_path: bool,
+
+        #[arg(long, help = "Print repositories as a JSON array")]
+        json: bool,
+
+        #[arg(long, help = "Show the branch actually checked out in each repository")]
+        show_branch: bool,
+
+        #[arg(long, help = "Show the disk usage of each repository")]
+        size: bool,
+
+        #[arg(long, value_name = "GLOB")]
+        #[arg(help = "Only list repositories whose path relative to the root matches the glob")]
+        filter: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "TEMPLATE",
+            conflicts_with_all = ["full_path", "json", "show_branch", "size"]
+        )]
+        #[arg(
+            help = "Print each repository with placeholders {host}, {user}, {repo}, {branch}, {path} and {relpath}"
+        )]
+        format: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["full_path", "json", "show_branch", "size", "format"]
+        )]
+        #[arg(help = "Print each repository once, followed by the branches of its worktrees")]
+        branches: bool,
+    },
+
+    #[command(about = "Print the path of the repository matching a query")]
+    Path {
+        #[arg(help = "Repository query (e.g. repo, user/repo, repo+branch)")]
+        query: String,
+    },
+
+    #[command(about = "Print the managed path of a repository URL")]
+    Whereis {
+        #[arg(help = "Git repository URL")]
+        url: String,
+
+        #[arg(help = "Branch name (prints every worktree if omitted)")]
+        branch: Option<String>,
+    },
+
+    #[command(about = "Open a subshell in the repository matching a query")]
+    Look {
+        #[arg(help = "Repository query (e.g. repo, user/repo, repo+branch)")]
+        query: String,
+    },
+
+    #[command(about = "Run a command in every worktree of a repository")]
+    Exec {
+        #[arg(help = "Git repository URL")]
+        url: String,
+
+        #[arg(long)]
+        #[arg(help = "Stop at the first worktree where the command fails")]
+        fail_fast: bool,
+
+        #[arg(last = true, required = true)]
+        #[arg(help = "Command to run, after `--`")]
+        command: Vec<String>,
+    },
+
+    #[command(about = "Fast-forward every worktree of a repository to its upstream")]
+    Pull {
+        #[arg(help = "Git repository URL")]
+        url: String,
     },
 
     #[command(about = "Remove a repository")]
     Remove {
-        #[arg(help = "Git repository URL (e.g. github.com/user/repo)")]
+        #[arg(help = "Git repository URL, or a path inside a managed repository (e.g. `.`)")]
         url: String,
 
+        #[arg(short, long, conflicts_with = "interactive")]
+        #[arg(help = "Remove only the worktree of this branch")]
+        branch: Option<String>,
+
         #[arg(short, long)]
         #[arg(help = "Force removal without confirmation")]
         force: bool,
+
+        #[arg(long)]
+        #[arg(help = "Print the worktrees which would be removed without removing them")]
+        dry_run: bool,
+
+        #[arg(short, long)]
+        #[arg(help = "Choose the worktree to remove when several match")]
+        interactive: bool,
+    },
+
+    #[command(about = "Remove repositories which were not touched for a while")]
+    Prune {
+        #[arg(long, value_name = "AGE", value_parser = parse_age)]
+        #[arg(help = "Remove repositories untouched for longer than this, e.g. 90d, 2w or 6m")]
+        since: Duration,
+
+        #[arg(short, long)]
+        #[arg(help = "Remove without confirmation")]
+        force: bool,
+    },
+
+    #[command(about = "Move a worktree to the directory of another branch name")]
+    Move {
+        #[arg(help = "Git repository URL")]
+        url: String,
+
+        #[arg(help = "Current branch name of the worktree directory")]
+        old_branch: String,
+
+        #[arg(help = "New branch name of the worktree directory")]
+        new_branch: String,
+
+        #[arg(long)]
+        #[arg(help = "Also rename the git branch (git branch -m)")]
+        rename_branch: bool,
+    },
+
+    #[command(about = "Show a summary of managed repositories")]
+    Stats {
+        #[arg(long, value_enum, default_value_t = FormatArg::Text)]
+        #[arg(help = "Output format")]
+        format: FormatArg,
+
+        #[arg(long, help = "Skip disk usage computation")]
+        no_size: bool,
+    },
+
+    #[command(about = "List worktrees with uncommitted changes")]
+    Status {
+        #[arg(short, long, help = "Also list clean worktrees")]
+        all: bool,
+    },
+
+    #[command(about = "Print a shell completion script to stdout")]
+    Completions {
+        #[arg(value_enum, help = "Shell to generate the script for")]
+        shell: Shell,
+    },
+
+    #[command(about = "Read or change settings in ~/.grmrc")]
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
 
     #[command(about = "Manage git worktree")]
@@ -141,24 +794,117 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    #[command(about = "Print the effective value of a setting")]
+    Get {
+        #[arg(help = "Setting name, e.g. root or default_host")]
+        key: String,
+    },
+
+    #[command(about = "Write a setting to ~/.grmrc")]
+    Set {
+        #[arg(help = "Setting name, e.g. root or default_host")]
+        key: String,
+
+        #[arg(help = "New value (comma-separated patterns for share_deny)")]
+        value: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum WorktreeCommands {
     #[command(about = "Create a new worktree for a branch")]
     Split {
         #[arg(help = "Branch name")]
         branch: String,
+
+        #[arg(long)]
+        #[arg(help = "Share group to assign to the new worktree (repeatable)")]
+        group: Vec<String>,
+
+        #[arg(long, help = "Fetch all remotes before creating the worktree")]
+        fetch: bool,
+
+        #[arg(long, value_name = "REF")]
+        #[arg(
+            help = "Check out a tag or commit with a detached HEAD, <branch> only names the worktree"
+        )]
+        at: Option<String>,
+
+        #[arg(long, value_name = "NAME")]
+        #[arg(help = "Remote used to locate the repository under the root (defaults to origin)")]
+        remote: Option<String>,
+    },
+
+    #[command(about = "List worktrees of the current repository")]
+    List {
+        #[arg(short, long, help = "Show full absolute paths")]
+        full_path: bool,
+
+        #[arg(
+            long,
+            help = "Only list worktrees under the root, leaving out ones created elsewhere"
+        )]
+        managed_only: bool,
+    },
+
+    #[command(about = "Print the path of a sibling worktree")]
+    Switch {
+        #[arg(help = "Branch name")]
+        branch: String,
+
+        #[arg(short, long, help = "Create the worktree if it does not exist")]
+        create: bool,
+
+        #[arg(long, value_name = "NAME")]
+        #[arg(help = "Remote used to locate the repository under the root (defaults to origin)")]
+        remote: Option<String>,
     },
 
     #[command(about = "Remove a worktree")]
     Remove {
         #[arg(help = "Branch name")]
         branch: String,
+
+        #[arg(long, value_name = "NAME")]
+        #[arg(help = "Remote used to locate the repository under the root (defaults to origin)")]
+        remote: Option<String>,
+
+        #[arg(long)]
+        #[arg(help = "Also delete the local branch (even if unmerged)")]
+        delete_branch: bool,
+    },
+
+    #[command(about = "Remove worktrees whose branch is gone")]
+    Prune {
+        #[arg(short, long)]
+        #[arg(help = "Force removal without confirmation")]
+        force: bool,
+
+        #[arg(long, value_name = "NAME")]
+        #[arg(help = "Remote asked whether a branch still exists (defaults to origin)")]
+        remote: Option<String>,
     },
 
     #[command(about = "Share a file/directory between worktree")]
     Share {
         #[arg(help = "Path to file/directory to share")]
         path: String,
+
+        #[arg(long)]
+        #[arg(help = "Share even if the path matches the share_deny patterns")]
+        allow_sensitive: bool,
+
+        #[arg(long)]
+        #[arg(help = "Only share with worktrees assigned to this group")]
+        group: Option<String>,
+
+        #[arg(long)]
+        #[arg(
+            help = "Copy into shared storage instead of moving, keeping the original until it is linked"
+        )]
+        copy: bool,
     },
 
     #[command(about = "Unshare a file/directory")]
@@ -167,9 +913,78 @@ enum WorktreeCommands {
         path: String,
     },
 
+    #[command(about = "List shared files/directories with their group")]
+    Shared,
+
     #[command(about = "Isolate a shared file/directory (copy to local)")]
     Isolate {
+        #[arg(required_unless_present = "all")]
         #[arg(help = "Path to shared file/directory")]
-        path: String,
+        path: Option<String>,
+
+        #[arg(long, conflicts_with = "path")]
+        #[arg(help = "Isolate every shared file/directory linked into the current worktree")]
+        all: bool,
     },
 }
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum FormatArg {
+    Text,
+    Json,
+}
+
+impl From<FormatArg> for StatsFormat {
+    fn from(format: FormatArg) -> Self {
+        match format {
+            FormatArg::Text => StatsFormat::Text,
+            FormatArg::Json => StatsFormat::Json,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::QuietInteraction;
+    use crate::adapters::test_helpers::MockUserInteraction;
+
+    #[test]
+    fn test_clone_ui_open() {
+        // 目的: --open 時の出力先
+        // 検証: 進捗は stderr に移り、パスだけが stdout に出る
+
+        let inner = Arc::new(MockUserInteraction::new());
+        let ui = clone_ui(inner.clone(), true, false);
+
+        ui.print("Repository cloned to: /grm/github.com/user/repo+main");
+        ui.print_result("/grm/github.com/user/repo+main");
+
+        assert_eq!(
+            inner.get_printed_messages(),
+            vec!["/grm/github.com/user/repo+main".to_string()]
+        );
+        assert_eq!(
+            inner.get_error_messages(),
+            vec!["Repository cloned to: /grm/github.com/user/repo+main".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_clone_ui_open_quiet() {
+        // 目的: --quiet と --open の併用
+        // 検証: 進捗はどこにも出ず、パスは stdout に出る
+
+        let inner = Arc::new(MockUserInteraction::new());
+        let ui = clone_ui(Arc::new(QuietInteraction::new(inner.clone())), true, true);
+
+        ui.print("Repository cloned to: /grm/github.com/user/repo+main");
+        ui.print_result("/grm/github.com/user/repo+main");
+
+        assert_eq!(
+            inner.get_printed_messages(),
+            vec!["/grm/github.com/user/repo+main".to_string()]
+        );
+        assert!(inner.get_error_messages().is_empty());
+    }
+}
@@ -6,10 +6,11 @@
 //!
 //! # Configuration Priority
 //!
-//! 1. Environment variable `GRM_ROOT`
-//! 2. `~/.grmrc` (TOML format)
-//! 3. `~/.gitconfig` ([grm] section)
-//! 4. Default: `~/grm`
+//! 1. Command line option `--root` (root only, see `Config::with_root`)
+//! 2. Environment variable `GRM_ROOT`
+//! 3. `~/.grmrc` (TOML format)
+//! 4. `~/.gitconfig` ([grm] section)
+//! 5. Default: `~/grm`
 
 // Internal provider implementations (private)
 mod default_provider;
@@ -18,10 +19,13 @@ mod gitconfig_provider;
 mod grmrc_provider;
 pub(crate) mod provider; // Available within crate for testing
 
+use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use thiserror::Error;
 
+use crate::core::Layout;
+use crate::core::layout::DEFAULT_LAYOUT;
 use crate::core::ports::FileSystemError;
 
 #[derive(Debug, Error)]
@@ -40,6 +44,132 @@ pub enum ConfigError {
 
     #[error("File system error: {0}")]
     FileSystem(#[from] FileSystemError),
+
+    #[error("Unknown config key `{0}` (expected one of: {keys})", keys = GRMRC_KEYS.join(", "))]
+    UnknownKey(String),
+
+    #[error("Invalid value `{value}` for `{key}`: {reason}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+}
+
+/// Configuration source which supplied `root`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// Command line option `--root`
+    Flag,
+    /// Environment variable `GRM_ROOT`
+    Env,
+    /// `~/.grmrc`
+    Grmrc,
+    /// `[grm]` section of `~/.gitconfig`
+    GitConfig,
+    /// Built-in `~/grm`
+    Default,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConfigSource::Flag => "--root",
+            ConfigSource::Env => "GRM_ROOT",
+            ConfigSource::Grmrc => "~/.grmrc",
+            ConfigSource::GitConfig => "~/.gitconfig",
+            ConfigSource::Default => "default",
+        })
+    }
+}
+
+/// Default glob patterns of files which must never be shared
+pub const DEFAULT_SHARE_DENY: &[&str] = &["*.pem", "*.key", "id_*", "credentials*"];
+
+/// Default lifetime in seconds of cached remote default branches (one day)
+pub const DEFAULT_BRANCH_CACHE_TTL: u64 = 24 * 60 * 60;
+
+/// Default seconds a git command whose output grm reads may run before it is killed
+pub const DEFAULT_GIT_TIMEOUT_SECS: u64 = 60;
+
+/// Keys of `~/.grmrc` understood by `grm config`
+pub const GRMRC_KEYS: &[&str] = &[
+    "root",
+    "share_deny",
+    "default_link",
+    "default_host",
+    "clone_depth",
+    "default_branch_cache_ttl",
+    "post_clone",
+    "normalize_host",
+    "layout",
+    "git_timeout_secs",
+    "clone_retries",
+];
+
+/// Convert `value` given on the command line to the TOML value stored for `key`
+///
+/// `share_deny` takes comma-separated patterns. Paths are stored as given.
+pub fn grmrc_value(key: &str, value: &str) -> Result<toml::Value, ConfigError> {
+    let invalid = |reason: String| ConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+        reason,
+    };
+
+    Ok(match key {
+        "root" | "default_host" | "post_clone" => toml::Value::String(value.to_string()),
+        "layout" => {
+            Layout::parse(value).map_err(|e| invalid(e.reason))?;
+            toml::Value::String(value.to_string())
+        }
+        "share_deny" => toml::Value::Array(
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|pattern| !pattern.is_empty())
+                .map(|pattern| toml::Value::String(pattern.to_string()))
+                .collect(),
+        ),
+        "default_link" | "normalize_host" => {
+            toml::Value::Boolean(value.parse().map_err(|e| invalid(format!("{e}")))?)
+        }
+        "clone_depth" | "clone_retries" => {
+            let depth: u32 = value.parse().map_err(|e| invalid(format!("{e}")))?;
+            toml::Value::Integer(i64::from(depth))
+        }
+        "default_branch_cache_ttl" | "git_timeout_secs" => {
+            let ttl: u64 = value.parse().map_err(|e| invalid(format!("{e}")))?;
+            toml::Value::Integer(i64::try_from(ttl).map_err(|e| invalid(format!("{e}")))?)
+        }
+        _ => return Err(ConfigError::UnknownKey(key.to_string())),
+    })
+}
+
+/// Starter `~/.grmrc` content using `root`
+///
+/// Optional settings are included as comments showing their defaults.
+pub fn grmrc_template(root: &Path) -> String {
+    let root = toml::Value::String(root.display().to_string());
+    let share_deny = DEFAULT_SHARE_DENY
+        .iter()
+        .map(|pattern| format!("\"{pattern}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "root = {root}\n\
+         # share_deny = [{share_deny}]\n\
+         # default_link = false\n\
+         # default_host = \"github.com\"\n\
+         # clone_depth = 1\n\
+         # default_branch_cache_ttl = {DEFAULT_BRANCH_CACHE_TTL}\n\
+         # post_clone = \"direnv allow\"\n\
+         # normalize_host = true\n\
+         # layout = \"{DEFAULT_LAYOUT}\"\n\
+         # git_timeout_secs = {DEFAULT_GIT_TIMEOUT_SECS}\n\
+         # clone_retries = 0\n"
+    )
 }
 
 /// Grm configuration manager
@@ -47,9 +177,49 @@ pub enum ConfigError {
 pub struct Config {
     /// Root directory for repository management
     pub root: PathBuf,
+    /// Glob patterns of files which must never be moved into shared storage
+    pub share_deny: Vec<String>,
+    /// Whether `clone` links `{host}/{user}/{repo}` to the default-branch worktree
+    pub default_link: bool,
+    /// Host prepended to `user/repo` URLs
+    pub default_host: Option<String>,
+    /// Default `--depth` for `clone`
+    pub clone_depth: Option<u32>,
+    /// Seconds a cached remote default branch stays valid, `0` disables the cache
+    pub default_branch_cache_ttl: u64,
+    /// Shell command run in each new clone, e.g. `npm install`
+    pub post_clone: Option<String>,
+    /// Whether hosts are lowercased and stripped of `www.`, see `RepoInfo::from_url_with`
+    pub normalize_host: bool,
+    /// Directory layout of worktrees below `root`
+    pub layout: Layout,
+    /// Seconds a git command whose output is read may run, `0` waits forever
+    pub git_timeout_secs: u64,
+    /// Default `--retries` for `clone`, `0` fails on the first error
+    pub clone_retries: u32,
+    /// Where `root` came from, `Default` for a `Config` built with `new`
+    pub source: ConfigSource,
 }
 
 impl Config {
+    /// Build a configuration with the given root and default settings
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            share_deny: DEFAULT_SHARE_DENY.iter().map(ToString::to_string).collect(),
+            default_link: false,
+            default_host: None,
+            clone_depth: None,
+            default_branch_cache_ttl: DEFAULT_BRANCH_CACHE_TTL,
+            post_clone: None,
+            normalize_host: true,
+            layout: Layout::default(),
+            git_timeout_secs: DEFAULT_GIT_TIMEOUT_SECS,
+            clone_retries: 0,
+            source: ConfigSource::Default,
+        }
+    }
+
     /// Load configuration and build Grm Config
     ///
     /// Priority order:
@@ -58,10 +228,10 @@ impl Config {
     /// 3. ~/.gitconfig ([grm] section)
     /// 4. Default: ~/grm
     pub fn load() -> Result<Self, ConfigError> {
-        use crate::adapters::unix_fs::UnixFs;
+        use crate::adapters::NativeFs;
         use provider::ConfigProvider;
 
-        let fs = Arc::new(UnixFs::new());
+        let fs = Arc::new(NativeFs::new());
 
         // Build the provider chain in priority order
         let providers: Vec<Box<dyn ConfigProvider>> = vec![
@@ -71,12 +241,70 @@ impl Config {
             Box::new(default_provider::DefaultProvider::new(fs.clone())),
         ];
 
-        // Try each provider in order until one returns a value
+        let (root, source) = Self::first_of(&providers, |p| {
+            Ok(p.load_root()?.map(|root| (root, p.source())))
+        })?;
+        let share_deny = Self::first_of(&providers, |p| p.load_share_deny())?;
+        let default_link = Self::first_of(&providers, |p| p.load_default_link())?;
+        let default_host = Self::find_first(&providers, |p| p.load_default_host())?;
+        let clone_depth = Self::find_first(&providers, |p| p.load_clone_depth())?;
+        let default_branch_cache_ttl =
+            Self::find_first(&providers, |p| p.load_default_branch_cache_ttl())?
+                .unwrap_or(DEFAULT_BRANCH_CACHE_TTL);
+        let post_clone = Self::find_first(&providers, |p| p.load_post_clone())?;
+        let normalize_host =
+            Self::find_first(&providers, |p| p.load_normalize_host())?.unwrap_or(true);
+        let layout = Self::find_first(&providers, |p| p.load_layout())?
+            .map(|template| {
+                Layout::parse(&template).map_err(|e| ConfigError::InvalidValue {
+                    key: "layout".to_string(),
+                    value: template.clone(),
+                    reason: e.reason,
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let git_timeout_secs = Self::find_first(&providers, |p| p.load_git_timeout_secs())?
+            .unwrap_or(DEFAULT_GIT_TIMEOUT_SECS);
+        let clone_retries =
+            Self::find_first(&providers, |p| p.load_clone_retries())?.unwrap_or_default();
+
+        Ok(Config {
+            share_deny,
+            default_link,
+            default_host,
+            clone_depth,
+            default_branch_cache_ttl,
+            post_clone,
+            normalize_host,
+            layout,
+            git_timeout_secs,
+            clone_retries,
+            source,
+            ..Config::new(root)
+        })
+    }
+
+    /// Try each provider in order until one returns a value
+    fn first_of<T>(
+        providers: &[Box<dyn provider::ConfigProvider>],
+        load: impl Fn(&dyn provider::ConfigProvider) -> Result<Option<T>, ConfigError>,
+    ) -> Result<T, ConfigError> {
+        // DefaultProvider should always return Some, so this is unreachable
+        Ok(Self::find_first(providers, load)?
+            .unwrap_or_else(|| unreachable!("DefaultProvider should always return a value")))
+    }
+
+    /// Try each provider in order, returning `None` for optional settings no provider has
+    fn find_first<T>(
+        providers: &[Box<dyn provider::ConfigProvider>],
+        load: impl Fn(&dyn provider::ConfigProvider) -> Result<Option<T>, ConfigError>,
+    ) -> Result<Option<T>, ConfigError> {
         for provider in providers {
-            match provider.load_root() {
-                Ok(Some(root)) => {
+            match load(provider.as_ref()) {
+                Ok(Some(value)) => {
                     // Found a configuration, return it
-                    return Ok(Config { root });
+                    return Ok(Some(value));
                 }
                 Ok(None) => {}
                 Err(e) => {
@@ -86,11 +314,52 @@ impl Config {
             }
         }
 
-        // DefaultProvider should always return Some, so this is unreachable
-        unreachable!("DefaultProvider should always return a value")
+        Ok(None)
+    }
+
+    /// Replace the loaded root with the one given by `--root`
+    ///
+    /// `root` may be relative to the current directory or start with `~`.
+    pub fn with_root(self, root: &str) -> Result<Self, ConfigError> {
+        use crate::adapters::NativeFs;
+        use crate::core::ports::FileSystem;
+
+        let fs = NativeFs::new();
+        let root = fs.normalize(Path::new(root), &fs.current_dir()?)?;
+
+        Ok(Config {
+            root,
+            source: ConfigSource::Flag,
+            ..self
+        })
     }
 
     pub fn root(&self) -> &Path {
         &self.root
     }
+
+    /// Effective value of the `~/.grmrc` key `key`, `None` if it is not set
+    ///
+    /// Lists are joined with `,`, as accepted by `grmrc_value`.
+    pub fn get(&self, key: &str) -> Result<Option<String>, ConfigError> {
+        Ok(match key {
+            "root" => Some(self.root.display().to_string()),
+            "share_deny" => Some(self.share_deny.join(",")),
+            "default_link" => Some(self.default_link.to_string()),
+            "default_host" => self.default_host.clone(),
+            "clone_depth" => self.clone_depth.map(|depth| depth.to_string()),
+            "default_branch_cache_ttl" => Some(self.default_branch_cache_ttl.to_string()),
+            "post_clone" => self.post_clone.clone(),
+            "normalize_host" => Some(self.normalize_host.to_string()),
+            "layout" => Some(self.layout.to_string()),
+            "git_timeout_secs" => Some(self.git_timeout_secs.to_string()),
+            "clone_retries" => Some(self.clone_retries.to_string()),
+            _ => return Err(ConfigError::UnknownKey(key.to_string())),
+        })
+    }
+
+    /// `{root}/.cache`, where grm keeps data it can rebuild
+    pub fn cache_dir(&self) -> PathBuf {
+        self.root.join(".cache")
+    }
 }
@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::configs::{ConfigError, ConfigSource, DEFAULT_SHARE_DENY, provider::ConfigProvider};
 use crate::core::ports::FileSystem;
-use crate::configs::{ConfigError, provider::ConfigProvider};
 
 /// Provider for the default configuration value
 ///
-/// Always returns `~/grm` as the root directory.
+/// Always returns `~/grm` as the root directory and the built-in share deny-list,
+/// with the default-branch link disabled.
 /// This provider should be last in the priority chain as a fallback.
 pub struct DefaultProvider {
     fs: Arc<dyn FileSystem>,
@@ -19,8 +20,22 @@ impl DefaultProvider {
 }
 
 impl ConfigProvider for DefaultProvider {
+    fn source(&self) -> ConfigSource {
+        ConfigSource::Default
+    }
+
     fn load_root(&self) -> Result<Option<PathBuf>, ConfigError> {
         let home = self.fs.home_dir()?;
         Ok(Some(home.join("grm")))
     }
+
+    fn load_share_deny(&self) -> Result<Option<Vec<String>>, ConfigError> {
+        Ok(Some(
+            DEFAULT_SHARE_DENY.iter().map(ToString::to_string).collect(),
+        ))
+    }
+
+    fn load_default_link(&self) -> Result<Option<bool>, ConfigError> {
+        Ok(Some(false))
+    }
 }
@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::configs::{ConfigError, ConfigSource, provider::ConfigProvider};
 use crate::core::ports::FileSystem;
-use crate::configs::{ConfigError, provider::ConfigProvider};
 
 /// Provider for environment variable configuration
 ///
@@ -18,6 +18,10 @@ impl EnvProvider {
 }
 
 impl ConfigProvider for EnvProvider {
+    fn source(&self) -> ConfigSource {
+        ConfigSource::Env
+    }
+
     fn load_root(&self) -> Result<Option<PathBuf>, ConfigError> {
         match std::env::var("GRM_ROOT") {
             Ok(path_str) => {
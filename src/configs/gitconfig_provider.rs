@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::configs::{ConfigError, ConfigSource, provider::ConfigProvider};
 use crate::core::ports::FileSystem;
-use crate::configs::{ConfigError, provider::ConfigProvider};
 
 const GIT_CONFIG_ROOT_KEY: &str = "grm.root";
 
@@ -27,6 +27,10 @@ impl GitConfigProvider {
 }
 
 impl ConfigProvider for GitConfigProvider {
+    fn source(&self) -> ConfigSource {
+        ConfigSource::GitConfig
+    }
+
     fn load_root(&self) -> Result<Option<PathBuf>, ConfigError> {
         // Try to open the default git config
         let config = match git2::Config::open_default() {
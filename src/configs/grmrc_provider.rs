@@ -3,13 +3,23 @@ use std::sync::Arc;
 
 use serde::Deserialize;
 
+use crate::configs::{ConfigError, ConfigSource, provider::ConfigProvider};
 use crate::core::ports::FileSystem;
-use crate::configs::{ConfigError, provider::ConfigProvider};
 
 /// TOML structure for .grmrc file
 #[derive(Debug, Deserialize)]
 struct GrmrcFile {
-    root: String,
+    root: Option<String>,
+    share_deny: Option<Vec<String>>,
+    default_link: Option<bool>,
+    default_host: Option<String>,
+    clone_depth: Option<u32>,
+    default_branch_cache_ttl: Option<u64>,
+    post_clone: Option<String>,
+    normalize_host: Option<bool>,
+    layout: Option<String>,
+    git_timeout_secs: Option<u64>,
+    clone_retries: Option<u32>,
 }
 
 /// Provider for ~/.grmrc configuration file
@@ -18,6 +28,16 @@ struct GrmrcFile {
 ///
 /// ```toml
 /// root = "/path/to/root"
+/// share_deny = ["*.pem", "*.key"]
+/// default_link = true
+/// default_host = "github.com"
+/// clone_depth = 1
+/// default_branch_cache_ttl = 86400
+/// post_clone = "direnv allow"
+/// normalize_host = true
+/// layout = "{host}/{user}/{repo}+{branch}"
+/// git_timeout_secs = 60
+/// clone_retries = 3
 /// ```
 pub struct GrmrcProvider {
     fs: Arc<dyn FileSystem>,
@@ -27,10 +47,8 @@ impl GrmrcProvider {
     pub fn new(fs: Arc<dyn FileSystem>) -> Self {
         Self { fs }
     }
-}
 
-impl ConfigProvider for GrmrcProvider {
-    fn load_root(&self) -> Result<Option<PathBuf>, ConfigError> {
+    fn read(&self) -> Result<Option<GrmrcFile>, ConfigError> {
         let home = self.fs.home_dir()?;
 
         let grmrc_path = home.join(".grmrc");
@@ -46,10 +64,108 @@ impl ConfigProvider for GrmrcProvider {
         let parsed: GrmrcFile = toml::from_str(&content)
             .map_err(|e| ConfigError::Parse(format!("Failed to parse .grmrc: {e}")))?;
 
+        Ok(Some(parsed))
+    }
+}
+
+impl ConfigProvider for GrmrcProvider {
+    fn source(&self) -> ConfigSource {
+        ConfigSource::Grmrc
+    }
+
+    fn load_root(&self) -> Result<Option<PathBuf>, ConfigError> {
+        let Some(root) = self.read()?.and_then(|parsed| parsed.root) else {
+            return Ok(None);
+        };
+
         // Normalize the path
-        let path = std::path::Path::new(&parsed.root);
+        let home = self.fs.home_dir()?;
+        let path = std::path::Path::new(&root);
         let normalized = self.fs.normalize(path, &home)?;
 
         Ok(Some(normalized))
     }
+
+    fn load_share_deny(&self) -> Result<Option<Vec<String>>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.share_deny))
+    }
+
+    fn load_default_link(&self) -> Result<Option<bool>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.default_link))
+    }
+
+    fn load_default_host(&self) -> Result<Option<String>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.default_host))
+    }
+
+    fn load_clone_depth(&self) -> Result<Option<u32>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.clone_depth))
+    }
+
+    fn load_default_branch_cache_ttl(&self) -> Result<Option<u64>, ConfigError> {
+        Ok(self
+            .read()?
+            .and_then(|parsed| parsed.default_branch_cache_ttl))
+    }
+
+    fn load_post_clone(&self) -> Result<Option<String>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.post_clone))
+    }
+
+    fn load_normalize_host(&self) -> Result<Option<bool>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.normalize_host))
+    }
+
+    fn load_layout(&self) -> Result<Option<String>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.layout))
+    }
+
+    fn load_git_timeout_secs(&self) -> Result<Option<u64>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.git_timeout_secs))
+    }
+
+    fn load_clone_retries(&self) -> Result<Option<u32>, ConfigError> {
+        Ok(self.read()?.and_then(|parsed| parsed.clone_retries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_root_only() {
+        // 目的: root のみの .grmrc の後方互換性
+        // 検証: 追加のキーは None として読み込まれる
+
+        let parsed: GrmrcFile = toml::from_str("root = \"~/grm\"\n").unwrap();
+
+        assert_eq!(parsed.root.as_deref(), Some("~/grm"));
+        assert!(parsed.default_host.is_none());
+        assert!(parsed.clone_depth.is_none());
+    }
+
+    #[test]
+    fn test_parse_optional_keys() {
+        // 目的: default_host と clone_depth の読み込み
+        // 検証: 記載された値が読み込まれる
+
+        let parsed: GrmrcFile = toml::from_str(
+            "default_host = \"gitlab.com\"\nclone_depth = 1\ndefault_branch_cache_ttl = 0\npost_clone = \"npm install\"\nnormalize_host = false\nlayout = \"{host}/{user}/{repo}/{branch}\"\ngit_timeout_secs = 5\nclone_retries = 3\n",
+        )
+        .unwrap();
+
+        assert!(parsed.root.is_none());
+        assert_eq!(parsed.default_host.as_deref(), Some("gitlab.com"));
+        assert_eq!(parsed.clone_depth, Some(1));
+        assert_eq!(parsed.default_branch_cache_ttl, Some(0));
+        assert_eq!(parsed.post_clone.as_deref(), Some("npm install"));
+        assert_eq!(parsed.normalize_host, Some(false));
+        assert_eq!(
+            parsed.layout.as_deref(),
+            Some("{host}/{user}/{repo}/{branch}")
+        );
+        assert_eq!(parsed.git_timeout_secs, Some(5));
+        assert_eq!(parsed.clone_retries, Some(3));
+    }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::configs::ConfigError;
+use crate::configs::{ConfigError, ConfigSource};
 
 /// Trait for configuration providers
 ///
@@ -9,6 +9,9 @@ use crate::configs::ConfigError;
 ///
 /// Providers are executed in priority order until one successfully returns a value.
 pub trait ConfigProvider {
+    /// The configuration source this provider reads
+    fn source(&self) -> ConfigSource;
+
     /// Attempt to load the root path from this configuration source
     ///
     /// # Returns
@@ -17,4 +20,74 @@ pub trait ConfigProvider {
     /// - `Ok(None)`: Configuration source does not exist (try next provider)
     /// - `Err(e)`: Configuration exists but failed to parse (stop immediately)
     fn load_root(&self) -> Result<Option<PathBuf>, ConfigError>;
+
+    /// Attempt to load the deny-list of glob patterns for sharing
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_share_deny(&self) -> Result<Option<Vec<String>>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load whether `clone` links `{repo}` to the default-branch worktree
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_default_link(&self) -> Result<Option<bool>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load the host prepended to `user/repo` URLs
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_default_host(&self) -> Result<Option<String>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load the default `--depth` for `clone`
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_clone_depth(&self) -> Result<Option<u32>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load how many seconds a cached remote default branch stays valid
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_default_branch_cache_ttl(&self) -> Result<Option<u64>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load the shell command run after `clone`
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_post_clone(&self) -> Result<Option<String>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load whether hosts of repository URLs are normalized
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_normalize_host(&self) -> Result<Option<bool>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load the directory layout template of worktrees
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_layout(&self) -> Result<Option<String>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load how many seconds a git command may run before it is killed
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_git_timeout_secs(&self) -> Result<Option<u64>, ConfigError> {
+        Ok(None)
+    }
+
+    /// Attempt to load how many times a failed `clone` is retried
+    ///
+    /// Providers which do not support this setting return `Ok(None)`.
+    fn load_clone_retries(&self) -> Result<Option<u32>, ConfigError> {
+        Ok(None)
+    }
 }
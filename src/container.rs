@@ -1,20 +1,52 @@
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::adapters::{GitCli, TerminalInteraction, UnixFs};
-use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::adapters::{
+    DEFAULT_BRANCH_CACHE_FILE, DefaultBranchCache, GitCli, NativeFs, ProcessRunner,
+    QuietInteraction, SystemClock, TerminalInteraction,
+};
+use crate::configs::Config;
+use crate::core::ports::{Clock, CommandRunner, FileSystem, GitRepository, UserInteraction};
 
 pub struct AppContainer {
-    pub git: Arc<dyn GitRepository>,
-    pub fs: Arc<dyn FileSystem>,
-    pub ui: Arc<dyn UserInteraction>,
+    pub git: Arc<dyn GitRepository + Send + Sync>,
+    pub fs: Arc<dyn FileSystem + Send + Sync>,
+    pub ui: Arc<dyn UserInteraction + Send + Sync>,
+    pub runner: Arc<dyn CommandRunner + Send + Sync>,
+    pub clock: Arc<dyn Clock + Send + Sync>,
 }
 
 impl AppContainer {
-    pub fn new() -> Self {
+    /// # Arguments
+    /// * `quiet` - Drop informational output, keeping errors and prompts
+    pub fn new(config: &Config, quiet: bool) -> Self {
+        let fs = Arc::new(NativeFs::new());
+        let clock = Arc::new(SystemClock::new());
+
+        let default_branch_cache = DefaultBranchCache::new(
+            fs.clone(),
+            clock.clone(),
+            config.cache_dir().join(DEFAULT_BRANCH_CACHE_FILE),
+            Duration::from_secs(config.default_branch_cache_ttl),
+        );
+
+        let ui: Arc<dyn UserInteraction + Send + Sync> = Arc::new(TerminalInteraction::new());
+        let ui: Arc<dyn UserInteraction + Send + Sync> = if quiet {
+            Arc::new(QuietInteraction::new(ui))
+        } else {
+            ui
+        };
+
         Self {
-            git: Arc::new(GitCli::new()),
-            fs: Arc::new(UnixFs::new()),
-            ui: Arc::new(TerminalInteraction::new()),
+            git: Arc::new(
+                GitCli::new()
+                    .with_default_branch_cache(default_branch_cache)
+                    .with_timeout(Duration::from_secs(config.git_timeout_secs)),
+            ),
+            fs,
+            ui,
+            runner: Arc::new(ProcessRunner::new()),
+            clock,
         }
     }
 }
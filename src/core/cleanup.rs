@@ -0,0 +1,120 @@
+//! Cleanup of directories left behind by removals
+
+use std::path::{Path, PathBuf};
+
+use crate::core::ports::FileSystem;
+
+/// Remove directories above `removed` which became empty
+///
+/// Walks upward from the parent of `removed` toward `root` (never removing
+/// `root` itself) and stops at the first directory which is not empty or
+/// cannot be removed. Failures are not errors: a directory which gained
+/// entries concurrently is simply kept.
+///
+/// # Returns
+/// The directories which were removed, innermost first
+pub fn remove_empty_parents(fs: &dyn FileSystem, root: &Path, removed: &Path) -> Vec<PathBuf> {
+    let mut pruned = Vec::new();
+
+    let mut current = removed.parent();
+    while let Some(dir) = current {
+        if dir == root || !dir.starts_with(root) {
+            break;
+        }
+        if fs.remove_empty_dir(dir).is_err() {
+            break;
+        }
+        pruned.push(dir.to_path_buf());
+        current = dir.parent();
+    }
+
+    pruned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    #[test]
+    fn test_remove_empty_parents_two_levels() {
+        // 目的: host/user の両方が空になった場合
+        // 検証: user と host が削除され、root は残る
+        let fs = MockFileSystem::new();
+        fs.add_dir("/root");
+        fs.add_dir("/root/git.example.org");
+        fs.add_dir("/root/git.example.org/olduser");
+
+        let pruned = remove_empty_parents(
+            &fs,
+            Path::new("/root"),
+            Path::new("/root/git.example.org/olduser/repo+main"),
+        );
+
+        assert_eq!(
+            pruned,
+            vec![
+                PathBuf::from("/root/git.example.org/olduser"),
+                PathBuf::from("/root/git.example.org"),
+            ]
+        );
+        assert!(fs.exists(Path::new("/root")));
+    }
+
+    #[test]
+    fn test_remove_empty_parents_one_level() {
+        // 目的: user のみ空になった場合
+        // 検証: user だけが削除され、他ユーザーを含む host は残る
+        let fs = MockFileSystem::new();
+        fs.add_dir("/root");
+        fs.add_dir("/root/github.com");
+        fs.add_dir("/root/github.com/olduser");
+        fs.add_dir("/root/github.com/other");
+        fs.add_git_repo("/root/github.com/other/repo+main");
+
+        let pruned = remove_empty_parents(
+            &fs,
+            Path::new("/root"),
+            Path::new("/root/github.com/olduser/repo+main"),
+        );
+
+        assert_eq!(pruned, vec![PathBuf::from("/root/github.com/olduser")]);
+        assert!(fs.exists(Path::new("/root/github.com")));
+    }
+
+    #[test]
+    fn test_remove_empty_parents_keeps_non_empty() {
+        // 目的: 兄弟ワークツリーが残っている場合
+        // 検証: 何も削除されない
+        let fs = MockFileSystem::new();
+        fs.add_dir("/root");
+        fs.add_dir("/root/github.com");
+        fs.add_dir("/root/github.com/user");
+        fs.add_git_repo("/root/github.com/user/repo+dev");
+
+        let pruned = remove_empty_parents(
+            &fs,
+            Path::new("/root"),
+            Path::new("/root/github.com/user/repo+main"),
+        );
+
+        assert!(pruned.is_empty());
+        assert!(fs.exists(Path::new("/root/github.com/user")));
+    }
+
+    #[test]
+    fn test_remove_empty_parents_tolerates_missing_dir() {
+        // 目的: 親ディレクトリが既に削除されている(競合)場合
+        // 検証: エラーにならず、処理が止まる
+        let fs = MockFileSystem::new();
+        fs.add_dir("/root");
+
+        let pruned = remove_empty_parents(
+            &fs,
+            Path::new("/root"),
+            Path::new("/root/github.com/user/repo+main"),
+        );
+
+        assert!(pruned.is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Directories excluded from scanning by `{root}/.grmignore`
+//!
+//! The file uses `.gitignore` syntax, one pattern per line:
+//!
+//! ```text
+//! # vendored trees
+//! archive/
+//! /scratch-*
+//! !scratch-keep
+//! ```
+//!
+//! - Blank lines and lines starting with `#` are ignored; `\#` and `\!` escape a leading `#` or `!`
+//! - A pattern without `/` matches a directory name at any depth
+//! - A pattern containing `/` matches the path relative to the root
+//! - A pattern starting with `!` re-includes what an earlier pattern excluded;
+//!   the last matching pattern wins, but nothing below an excluded directory is re-included
+//! - Lines that are not valid patterns are skipped, as git does
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+use crate::core::ports::{FileSystem, FileSystemError};
+
+/// File name of the ignore file inside the root
+pub const GRMIGNORE_FILE: &str = ".grmignore";
+
+#[derive(Debug, Clone)]
+pub struct GrmIgnore {
+    root: PathBuf,
+    matcher: Gitignore,
+}
+
+impl Default for GrmIgnore {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            matcher: Gitignore::empty(),
+        }
+    }
+}
+
+impl GrmIgnore {
+    /// Read `{root}/.grmignore`, ignoring nothing if it does not exist
+    pub fn load(fs: &dyn FileSystem, root: &Path) -> Result<Self, FileSystemError> {
+        let path = root.join(GRMIGNORE_FILE);
+        if !fs.exists(&path) {
+            return Ok(Self::parse(root, ""));
+        }
+
+        Ok(Self::parse(root, &fs.read_to_string(&path)?))
+    }
+
+    pub fn parse(root: &Path, content: &str) -> Self {
+        let mut builder = GitignoreBuilder::new(root);
+        for line in content.lines() {
+            // An invalid pattern only drops its own line
+            let _ = builder.add_line(None, line);
+        }
+
+        Self {
+            root: root.to_path_buf(),
+            matcher: builder.build().unwrap_or_else(|_| Gitignore::empty()),
+        }
+    }
+
+    /// Whether the directory `dir` under the root should not be scanned
+    pub fn is_ignored(&self, dir: &Path) -> bool {
+        let Ok(relative) = dir.strip_prefix(&self.root) else {
+            return false;
+        };
+        if relative.as_os_str().is_empty() {
+            return false;
+        }
+
+        self.matcher
+            .matched_path_or_any_parents(relative, true)
+            .is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    #[test]
+    fn test_is_ignored_name_and_anchored_patterns() {
+        // 目的: 名前だけのパターンと `/` を含むパターンの違い
+        // 検証: 名前のパターンは任意の深さに、`/` を含むパターンはルートからの相対パスにのみ一致する
+
+        let ignore = GrmIgnore::parse(
+            Path::new("/grm"),
+            "# comment\n\nnode_modules\n/archive/\ngithub.com/old-*\n",
+        );
+
+        assert!(ignore.is_ignored(Path::new("/grm/node_modules")));
+        assert!(ignore.is_ignored(Path::new("/grm/github.com/user/node_modules")));
+        assert!(ignore.is_ignored(Path::new("/grm/archive")));
+        assert!(!ignore.is_ignored(Path::new("/grm/github.com/archive")));
+        assert!(ignore.is_ignored(Path::new("/grm/github.com/old-user")));
+        assert!(!ignore.is_ignored(Path::new("/grm/gitlab.com/old-user")));
+        assert!(!ignore.is_ignored(Path::new("/grm")));
+        assert!(!ignore.is_ignored(Path::new("/elsewhere/node_modules")));
+    }
+
+    #[test]
+    fn test_is_ignored_last_match_wins() {
+        // 目的: `!` による再包含
+        // 検証: 最後に一致したパターンが採用される
+
+        let ignore = GrmIgnore::parse(Path::new("/grm"), "tmp-*\n!tmp-keep\n");
+
+        assert!(ignore.is_ignored(Path::new("/grm/tmp-old")));
+        assert!(!ignore.is_ignored(Path::new("/grm/tmp-keep")));
+    }
+
+    #[test]
+    fn test_is_ignored_gitignore_syntax() {
+        // 目的: 文字クラス・エスケープ・末尾空白の扱い
+        // 検証: `.gitignore` と同じ規則で一致する
+
+        let ignore = GrmIgnore::parse(
+            Path::new("/grm"),
+            "build-[0-9]\n\\#notes\n\\!bang\ntrailing   \n",
+        );
+
+        assert!(ignore.is_ignored(Path::new("/grm/build-1")));
+        assert!(!ignore.is_ignored(Path::new("/grm/build-x")));
+        assert!(ignore.is_ignored(Path::new("/grm/#notes")));
+        assert!(ignore.is_ignored(Path::new("/grm/!bang")));
+        assert!(ignore.is_ignored(Path::new("/grm/trailing")));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        // 目的: `.grmignore` が無い場合の扱い
+        // 検証: エラーにならず何も除外されない
+
+        let fs = MockFileSystem::new();
+        fs.add_dir("/grm");
+
+        let ignore = GrmIgnore::load(&fs, Path::new("/grm")).unwrap();
+
+        assert!(!ignore.is_ignored(Path::new("/grm/archive")));
+    }
+}
@@ -0,0 +1,445 @@
+//! Directory layout of managed worktrees below the root
+//!
+//! A layout is a template such as `{host}/{user}/{repo}+{branch}` made of
+//! `/`-separated segments. The tokens are `{host}`, `{user}`, `{repo}` and
+//! `{branch}`; everything else is literal text. To read a path back into
+//! ``RepoInfo``:
+//!
+//! - `{repo}` and `{branch}` are required, and `{branch}` ends the template,
+//!   since a branch containing `/` continues as nested directories
+//! - `{user}` takes a segment of its own, as nested namespaces span several
+//! - two tokens within a segment are separated by literal text, and the first
+//!   occurrence of that text ends the first token
+//!
+//! Without literal text before `{branch}`, as in `{host}/{user}/{repo}/{branch}`,
+//! a nested namespace cannot be told apart from a branch containing `/`; the
+//! `.grm/repo.toml` marker settles it for worktrees which have one.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::core::RepoInfo;
+
+/// Layout used unless `layout` is configured
+pub const DEFAULT_LAYOUT: &str = "{host}/{user}/{repo}+{branch}";
+
+#[derive(Debug, Error)]
+#[error("Invalid layout `{template}`: {reason}")]
+pub struct LayoutError {
+    pub template: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Host,
+    User,
+    Repo,
+    Branch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Literal(String),
+    Token(Token),
+}
+
+/// Values read from a path, `None` for tokens the layout does not contain
+#[derive(Default)]
+struct Values {
+    host: Option<String>,
+    user: Option<String>,
+    repo: Option<String>,
+    branch: Option<String>,
+}
+
+impl Values {
+    fn set(&mut self, token: Token, value: &str) {
+        let slot = match token {
+            Token::Host => &mut self.host,
+            Token::User => &mut self.user,
+            Token::Repo => &mut self.repo,
+            Token::Branch => &mut self.branch,
+        };
+        *slot = Some(value.to_string());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Layout {
+    template: String,
+    segments: Vec<Vec<Piece>>,
+}
+
+impl Layout {
+    /// Parse and validate a layout template
+    pub fn parse(template: &str) -> Result<Self, LayoutError> {
+        let invalid = |reason: &str| LayoutError {
+            template: template.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let mut segments = Vec::new();
+        let mut seen = Vec::new();
+        for segment in template.split('/') {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(invalid("segments must not be empty, `.` or `..`"));
+            }
+
+            let pieces = parse_segment(segment).map_err(|reason| invalid(&reason))?;
+            for pair in pieces.windows(2) {
+                if let [Piece::Token(_), Piece::Token(_)] = pair {
+                    return Err(invalid("tokens must be separated by literal text"));
+                }
+            }
+            for piece in &pieces {
+                if let Piece::Token(token) = piece {
+                    if seen.contains(token) {
+                        return Err(invalid("each token may appear only once"));
+                    }
+                    if *token == Token::User && pieces.len() > 1 {
+                        return Err(invalid("`{user}` must be a directory of its own"));
+                    }
+                    seen.push(*token);
+                }
+            }
+            segments.push(pieces);
+        }
+
+        if !seen.contains(&Token::Repo) {
+            return Err(invalid("must contain `{repo}`"));
+        }
+        if !seen.contains(&Token::Branch) {
+            return Err(invalid("must contain `{branch}` to tell worktrees apart"));
+        }
+        if segments.last().and_then(|pieces| pieces.last()) != Some(&Piece::Token(Token::Branch)) {
+            return Err(invalid("must end with `{branch}`"));
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+            segments,
+        })
+    }
+
+    /// The template the layout was parsed from
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Number of directories below the root, not counting nested namespaces or branches containing `/`
+    pub fn depth(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Path of the worktree of `branch` of `info` below `root`
+    pub fn build(&self, root: &Path, info: &RepoInfo, branch: &str) -> PathBuf {
+        let mut path = root.to_path_buf();
+        for pieces in &self.segments {
+            path.push(render_segment(pieces, info, branch));
+        }
+        path
+    }
+
+    /// Path of a checkout of `info` without a branch below `root`
+    ///
+    /// The worktree path with `{branch}` and the literal text before it dropped,
+    /// e.g. `{host}/{user}/{repo}` for the default layout. `None` if nothing is
+    /// left of the last directory, as in `{host}/{user}/{repo}/{branch}`, where
+    /// that path is the directory holding the worktrees.
+    pub fn build_plain(&self, root: &Path, info: &RepoInfo) -> Option<PathBuf> {
+        let (last, parents) = self.segments.split_last()?;
+        let ([last @ .., Piece::Literal(_), Piece::Token(Token::Branch)]
+        | [last @ .., Piece::Token(Token::Branch)]) = last.as_slice()
+        else {
+            return None;
+        };
+        if last.is_empty() {
+            return None;
+        }
+
+        let mut path = root.to_path_buf();
+        for pieces in parents {
+            path.push(render_segment(pieces, info, ""));
+        }
+        path.push(render_segment(last, info, ""));
+        Some(path)
+    }
+
+    /// Read the components of a path below the root back into ``RepoInfo``
+    ///
+    /// Tokens missing from the layout are left empty. When `{user}` can span
+    /// several components, the shortest namespace which lets the rest match wins.
+    /// Returns `None` if the components do not follow the layout.
+    pub fn parse_components(&self, components: &[&str]) -> Option<RepoInfo> {
+        let extra = components.len().checked_sub(self.segments.len())?;
+        let user_index = self
+            .segments
+            .iter()
+            .position(|pieces| pieces == &[Piece::Token(Token::User)]);
+
+        let values = match user_index {
+            Some(user_index) => (1..=extra + 1)
+                .find_map(|user_len| self.match_with_user(components, user_index, user_len))?,
+            None => self.match_with_user(components, usize::MAX, 1)?,
+        };
+
+        Some(RepoInfo::new(
+            values.host.unwrap_or_default(),
+            values.user.unwrap_or_default(),
+            values.repo?,
+            values.branch.filter(|branch| !branch.is_empty()),
+        ))
+    }
+
+    /// Whether a directory named `name` at `depth()` may hold the rest of a branch containing `/`
+    pub fn continues_branch(&self, name: &str) -> bool {
+        self.segments
+            .last()
+            .is_some_and(|pieces| match_segment(pieces, name, &mut Values::default()))
+    }
+
+    /// Match `components` with the segment at `user_index` taking `user_len` of them
+    ///
+    /// The last segment takes whatever is left, joined with `/`.
+    fn match_with_user(
+        &self,
+        components: &[&str],
+        user_index: usize,
+        user_len: usize,
+    ) -> Option<Values> {
+        let mut values = Values::default();
+        let mut rest = components;
+        let last = self.segments.len() - 1;
+        for (index, pieces) in self.segments.iter().enumerate() {
+            let len = if index == user_index {
+                user_len
+            } else if index == last {
+                rest.len()
+            } else {
+                1
+            };
+            if len == 0 || rest.len() < len {
+                return None;
+            }
+            let (taken, remaining) = rest.split_at(len);
+            rest = remaining;
+
+            if index == user_index {
+                values.set(Token::User, &taken.join("/"));
+            } else if !match_segment(pieces, &taken.join("/"), &mut values) {
+                return None;
+            }
+        }
+
+        rest.is_empty().then_some(values)
+    }
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Self::parse(DEFAULT_LAYOUT).expect("the default layout is valid")
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+/// Directory name for `pieces`, with the tokens replaced by the values of `info` and `branch`
+fn render_segment(pieces: &[Piece], info: &RepoInfo, branch: &str) -> String {
+    pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Literal(text) => text.as_str(),
+            Piece::Token(Token::Host) => info.host.as_str(),
+            Piece::Token(Token::User) => info.user.as_str(),
+            Piece::Token(Token::Repo) => info.repo.as_str(),
+            Piece::Token(Token::Branch) => branch,
+        })
+        .collect()
+}
+
+fn parse_segment(segment: &str) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut rest = segment;
+    while !rest.is_empty() {
+        if let Some(after_brace) = rest.strip_prefix('{') {
+            let (name, after) = after_brace
+                .split_once('}')
+                .ok_or_else(|| "unclosed `{`".to_string())?;
+            let token = match name {
+                "host" => Token::Host,
+                "user" => Token::User,
+                "repo" => Token::Repo,
+                "branch" => Token::Branch,
+                _ => return Err(format!("unknown token `{{{name}}}`")),
+            };
+            pieces.push(Piece::Token(token));
+            rest = after;
+        } else {
+            let end = rest.find(['{', '}']).unwrap_or(rest.len());
+            if end == 0 {
+                return Err("unmatched `}`".to_string());
+            }
+            pieces.push(Piece::Literal(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+    }
+    Ok(pieces)
+}
+
+/// Match `text` against the pieces of one segment, storing token values in `values`
+///
+/// Only `{branch}` may be empty or span several components.
+fn match_segment(pieces: &[Piece], text: &str, values: &mut Values) -> bool {
+    let mut rest = text;
+    for (index, piece) in pieces.iter().enumerate() {
+        match piece {
+            Piece::Literal(literal) => match rest.strip_prefix(literal.as_str()) {
+                Some(after) => rest = after,
+                None => return false,
+            },
+            Piece::Token(token) => {
+                let end = match pieces.get(index + 1) {
+                    Some(Piece::Literal(literal)) => match rest.find(literal.as_str()) {
+                        Some(end) => end,
+                        None => return false,
+                    },
+                    _ => rest.len(),
+                };
+                let value = &rest[..end];
+                if *token != Token::Branch && (value.is_empty() || value.contains('/')) {
+                    return false;
+                }
+                values.set(*token, value);
+                rest = &rest[end..];
+            }
+        }
+    }
+    rest.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(user: &str) -> RepoInfo {
+        RepoInfo::new(
+            "github.com".to_string(),
+            user.to_string(),
+            "repo".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_build_and_parse_round_trip() {
+        // 目的: レイアウトに従ったパスの生成と逆変換
+        // 検証: `/` を含むブランチは元の値に戻り、ネストした名前空間は `{branch}` の前に文字列がある場合に戻る
+
+        let root = Path::new("/grm");
+        for (template, nested) in [
+            (DEFAULT_LAYOUT, true),
+            ("{user}/{repo}@{branch}", true),
+            ("src/{host}/{user}/{repo}/wt-{branch}", true),
+            ("{host}/{user}/{repo}/{branch}", false),
+        ] {
+            let layout = Layout::parse(template).unwrap();
+            let users = if nested {
+                vec!["user", "group/sub"]
+            } else {
+                vec!["user"]
+            };
+            for user in users {
+                let path = layout.build(root, &info(user), "feature/foobar");
+                let components = path
+                    .strip_prefix(root)
+                    .unwrap()
+                    .iter()
+                    .map(|c| c.to_str().unwrap())
+                    .collect::<Vec<_>>();
+
+                let parsed = layout.parse_components(&components).unwrap();
+
+                let host = if template.contains("{host}") {
+                    "github.com"
+                } else {
+                    ""
+                };
+                assert_eq!(parsed.host, host, "{template}");
+                assert_eq!(parsed.user, user, "{template}");
+                assert_eq!(parsed.repo, "repo", "{template}");
+                assert_eq!(
+                    parsed.branch.as_deref(),
+                    Some("feature/foobar"),
+                    "{template}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_plain() {
+        // 目的: ブランチを持たないパスの生成
+        // 検証: 最後のディレクトリから `{branch}` と直前の文字列を除いたパスになり、何も残らないレイアウトでは None になる
+
+        let root = Path::new("/grm");
+        for (template, expected) in [
+            (DEFAULT_LAYOUT, Some("/grm/github.com/user/repo")),
+            ("{user}/{repo}@{branch}", Some("/grm/user/repo")),
+            ("src/{host}/{user}/{repo}/wt-{branch}", None),
+            ("{host}/{user}/{repo}/{branch}", None),
+        ] {
+            let layout = Layout::parse(template).unwrap();
+
+            let plain = layout.build_plain(root, &info("user"));
+
+            assert_eq!(plain, expected.map(PathBuf::from), "{template}");
+        }
+    }
+
+    #[test]
+    fn test_parse_default_layout() {
+        // 目的: 既定のレイアウトのパス
+        // 検証: `+` を含むリポジトリ名は最初の `+` で分割され、`+` の無いパスは一致しない
+
+        let layout = Layout::default();
+
+        let plus = layout
+            .parse_components(&["github.com", "user", "c++lib+main"])
+            .unwrap();
+        let plain = layout.parse_components(&["github.com", "user", "repo"]);
+
+        assert_eq!(plus.repo, "c");
+        assert_eq!(plus.branch.as_deref(), Some("+lib+main"));
+        assert!(plain.is_none());
+        assert!(layout.continues_branch("repo+feature"));
+        assert!(!layout.continues_branch("repo"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        // 目的: テンプレートの検証
+        // 検証: 逆変換できないテンプレートは理由と共に拒否される
+
+        for (template, reason) in [
+            ("{host}/{user}/{branch}", "must contain `{repo}`"),
+            ("{host}/{user}/{repo}", "must contain `{branch}`"),
+            ("{host}/{branch}/{repo}", "must end with `{branch}`"),
+            ("{host}/{user}/{repo}{branch}", "separated by literal text"),
+            ("{host}/{user}-{repo}/{branch}", "directory of its own"),
+            ("{host}/{repo}/{repo}+{branch}", "only once"),
+            ("{host}//{repo}+{branch}", "must not be empty"),
+            ("{owner}/{repo}+{branch}", "unknown token `{owner}`"),
+            ("{host/{repo}+{branch}", "unclosed `{`"),
+        ] {
+            let error = Layout::parse(template).unwrap_err();
+            assert!(error.reason.contains(reason), "{template}: {error}");
+        }
+    }
+}
@@ -1,9 +1,25 @@
+pub mod cleanup;
+
+pub mod grm_ignore;
+pub use grm_ignore::GrmIgnore;
+
+pub mod layout;
+pub use layout::Layout;
+
+pub mod mount_manifest;
 pub mod ports;
 
 pub mod repo_info;
 pub use repo_info::RepoInfo;
 
+pub mod repo_marker;
+pub use repo_marker::RepoMarker;
+
+pub mod repo_query;
+pub use repo_query::RepoQuery;
+
 pub mod repo_scanner;
-pub use repo_scanner::RepoScanner;
+pub use repo_scanner::{RepoEntry, RepoScanner, is_bare_repository};
 
+pub mod share_manifest;
 pub mod shared_resource;
@@ -0,0 +1,117 @@
+//! Per-repository selection of shared files to mount
+//!
+//! A repository can commit a `.grm-shared` file at its root to limit which
+//! shared paths are mounted into its worktrees:
+//!
+//! ```toml
+//! paths = [".env", "config/local"]
+//! ```
+//!
+//! Listed directories include everything below them. Without the file, every
+//! shared path is mounted.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::core::ports::FileSystem;
+use crate::errors::GrmError;
+
+/// File name of the manifest at the root of a worktree
+pub const MOUNT_MANIFEST_FILE: &str = ".grm-shared";
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct MountManifest {
+    /// Shared paths (relative to the repository root) to mount
+    #[serde(default)]
+    pub paths: Vec<PathBuf>,
+}
+
+impl MountManifest {
+    /// Load the manifest of a worktree, or `None` if it has none
+    pub fn load(fs: &dyn FileSystem, repo_root: &Path) -> Result<Option<Self>, GrmError> {
+        let path = repo_root.join(MOUNT_MANIFEST_FILE);
+        if !fs.exists(&path) {
+            return Ok(None);
+        }
+
+        let content = fs.read_to_string(&path)?;
+        toml::from_str(&content)
+            .map(Some)
+            .map_err(|e| GrmError::InvalidManifest {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Whether a path is listed or lies under a listed directory
+    pub fn includes(&self, relative_path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|listed| relative_path.starts_with(listed))
+    }
+
+    /// Whether a directory has to be walked to reach a listed path
+    pub fn leads_to(&self, relative_path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|listed| listed.starts_with(relative_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    #[test]
+    fn test_load_missing_manifest() {
+        // 目的: マニフェストがない場合の読み込み
+        // 検証: None が返される
+
+        let fs = MockFileSystem::new();
+        fs.add_dir("/repo");
+
+        let manifest = MountManifest::load(&fs, Path::new("/repo")).unwrap();
+
+        assert!(manifest.is_none());
+    }
+
+    #[test]
+    fn test_includes_and_leads_to() {
+        // 目的: 記載パスによる判定
+        // 検証: 記載パスとその配下は含まれ、親ディレクトリは辿る対象になる
+
+        let fs = MockFileSystem::new();
+        fs.add_dir("/repo");
+        fs.add_file_with_content(
+            "/repo/.grm-shared",
+            "paths = [\".env\", \"config/local\"]\n",
+        );
+
+        let manifest = MountManifest::load(&fs, Path::new("/repo"))
+            .unwrap()
+            .unwrap();
+
+        assert!(manifest.includes(Path::new(".env")));
+        assert!(manifest.includes(Path::new("config/local/db.toml")));
+        assert!(!manifest.includes(Path::new("config")));
+        assert!(!manifest.includes(Path::new(".env.local")));
+        assert!(manifest.leads_to(Path::new("config")));
+        assert!(!manifest.leads_to(Path::new("secrets")));
+    }
+
+    #[test]
+    fn test_load_invalid_manifest() {
+        // 目的: 不正なマニフェストの読み込み
+        // 検証: InvalidManifest エラーが返される
+
+        let fs = MockFileSystem::new();
+        fs.add_dir("/repo");
+        fs.add_file_with_content("/repo/.grm-shared", "paths = \".env\"\n");
+
+        let result = MountManifest::load(&fs, Path::new("/repo"));
+
+        assert!(matches!(result, Err(GrmError::InvalidManifest { .. })));
+    }
+}
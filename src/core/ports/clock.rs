@@ -0,0 +1,13 @@
+use std::time::{Duration, SystemTime};
+
+/// Interface for reading the current time
+///
+/// This trait abstracts the system clock so that time-dependent behavior
+/// (e.g. cache expiry or retry backoff) can be tested deterministically.
+pub trait Clock {
+    /// The current time
+    fn now(&self) -> SystemTime;
+
+    /// Block the current thread for `duration`
+    fn sleep(&self, duration: Duration);
+}
@@ -0,0 +1,58 @@
+use std::path::Path;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    #[error("Failed to execute {program}: {message}")]
+    Execution { program: String, message: String },
+}
+
+/// Output stream a captured line was written to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Interface for spawning external programs
+///
+/// This trait abstracts process execution so that use cases spawning
+/// interactive programs (e.g. a subshell) can be tested without side effects.
+pub trait CommandRunner {
+    /// Runs a program attached to the current terminal and waits for it to exit
+    ///
+    /// # Arguments
+    /// * `program` - The program to execute
+    /// * `args` - Arguments passed to the program
+    /// * `current_dir` - Working directory of the spawned process
+    /// * `envs` - Additional environment variables for the spawned process
+    ///
+    /// # Returns
+    /// * `Ok(i32)` - Exit code of the program (`-1` if terminated by a signal)
+    /// * `Err` - If the program cannot be spawned
+    fn run(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        envs: &[(String, String)],
+    ) -> Result<i32, CommandError>;
+
+    /// Runs a program with its output captured line by line and waits for it to exit
+    ///
+    /// # Arguments
+    /// * `program` - The program to execute
+    /// * `args` - Arguments passed to the program
+    /// * `current_dir` - Working directory of the spawned process
+    /// * `on_output` - Called with each line of stdout and stderr, in the order they arrive
+    ///
+    /// # Returns
+    /// * `Ok(i32)` - Exit code of the program (`-1` if terminated by a signal)
+    /// * `Err` - If the program cannot be spawned
+    fn run_captured(
+        &self,
+        program: &str,
+        args: &[String],
+        current_dir: &Path,
+        on_output: &mut dyn FnMut(OutputStream, &str),
+    ) -> Result<i32, CommandError>;
+}
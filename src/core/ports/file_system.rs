@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 #[derive(Debug, thiserror::Error)]
 pub enum FileSystemError {
@@ -45,6 +46,9 @@ pub trait FileSystem {
     /// # Arguments
     /// * `path` - The path to check
     ///
+    /// A `.git` file, as written by `git worktree add` and submodules, counts
+    /// the same as a `.git` directory. A `.git` symlink counts if its target exists.
+    ///
     /// # Returns
     /// * `true` if the path contains a `.git` directory or file (for worktrees)
     /// * `false` otherwise
@@ -95,6 +99,47 @@ pub trait FileSystem {
     /// * `Err` - If the symlink cannot be created
     fn create_symlink(&self, target: &Path, link: &Path) -> Result<(), FileSystemError>;
 
+    /// Read the target of a symbolic link
+    ///
+    /// # Arguments
+    /// * `link` - The symlink path
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - The path the symlink points to
+    /// * `Err` - If the path is not a symlink or cannot be read
+    fn read_link(&self, link: &Path) -> Result<PathBuf, FileSystemError>;
+
+    /// Resolve a path to its absolute form with all symlinks followed
+    ///
+    /// # Arguments
+    /// * `path` - The path to resolve
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - The canonical path
+    /// * `Err` - If the path does not exist, e.g. a broken symlink, or a symlink loops
+    fn canonicalize(&self, path: &Path) -> Result<PathBuf, FileSystemError>;
+
+    /// Read the whole content of a file as UTF-8 text
+    ///
+    /// # Arguments
+    /// * `path` - The file path to read
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The file content
+    /// * `Err` - If the file cannot be read
+    fn read_to_string(&self, path: &Path) -> Result<String, FileSystemError>;
+
+    /// Write text to a file, replacing any existing content
+    ///
+    /// # Arguments
+    /// * `path` - The file path to write
+    /// * `content` - The text to write
+    ///
+    /// # Returns
+    /// * `Ok(())` - Written successfully
+    /// * `Err` - If the file cannot be written
+    fn write(&self, path: &Path, content: &str) -> Result<(), FileSystemError>;
+
     /// Copy a file or directory
     ///
     /// # Arguments
@@ -117,6 +162,34 @@ pub trait FileSystem {
     /// * `Err` - If the operation fails
     fn rename(&self, from: &Path, to: &Path) -> Result<(), FileSystemError>;
 
+    /// Move a file or directory, also across filesystems
+    ///
+    /// Falls back to `copy` followed by `remove` of the source when `rename`
+    /// fails because `from` and `to` are on different devices. A partial copy
+    /// is removed if the fallback fails.
+    ///
+    /// # Arguments
+    /// * `from` - The source path
+    /// * `to` - The destination path
+    ///
+    /// # Returns
+    /// * `Ok(())` - Moved successfully
+    /// * `Err` - If the operation fails
+    fn move_path(&self, from: &Path, to: &Path) -> Result<(), FileSystemError> {
+        match self.rename(from, to) {
+            Err(FileSystemError::Io(e)) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+                if let Err(e) = self.copy(from, to) {
+                    if self.exists(to) || self.is_symlink(to) {
+                        let _ = self.remove(to);
+                    }
+                    return Err(e);
+                }
+                self.remove(from)
+            }
+            result => result,
+        }
+    }
+
     /// Remove a directory and all its contents recursively
     ///
     /// # Arguments
@@ -127,6 +200,37 @@ pub trait FileSystem {
     /// * `Err` - If the directory / file cannot be removed
     fn remove(&self, path: &Path) -> Result<(), FileSystemError>;
 
+    /// Remove a directory only if it is empty
+    ///
+    /// # Arguments
+    /// * `path` - The directory path to remove
+    ///
+    /// # Returns
+    /// * `Ok(())` - removed successfully
+    /// * `Err` - If the directory is not empty or cannot be removed
+    fn remove_empty_dir(&self, path: &Path) -> Result<(), FileSystemError>;
+
+    /// Get the total size of a file or directory in bytes
+    ///
+    /// # Arguments
+    /// * `path` - The file or directory to measure
+    ///
+    /// # Returns
+    /// * `Ok(u64)` - Size of the file, or the sum of all files under the directory.
+    ///   Symbolic links are not followed.
+    /// * `Err` - If the path cannot be read
+    fn size(&self, path: &Path) -> Result<u64, FileSystemError>;
+
+    /// Get the last modification time of a file or directory
+    ///
+    /// # Arguments
+    /// * `path` - The file or directory to inspect, a symbolic link itself is inspected
+    ///
+    /// # Returns
+    /// * `Ok(SystemTime)` - When the entry was last modified
+    /// * `Err` - If the path cannot be read
+    fn modified(&self, path: &Path) -> Result<SystemTime, FileSystemError>;
+
     /// Normalize a path to an absolute ``PathBuf``
     ///
     /// # Arguments
@@ -142,4 +246,78 @@ pub trait FileSystem {
     /// * `Ok(PathBuf)` - The normalized absolute path
     /// * `Err` - If the path cannot be normalized
     fn normalize(&self, path: &Path, base: &Path) -> Result<PathBuf, FileSystemError>;
+
+    /// Borrow the file system for use from several threads
+    ///
+    /// # Returns
+    /// * `Some` - If the adapter can be shared between threads
+    /// * `None` - Otherwise, callers must stay on the current thread
+    fn as_sync(&self) -> Option<&(dyn FileSystem + Sync)> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    fn cross_device_error() -> FileSystemError {
+        FileSystemError::Io(std::io::Error::from(std::io::ErrorKind::CrossesDevices))
+    }
+
+    #[test]
+    fn test_move_path_falls_back_to_copy_across_devices() {
+        let fs = MockFileSystem::new();
+        fs.add_dir("/a");
+        fs.add_dir("/a/dir");
+        fs.add_file("/a/dir/file.txt");
+        fs.add_symlink("/a/dir/link", "/a/dir/file.txt");
+        fs.add_dir("/b");
+        fs.inject_error(cross_device_error());
+
+        let result = fs.move_path(Path::new("/a/dir"), Path::new("/b/dir"));
+
+        assert!(result.is_ok(), "move failed: {:?}", result.err());
+        assert!(!fs.exists(Path::new("/a/dir")));
+        assert!(fs.exists(Path::new("/b/dir/file.txt")));
+        assert!(fs.is_symlink(Path::new("/b/dir/link")));
+    }
+
+    #[test]
+    fn test_move_path_removes_partial_copy_on_failure() {
+        let fs = MockFileSystem::new();
+        fs.add_dir("/a");
+        fs.add_dir("/a/dir");
+        fs.add_file("/a/dir/file.txt");
+        fs.add_dir("/b");
+        fs.inject_error(cross_device_error());
+        // copy, create_dir and read_dir succeed, copying the file fails
+        fs.inject_error_after(
+            3,
+            FileSystemError::Io(std::io::Error::from(std::io::ErrorKind::StorageFull)),
+        );
+
+        let result = fs.move_path(Path::new("/a/dir"), Path::new("/b/dir"));
+
+        assert!(result.is_err());
+        assert!(!fs.exists(Path::new("/b/dir")));
+        assert!(fs.exists(Path::new("/a/dir/file.txt")));
+    }
+
+    #[test]
+    fn test_move_path_does_not_fall_back_on_other_errors() {
+        let fs = MockFileSystem::new();
+        fs.add_dir("/a");
+        fs.add_file("/a/file.txt");
+        fs.inject_error(FileSystemError::Io(std::io::Error::from(
+            std::io::ErrorKind::PermissionDenied,
+        )));
+
+        let result = fs.move_path(Path::new("/a/file.txt"), Path::new("/b/file.txt"));
+
+        assert!(result.is_err());
+        assert!(fs.exists(Path::new("/a/file.txt")));
+        assert!(!fs.exists(Path::new("/b/file.txt")));
+    }
 }
@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug, thiserror::Error)]
 pub enum GitError {
@@ -10,32 +11,232 @@ pub enum GitError {
 
     #[error("Failed to parse git output: {0}")]
     Parse(String),
+
+    #[error("Cannot fast-forward: {0}")]
+    NotFastForward(String),
+
+    #[error(
+        "git {command} did not finish within {timeout:?}\nRaise `git_timeout_secs` in ~/.grmrc if it needs longer"
+    )]
+    Timeout { command: String, timeout: Duration },
+}
+
+/// Messages of git and its transports which point at a network problem rather than e.g. a rejected login
+const TRANSIENT_MESSAGES: &[&str] = &[
+    "could not resolve host",
+    "temporary failure in name resolution",
+    "connection timed out",
+    "connection reset",
+    "connection refused",
+    "failed to connect",
+    "operation timed out",
+    "the remote end hung up unexpectedly",
+    "early eof",
+    "unexpected disconnect",
+    "rpc failed",
+    "gnutls_handshake",
+    "ssl_error_syscall",
+];
+
+impl GitError {
+    /// Whether the command may succeed when run again, e.g. after a dropped connection
+    ///
+    /// Only errors whose message names a network problem count, so that e.g.
+    /// a rejected login or a missing repository fails right away.
+    pub fn is_transient(&self) -> bool {
+        let message = match self {
+            Self::Execution(message)
+            | Self::Failed {
+                stderr: message, ..
+            } => message.to_lowercase(),
+            _ => return false,
+        };
+        TRANSIENT_MESSAGES
+            .iter()
+            .any(|transient| message.contains(transient))
+    }
+}
+
+/// Remote read by `get_remote_url` when no remote is named
+pub const DEFAULT_REMOTE: &str = "origin";
+
+/// A worktree entry reported by `git worktree list --porcelain`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeInfo {
+    pub path: PathBuf,
+    /// Checked out branch without `refs/heads/`, `None` when detached or bare
+    pub branch: Option<String>,
+    /// Commit id of HEAD, `None` for a bare repository
+    pub head: Option<String>,
+    pub locked: bool,
+    pub prunable: bool,
+    pub bare: bool,
+}
+
+/// How `add_worktree` gets the branch it checks out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WorktreeBranch {
+    /// Check out the existing local branch
+    Existing,
+    /// Create the branch at the current `HEAD`
+    New,
+    /// Create the branch from the named remote's branch of the same name and track it
+    ///
+    /// For a branch which exists only on the remote, where a plain checkout may
+    /// leave the worktree detached depending on the git version.
+    Track(String),
 }
 
 pub trait GitRepository {
+    /// Version of the git installation, e.g. `2.43.0`
+    fn version(&self) -> Result<String, GitError>;
+
     fn get_default_branch(&self, url: &str) -> Result<String, GitError>;
 
+    /// Drop any cached default branch of `url`, so the next lookup asks the remote
+    ///
+    /// Implementations without a cache do nothing.
+    fn forget_default_branch(&self, _url: &str) {}
+
     fn get_repository_root(&self) -> Result<PathBuf, GitError>;
 
-    fn get_remote_url(&self, repo_path: &Path) -> Result<String, GitError>;
+    fn get_repository_root_at(&self, path: &Path) -> Result<PathBuf, GitError>;
+
+    /// URL of the remote `remote`, or of `origin` when `None`
+    fn get_remote_url(&self, repo_path: &Path, remote: Option<&str>) -> Result<String, GitError>;
+
+    /// Branch checked out at `repo_path`, `HEAD` when detached
+    fn get_current_branch(&self, repo_path: &Path) -> Result<String, GitError>;
 
-    fn local_branch_exists(&self, branch: &str) -> Result<bool, GitError>;
+    /// Whether the working tree at `repo_path` has uncommitted or untracked changes
+    fn is_dirty(&self, repo_path: &Path) -> Result<bool, GitError>;
+
+    fn local_branch_exists(&self, repo_path: &Path, branch: &str) -> Result<bool, GitError>;
 
     fn remote_branch_exists(&self, remote_url: &str, branch: &str) -> Result<bool, GitError>;
 
+    /// Clone `url` into `destination`, shallowly if `depth` is given
+    ///
+    /// With `recurse_submodules`, the submodules are cloned and checked out as well.
     fn clone_repository(
         &self,
         url: &str,
         destination: &Path,
         branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
     ) -> Result<(), GitError>;
 
+    /// Clone `url` into `destination` with only the `paths` directories checked out
+    ///
+    /// The sparse-checkout is set in cone mode before the first checkout, and
+    /// blobs outside of it are not downloaded (`--filter=blob:none`).
+    fn clone_sparse(
+        &self,
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        paths: &[String],
+    ) -> Result<(), GitError>;
+
+    /// Clone `url` into `destination` without a working tree (`--bare`, or `--mirror` if `mirror`)
+    fn clone_bare(&self, url: &str, destination: &Path, mirror: bool) -> Result<(), GitError>;
+
+    /// Clone like `clone_repository`, passing each progress line of `git clone` to `progress`
+    ///
+    /// Implementations which cannot capture progress clone without reporting it.
+    fn clone_repository_with_progress(
+        &self,
+        url: &str,
+        destination: &Path,
+        branch: Option<&str>,
+        depth: Option<u32>,
+        recurse_submodules: bool,
+        _progress: &mut dyn FnMut(&str),
+    ) -> Result<(), GitError> {
+        self.clone_repository(url, destination, branch, depth, recurse_submodules)
+    }
+
     fn add_worktree(
         &self,
+        repo_path: &Path,
         worktree_path: &Path,
         branch: &str,
-        create_new: bool,
+        source: WorktreeBranch,
+    ) -> Result<(), GitError>;
+
+    /// Add a worktree with a detached `HEAD` at `commitish` (a tag, commit or branch)
+    fn add_worktree_detached(
+        &self,
+        repo_path: &Path,
+        worktree_path: &Path,
+        commitish: &str,
     ) -> Result<(), GitError>;
 
     fn remove_worktree(&self, worktree_path: &Path) -> Result<(), GitError>;
+
+    /// Fetch all remotes of the repository at `repo_path`
+    fn fetch(&self, repo_path: &Path) -> Result<(), GitError>;
+
+    /// Fast-forward the checked out branch at `repo_path` to its upstream
+    ///
+    /// Returns `GitError::NotFastForward` if the branches have diverged.
+    fn pull(&self, repo_path: &Path) -> Result<(), GitError>;
+
+    /// Rename the local branch `old` to `new` in the repository at `repo_path`
+    fn rename_branch(&self, repo_path: &Path, old: &str, new: &str) -> Result<(), GitError>;
+
+    /// Delete the local branch `branch` of the repository at `repo_path`, even if unmerged
+    fn delete_branch(&self, repo_path: &Path, branch: &str) -> Result<(), GitError>;
+
+    /// Committer date of the commit checked out in the repository at `repo_path`
+    ///
+    /// Fails for a repository without commits.
+    fn last_commit_timestamp(&self, repo_path: &Path) -> Result<SystemTime, GitError>;
+
+    /// Fix the links between a worktree moved outside of git and the other worktrees
+    fn repair_worktrees(&self, worktree_path: &Path) -> Result<(), GitError>;
+
+    fn worktree_details(&self, repo_path: &Path) -> Result<Vec<WorktreeInfo>, GitError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(stderr: &str) -> GitError {
+        GitError::Failed {
+            status: 128,
+            stderr: stderr.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_transient() {
+        // 目的: リトライ対象となるエラーの判定
+        // 検証: ネットワーク障害のメッセージだけが一時的なエラーとなり、認証エラーなどは対象外となる
+
+        assert!(failed("fatal: unable to access 'https://github.com/user/repo/': Could not resolve host: github.com").is_transient());
+        assert!(
+            failed("error: RPC failed; curl 56 GnuTLS recv error (-9)\nfatal: early EOF")
+                .is_transient()
+        );
+        assert!(
+            GitError::Execution(
+                "ssh: connect to host github.com port 22: Connection timed out".to_string()
+            )
+            .is_transient()
+        );
+
+        assert!(
+            !failed("fatal: Authentication failed for 'https://github.com/user/repo/'")
+                .is_transient()
+        );
+        assert!(
+            !failed("ERROR: Repository not found.\nfatal: Could not read from remote repository.")
+                .is_transient()
+        );
+        assert!(!GitError::Parse("connection reset".to_string()).is_transient());
+    }
 }
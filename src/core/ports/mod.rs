@@ -1,7 +1,11 @@
+pub mod clock;
+pub mod command_runner;
 pub mod file_system;
 pub mod git_repository;
 pub mod user_interaction;
 
+pub use clock::Clock;
+pub use command_runner::{CommandError, CommandRunner, OutputStream};
 pub use file_system::{FileSystem, FileSystemError};
-pub use git_repository::{GitError, GitRepository};
+pub use git_repository::{DEFAULT_REMOTE, GitError, GitRepository, WorktreeBranch, WorktreeInfo};
 pub use user_interaction::{InteractionError, UserInteraction};
@@ -6,6 +6,13 @@ use thiserror::Error;
 pub enum InteractionError {
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
+
+    #[error("Invalid selection: {0}")]
+    InvalidSelection(String),
+
+    /// Nobody can answer the prompt, e.g. in CI or with piped input
+    #[error("Cannot prompt: standard input is not a terminal")]
+    NotInteractive,
 }
 
 /// Interface for user interaction operations
@@ -21,18 +28,74 @@ pub trait UserInteraction {
     /// # Returns
     /// * `Ok(true)` - User confirmed (yes)
     /// * `Ok(false)` - User declined (no)
-    /// * `Err` - Failed to read user input
+    /// * `Err` - Failed to read user input, or `NotInteractive` if nobody can answer
     fn confirm(&self, message: &str) -> Result<bool, InteractionError>;
 
+    /// Prompts the user for confirmation, asking again on answers other than yes or no
+    ///
+    /// Meant for destructive operations, where a typo must not be taken as an answer.
+    /// Implementations give up after a few attempts and return `Ok(false)`.
+    /// Defaults to `confirm`.
+    ///
+    /// # Arguments
+    /// * `message` - The confirmation message to display
+    fn confirm_strict(&self, message: &str) -> Result<bool, InteractionError> {
+        self.confirm(message)
+    }
+
+    /// Prompts the user for free-form text input
+    ///
+    /// # Arguments
+    /// * `message` - The prompt message to display
+    /// * `default` - Value returned when the user enters nothing
+    ///
+    /// # Returns
+    /// * `Ok(String)` - The trimmed input, or the default if the input is empty
+    /// * `Err` - Failed to read user input
+    fn input(&self, message: &str, default: Option<&str>) -> Result<String, InteractionError>;
+
+    /// Prompts the user to choose one of the given options
+    ///
+    /// # Arguments
+    /// * `message` - The prompt message to display
+    /// * `options` - The options to choose from
+    ///
+    /// # Returns
+    /// * `Ok(Some(usize))` - Index of the selected option
+    /// * `Ok(None)` - The user cancelled with an empty answer or `q`
+    /// * `Err` - Failed to read user input or the selection is invalid
+    fn select(&self, message: &str, options: &[String]) -> Result<Option<usize>, InteractionError>;
+
     /// Prints a message to the user
     ///
     /// # Arguments
     /// * `message` - The message to display
     fn print(&self, message: &str);
 
+    /// Prints the result of a command, e.g. a path or a listing read by scripts
+    ///
+    /// Unlike `print`, which is for progress and information, this is kept
+    /// under `--quiet`. Defaults to `print`.
+    ///
+    /// # Arguments
+    /// * `message` - The result to display
+    fn print_result(&self, message: &str) {
+        self.print(message);
+    }
+
     /// Prints an error message to the user
     ///
     /// # Arguments
     /// * `message` - The error message to display
     fn print_error(&self, message: &str);
+
+    /// Prints a warning, i.e. a problem which does not stop the operation
+    ///
+    /// Defaults to `print_error`.
+    ///
+    /// # Arguments
+    /// * `message` - The warning to display, without a `warning:` prefix
+    fn print_warning(&self, message: &str) {
+        self.print_error(message);
+    }
 }
@@ -1,6 +1,16 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use thiserror::Error;
 
+use crate::core::Layout;
+use crate::core::ports::FileSystem;
+use crate::errors::GrmError;
+
+/// Suffix of the directory of a bare clone, `{repo}.git`
+pub const BARE_SUFFIX: &str = ".git";
+
+/// Host directory of repositories cloned from a local path or `file://` URL
+pub const LOCAL_HOST: &str = "local";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RepoInfo {
     pub host: String,
@@ -17,7 +27,12 @@ pub enum RepositoryError {
 
 impl RepoInfo {
     pub fn new(host: String, user: String, repo: String, branch: Option<String>) -> Self {
-        Self { host, user, repo, branch }
+        Self {
+            host,
+            user,
+            repo,
+            branch,
+        }
     }
 
     /// Parse a git repository URL into ``RepoInfo``
@@ -25,53 +40,205 @@ impl RepoInfo {
     /// examples of supported URL formats:
     /// - <https://host/user/repo.git>
     /// - <ssh://git@host/user/repo.git>
-    /// - <git@host:user/repo.git>
+    /// - <ssh://deploy@host:2222/user/repo.git> (the login and port are not part of the host)
+    /// - <git@host:user/repo.git> (any login, e.g. <deploy@host:user/repo.git>)
+    /// - `host/user/repo` (shorthand for <https://host/user/repo>)
+    /// - <file:///path/to/user/repo.git> and `/path/to/user/repo.git`, with the host
+    ///   `local` and only the parent directory as the user
     pub fn from_url(url: &str) -> Result<Self, RepositoryError> {
+        Self::from_url_with(url, true)
+    }
+
+    /// Parse like `from_url`, choosing whether the host is normalized
+    ///
+    /// With `normalize_host`, the host is lowercased and a leading `www.` is
+    /// stripped so that `https://GitHub.com/...` and `https://www.github.com/...`
+    /// map to the same directory. The user and repository are kept verbatim
+    /// since git hosts may treat them case-sensitively.
+    pub fn from_url_with(url: &str, normalize_host: bool) -> Result<Self, RepositoryError> {
+        let mut info = Self::parse_url(url)?;
+        if normalize_host {
+            info.host = Self::normalize_host(&info.host);
+        }
+        Ok(info)
+    }
+
+    fn normalize_host(host: &str) -> String {
+        let host = host.to_ascii_lowercase();
+        match host.strip_prefix("www.") {
+            Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+            _ => host,
+        }
+    }
+
+    fn parse_url(url: &str) -> Result<Self, RepositoryError> {
         let url = url.trim();
 
-        let formats = [("https://", "/"), ("ssh://git@", "/"), ("git@", ":")];
+        let formats = [("https://", "/"), ("ssh://", "/")];
 
         for (prefix, separator) in formats {
             if let Some(url_without_scheme) = url.strip_prefix(prefix) {
-                let parts: Vec<&str> = url_without_scheme.splitn(2, separator).collect();
-                if parts.len() != 2 {
-                    return Err(RepositoryError::Invalid(format!(
-                        "Expected format: {prefix}host{separator}user/repo, got: {url}",
-                    )));
-                }
-
-                let host = parts[0];
-                let path = parts[1];
-
-                let path_parts: Vec<&str> = path.split('/').collect();
-                if path_parts.len() < 2 {
-                    return Err(RepositoryError::Invalid(format!(
-                        "Expected format: {prefix}host{separator}user/repo, got: {url}",
-                    )));
-                }
-
-                let user = path_parts[0];
-                let repo = path_parts[1].trim_end_matches(".git");
-
-                return Ok(RepoInfo::new(
-                    host.to_string(),
-                    user.to_string(),
-                    repo.to_string(),
-                    None,
-                ));
+                let expected = format!("{prefix}host{separator}user/repo");
+                let Some((host, path)) = url_without_scheme.split_once(separator) else {
+                    return Err(Self::unexpected(&expected, url));
+                };
+
+                let host = if prefix == "ssh://" {
+                    // ssh://[login@]host[:port]
+                    let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+                    host.split_once(':').map_or(host, |(host, _)| host)
+                } else {
+                    host
+                };
+
+                return Self::from_host_and_path(host, path, &expected, url);
+            }
+        }
+
+        // A local repository has no host, and only its parent directory
+        // is kept as the user so that the path does not nest arbitrarily deep
+        let local_path = url
+            .strip_prefix("file://")
+            .or_else(|| url.starts_with('/').then_some(url));
+        if let Some(path) = local_path {
+            let expected = "file:///path/to/user/repo";
+            if !path.starts_with('/') {
+                return Err(Self::unexpected(expected, url));
             }
+            let mut components = path.trim_end_matches('/').rsplit('/');
+            let repo = components.next().unwrap_or_default();
+            let user = components.next().unwrap_or_default();
+            return Self::from_host_and_path(LOCAL_HOST, &format!("{user}/{repo}"), expected, url);
+        }
+
+        // scp-like `login@host:user/repo`
+        if !url.contains("://")
+            && let Some((login_host, path)) = url.split_once(':')
+            && let Some((_, host)) = login_host.split_once('@')
+        {
+            return Self::from_host_and_path(host, path, "login@host:user/repo", url);
+        }
+
+        if Self::is_shorthand(url) {
+            return Self::parse_url(&format!("https://{url}"));
         }
 
         Err(RepositoryError::Invalid(format!(
-            "Unsupported URL format. Supported: https://, git@, ssh://. Got: {url}",
+            "Unsupported URL format. Supported: https://, git@, ssh://, file://, host/user/repo. Got: {url}",
         )))
     }
 
+    fn from_host_and_path(
+        host: &str,
+        path: &str,
+        expected: &str,
+        url: &str,
+    ) -> Result<Self, RepositoryError> {
+        // Everything before the last component is the user, which
+        // may be a nested namespace such as `group/subgroup`
+        let Some((user, repo)) = path.trim_end_matches('/').rsplit_once('/') else {
+            return Err(Self::unexpected(expected, url));
+        };
+        let repo = repo.trim_end_matches(".git");
+
+        if host.is_empty() || user.split('/').any(str::is_empty) || repo.is_empty() {
+            return Err(Self::unexpected(expected, url));
+        }
+
+        Ok(RepoInfo::new(
+            host.to_string(),
+            user.to_string(),
+            repo.to_string(),
+            None,
+        ))
+    }
+
+    fn unexpected(expected: &str, url: &str) -> RepositoryError {
+        RepositoryError::Invalid(format!("Expected format: {expected}, got: {url}"))
+    }
+
+    /// Expand the `host/user/repo` shorthand into a clonable `https://` URL
+    ///
+    /// With `default_host`, `user/repo` is expanded against that host as well.
+    /// Any other input is returned unchanged.
+    pub fn expand_url(url: &str, default_host: Option<&str>) -> String {
+        let url = url.trim();
+        if Self::is_shorthand(url) {
+            format!("https://{url}")
+        } else if let Some(host) = default_host.filter(|_| Self::is_hostless(url)) {
+            format!("https://{host}/{url}")
+        } else {
+            url.to_string()
+        }
+    }
+
+    /// Check if `url` is a schemeless `user/repo` without a host
+    fn is_hostless(url: &str) -> bool {
+        if url.contains("://") || url.contains(['@', ':']) || url.starts_with(['.', '/', '~']) {
+            return false;
+        }
+
+        let parts: Vec<&str> = url.split('/').collect();
+        parts.len() >= 2 && parts.iter().all(|part| !part.is_empty())
+    }
+
+    /// Check if `url` is a schemeless `host/user/repo` with a dotted host
+    ///
+    /// Relative paths such as `./a.b/user/repo` or `../vendor/lib` are local
+    /// paths, so a host may not be `.`, `..` or start with a dot.
+    fn is_shorthand(url: &str) -> bool {
+        if url.contains("://") || url.starts_with("./") || url.starts_with("../") {
+            return false;
+        }
+
+        let parts: Vec<&str> = url.split('/').collect();
+        parts.len() >= 3
+            && parts.iter().all(|part| !part.is_empty())
+            && parts[0].contains('.')
+            && !parts[0].starts_with('.')
+            && !parts[0].contains(['@', ':'])
+    }
+
     /// Constructs a `RepoInfo` from a given path relative to the root directory.
     ///
     /// examples of supported path formats:
     /// - `{root}/{host}/{user}/{repo}+{branch}`
+    /// - `{root}/{host}/{group}/{subgroup}/{repo}+{branch}`
+    /// - `{root}/{host}/{user}/{repo}`
+    /// - `{root}/{host}/{user}/{repo}.git` (a bare clone)
     pub fn from_path(root: &Path, path: &Path) -> Result<Self, RepositoryError> {
+        Self::from_path_with(root, path, &Layout::default())
+    }
+
+    /// Parse like `from_path_with` a path which may have its symlinks resolved
+    ///
+    /// git reports paths with symlinks resolved, so a root under e.g. a symlinked
+    /// home is not a prefix of them. In that case both are canonicalized through
+    /// `fs` and compared again.
+    pub fn from_path_resolved(
+        fs: &dyn FileSystem,
+        root: &Path,
+        path: &Path,
+        layout: &Layout,
+    ) -> Result<Self, RepositoryError> {
+        if path.starts_with(root) {
+            return Self::from_path_with(root, path, layout);
+        }
+
+        let resolved_root = fs.canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let resolved_path = fs.canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        Self::from_path_with(&resolved_root, &resolved_path, layout)
+    }
+
+    /// Parse like `from_path`, reading worktrees with `layout` instead of the default
+    ///
+    /// Paths without a branch keep `{host}/{user}/{repo}` whatever the layout.
+    pub fn from_path_with(
+        root: &Path,
+        path: &Path,
+        layout: &Layout,
+    ) -> Result<Self, RepositoryError> {
+        // Trailing separators and `.` components are ignored by the component-wise comparison
         let relative_path = path.strip_prefix(root).map_err(|_| {
             RepositoryError::Invalid(format!(
                 "Path {} is not under root {}",
@@ -85,6 +252,10 @@ impl RepoInfo {
             .map(|comp| comp.as_os_str().to_str().unwrap_or(""))
             .collect();
 
+        if let Some(info) = layout.parse_components(&components) {
+            return Ok(info);
+        }
+
         if components.len() < 3 {
             return Err(RepositoryError::Invalid(format!(
                 "Path {} does not have managed repository structure",
@@ -92,30 +263,48 @@ impl RepoInfo {
             )));
         }
 
-        let host = components[0].to_string();
-        let user = components[1].to_string();
-        let repo_with_branch = components[2];
-
-        if let Some(plus_pos) = repo_with_branch.find('+') {
-            let repo = repo_with_branch[..plus_pos].to_string();
-            let branch_first_part = &repo_with_branch[plus_pos + 1..];
-            let remaining_components = &components[3..];
-
-            let branch = if !remaining_components.is_empty() {
-                let mut branch_parts = vec![branch_first_part];
-                branch_parts.extend(remaining_components);
-                Some(branch_parts.join("/"))
-            } else if !branch_first_part.is_empty() {
-                Some(branch_first_part.to_string())
-            } else {
-                None
-            };
-
-            Ok(RepoInfo::new(host, user, repo, branch))
-        } else {
-            let repo = repo_with_branch.to_string();
-            Ok(RepoInfo::new(host, user, repo, None))
+        let (repo, namespace) = components[1..]
+            .split_last()
+            .expect("at least three components");
+        // `{repo}.git` is a bare clone of `repo`
+        let repo = repo.strip_suffix(BARE_SUFFIX).unwrap_or(repo);
+        Ok(RepoInfo::new(
+            components[0].to_string(),
+            namespace.join("/"),
+            repo.to_string(),
+            None,
+        ))
+    }
+
+    /// Check that a branch name can be used in `{repo}+{branch}`
+    ///
+    /// Branch names containing `/` become nested directories, but names that
+    /// are empty, contain `+`, are absolute or contain a `..` component are rejected since
+    /// they would escape the repository directory or break `from_path`.
+    pub fn validate_branch_name(branch: &str) -> Result<(), GrmError> {
+        let invalid = |reason: &str| {
+            Err(GrmError::InvalidBranch {
+                branch: branch.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+
+        if branch.is_empty() {
+            return invalid("must not be empty");
+        }
+        if branch.contains('+') {
+            return invalid("must not contain `+`");
         }
+
+        let path = Path::new(branch);
+        if path.has_root() {
+            return invalid("must not be an absolute path");
+        }
+        if path.components().any(|c| c == Component::ParentDir) {
+            return invalid("must not contain a `..` path component");
+        }
+
+        Ok(())
     }
 
     /// Builds the repository path
@@ -127,9 +316,42 @@ impl RepoInfo {
     /// # Returns
     /// Path in the format: `{root}/{host}/{user}/{repo}+{branch}`
     pub fn build_repo_path(&self, root: &Path, branch: &str) -> PathBuf {
-        root.join(&self.host)
-            .join(&self.user)
-            .join(format!("{}+{}", self.repo, branch))
+        self.build_repo_path_with(root, branch, &Layout::default())
+    }
+
+    /// Builds the repository path following `layout`
+    pub fn build_repo_path_with(&self, root: &Path, branch: &str, layout: &Layout) -> PathBuf {
+        layout.build(root, self, branch)
+    }
+
+    /// Builds the repository path without a branch suffix, following `layout`
+    ///
+    /// Also the path of the stable link to the default-branch worktree.
+    ///
+    /// # Arguments
+    /// * `root` - The root directory for managed repositories
+    /// * `layout` - The layout of the worktrees
+    ///
+    /// # Returns
+    /// Path in the format: `{root}/{host}/{user}/{repo}` for the default layout,
+    /// or `None` if the layout has no directory for it (see ``Layout::build_plain``)
+    pub fn build_repo_path_plain(&self, root: &Path, layout: &Layout) -> Option<PathBuf> {
+        layout.build_plain(root, self)
+    }
+
+    /// Builds the path of a bare clone, following `layout`
+    ///
+    /// # Arguments
+    /// * `root` - The root directory for managed repositories
+    /// * `layout` - The layout of the worktrees
+    ///
+    /// # Returns
+    /// Path in the format: `{root}/{host}/{user}/{repo}.git` for the default layout,
+    /// or `None` if the layout has no directory for it
+    pub fn build_bare_path(&self, root: &Path, layout: &Layout) -> Option<PathBuf> {
+        let mut path = self.build_repo_path_plain(root, layout)?.into_os_string();
+        path.push(BARE_SUFFIX);
+        Some(path.into())
     }
 
     /// Builds the shared file path
@@ -152,6 +374,7 @@ impl RepoInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
 
     #[test]
     fn test_from_url_https() {
@@ -187,6 +410,29 @@ mod tests {
         assert_eq!(info.repo, "repo");
     }
 
+    #[test]
+    fn test_from_url_ssh_with_port_and_login() {
+        // 目的: ポート番号や git 以外のログイン名を含む SSH URL の解析
+        // 検証: host にはログイン名とポート番号が含まれない
+
+        let info = RepoInfo::from_url("ssh://git@example.com:2222/u/r.git").unwrap();
+        assert_eq!(info.host, "example.com");
+        assert_eq!(info.user, "u");
+        assert_eq!(info.repo, "r");
+
+        let info = RepoInfo::from_url("ssh://deploy@host/u/r.git").unwrap();
+        assert_eq!(info.host, "host");
+        assert_eq!(info.user, "u");
+        assert_eq!(info.repo, "r");
+
+        let info = RepoInfo::from_url("deploy@host:u/r.git").unwrap();
+        assert_eq!(info.host, "host");
+        assert_eq!(info.user, "u");
+        assert_eq!(info.repo, "r");
+
+        assert!(RepoInfo::from_url("ssh://git@:2222/u/r.git").is_err());
+    }
+
     #[test]
     fn test_from_url_invalid() {
         assert!(RepoInfo::from_url("invalid").is_err());
@@ -216,6 +462,47 @@ mod tests {
         assert_eq!(info.branch, Some("feature/foobar".to_string()));
     }
 
+    #[test]
+    fn test_from_path_root_with_trailing_slash() {
+        let root = PathBuf::from("/grm/");
+        let path = PathBuf::from("/grm/github.com/u/r+main");
+        let info = RepoInfo::from_path(&root, &path).unwrap();
+        assert_eq!(info.host, "github.com");
+        assert_eq!(info.user, "u");
+        assert_eq!(info.repo, "r");
+        assert_eq!(info.branch, Some("main".to_string()));
+
+        let path = PathBuf::from("/grm/./github.com/u/r+main/");
+        let info = RepoInfo::from_path(&root, &path).unwrap();
+        assert_eq!(info.branch, Some("main".to_string()));
+    }
+
+    #[test]
+    fn test_from_path_resolved_symlinked_root() {
+        let fs = MockFileSystem::new();
+        fs.create_dir(Path::new("/real/github.com/u/r+main"))
+            .unwrap();
+        fs.add_symlink("/linked", "/real");
+        let layout = Layout::default();
+
+        let resolved = Path::new("/real/github.com/u/r+main");
+        let info =
+            RepoInfo::from_path_resolved(&fs, Path::new("/linked"), resolved, &layout).unwrap();
+        assert_eq!(info.repo, "r");
+        assert_eq!(info.branch, Some("main".to_string()));
+
+        assert!(RepoInfo::from_path(Path::new("/linked"), resolved).is_err());
+        assert!(
+            RepoInfo::from_path_resolved(
+                &fs,
+                Path::new("/linked"),
+                Path::new("/elsewhere/github.com/u/r"),
+                &layout
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_from_path_without_branch() {
         let root = PathBuf::from("/home/user/grm");
@@ -227,6 +514,20 @@ mod tests {
         assert_eq!(info.branch, None);
     }
 
+    #[test]
+    fn test_from_path_bare_clone() {
+        let root = PathBuf::from("/home/user/grm");
+        let info = RepoInfo::from_url("https://github.com/test/repo").unwrap();
+        let path = info.build_bare_path(&root, &Layout::default()).unwrap();
+        assert_eq!(
+            path,
+            PathBuf::from("/home/user/grm/github.com/test/repo.git")
+        );
+
+        let parsed = RepoInfo::from_path(&root, &path).unwrap();
+        assert_eq!(parsed, info);
+    }
+
     #[test]
     fn test_build_repo_path() {
         let info = RepoInfo::new(
@@ -243,6 +544,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_repo_path_plain() {
+        let info = RepoInfo::new(
+            "github.com".to_string(),
+            "test".to_string(),
+            "repo".to_string(),
+            None,
+        );
+        let root = PathBuf::from("/home/user/grm");
+        let path = info.build_repo_path_plain(&root, &Layout::default());
+        assert_eq!(
+            path,
+            Some(PathBuf::from("/home/user/grm/github.com/test/repo"))
+        );
+
+        // The plain path would be the directory holding the worktrees
+        let nested = Layout::parse("{host}/{user}/{repo}/{branch}").unwrap();
+        assert_eq!(info.build_repo_path_plain(&root, &nested), None);
+        assert_eq!(info.build_bare_path(&root, &nested), None);
+    }
+
     #[test]
     fn test_build_shared_path() {
         let info = RepoInfo::new(
@@ -275,13 +597,176 @@ mod tests {
         assert_eq!(info.repo, "repo-name");
     }
 
+    #[test]
+    fn test_from_url_normalizes_host() {
+        // 目的: ホスト名の正規化
+        // 検証: ホストのみ小文字化・www. 除去され、user/repo の大文字小文字は保持される
+
+        let info = RepoInfo::from_url("https://www.GitHub.com/User/Repo").unwrap();
+        assert_eq!(info.host, "github.com");
+        assert_eq!(info.user, "User");
+        assert_eq!(info.repo, "Repo");
+
+        let info = RepoInfo::from_url("git@GitHub.com:User/Repo.git").unwrap();
+        assert_eq!(info.host, "github.com");
+
+        let info = RepoInfo::from_url_with("https://www.GitHub.com/User/Repo", false).unwrap();
+        assert_eq!(info.host, "www.GitHub.com");
+    }
+
+    #[test]
+    fn test_from_url_shorthand() {
+        // 目的: スキームなしの host/user/repo 形式
+        // 検証: https:// として解釈され、クローン用URLが補完される
+
+        let info = RepoInfo::from_url("github.com/user/repo").unwrap();
+        assert_eq!(info.host, "github.com");
+        assert_eq!(info.user, "user");
+        assert_eq!(info.repo, "repo");
+
+        assert_eq!(
+            RepoInfo::expand_url("github.com/user/repo", None),
+            "https://github.com/user/repo"
+        );
+        assert_eq!(
+            RepoInfo::expand_url("git@github.com:user/repo.git", None),
+            "git@github.com:user/repo.git"
+        );
+        assert_eq!(RepoInfo::expand_url("just-a-word", None), "just-a-word");
+    }
+
+    #[test]
+    fn test_expand_url_relative_path_is_not_shorthand() {
+        // 目的: ./ や ../ で始まる相対パス、ドットで始まるホスト
+        // 検証: host/user/repo の短縮形とみなされず、ローカルパスとしてそのまま返る
+
+        for url in [
+            "../vendor/lib",
+            "./a.b/x/y",
+            "../a.b/user/repo",
+            "./user/repo",
+            ".hidden.dir/user/repo",
+        ] {
+            assert_eq!(RepoInfo::expand_url(url, None), url);
+            assert_eq!(RepoInfo::expand_url(url, Some("github.com")), url);
+            assert!(
+                RepoInfo::from_url(url).is_err(),
+                "{url} should not parse as a shorthand"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_url_default_host() {
+        // 目的: default_host を使った user/repo 形式の補完
+        // 検証: ホストなしの形式のみ default_host で補完され、それ以外は変わらない
+
+        let host = Some("gitlab.com");
+        assert_eq!(
+            RepoInfo::expand_url("user/repo", host),
+            "https://gitlab.com/user/repo"
+        );
+        assert_eq!(
+            RepoInfo::expand_url("group/subgroup/repo", host),
+            "https://gitlab.com/group/subgroup/repo"
+        );
+        assert_eq!(
+            RepoInfo::expand_url("github.com/user/repo", host),
+            "https://github.com/user/repo"
+        );
+        assert_eq!(
+            RepoInfo::expand_url("git@github.com:user/repo.git", host),
+            "git@github.com:user/repo.git"
+        );
+        assert_eq!(
+            RepoInfo::expand_url("/srv/user/repo", host),
+            "/srv/user/repo"
+        );
+        assert_eq!(RepoInfo::expand_url("user/repo", None), "user/repo");
+    }
+
+    #[test]
+    fn test_from_url_nested_namespace() {
+        // 目的: GitLab のサブグループを含むURL
+        // 検証: ホストと最後の要素の間が全てユーザー(名前空間)になる
+
+        let info = RepoInfo::from_url("https://gitlab.com/group/subgroup/repo.git").unwrap();
+        assert_eq!(info.host, "gitlab.com");
+        assert_eq!(info.user, "group/subgroup");
+        assert_eq!(info.repo, "repo");
+
+        let info = RepoInfo::from_url("git@gitlab.com:group/sub/subsub/repo.git").unwrap();
+        assert_eq!(info.user, "group/sub/subsub");
+        assert_eq!(info.repo, "repo");
+
+        assert!(RepoInfo::from_url("https://gitlab.com/group//repo").is_err());
+    }
+
+    #[test]
+    fn test_nested_namespace_round_trip() {
+        // 目的: サブグループのパス変換の往復
+        // 検証: build_repo_path で作ったパスを from_path で同じ情報に戻せる
+
+        let root = PathBuf::from("/grm");
+        for url in [
+            "https://gitlab.com/group/subgroup/repo",
+            "https://gitlab.com/group/sub/subsub/repo",
+        ] {
+            let info = RepoInfo::from_url(url).unwrap();
+
+            for branch in ["main", "feature/nested"] {
+                let path = info.build_repo_path(&root, branch);
+                let parsed = RepoInfo::from_path(&root, &path).unwrap();
+                assert_eq!(parsed.host, info.host);
+                assert_eq!(parsed.user, info.user);
+                assert_eq!(parsed.repo, info.repo);
+                assert_eq!(parsed.branch.as_deref(), Some(branch));
+            }
+
+            let plain = info
+                .build_repo_path_plain(&root, &Layout::default())
+                .unwrap();
+            let parsed = RepoInfo::from_path(&root, &plain).unwrap();
+            assert_eq!(parsed, info);
+        }
+
+        assert_eq!(
+            RepoInfo::from_url("https://gitlab.com/group/subgroup/repo")
+                .unwrap()
+                .build_repo_path(&root, "main"),
+            PathBuf::from("/grm/gitlab.com/group/subgroup/repo+main")
+        );
+    }
+
+    #[test]
+    fn test_from_url_local() {
+        // 目的: ローカルのリポジトリの URL
+        // 検証: file:// と絶対パスはホスト `local` の下に、親ディレクトリをユーザーとして配置される
+
+        for url in [
+            "file:///srv/git/vendor/lib.git",
+            "file:///srv/git/vendor/lib/",
+            "/srv/git/vendor/lib.git",
+        ] {
+            let info = RepoInfo::from_url(url).unwrap();
+            assert_eq!(info.host, LOCAL_HOST, "{url}");
+            assert_eq!(info.user, "vendor", "{url}");
+            assert_eq!(info.repo, "lib", "{url}");
+        }
+
+        assert!(RepoInfo::from_url("file:///lib.git").is_err());
+        assert!(RepoInfo::from_url("file://localhost/srv/git/lib.git").is_err());
+        assert!(RepoInfo::from_url("/").is_err());
+    }
+
     #[test]
     fn test_from_url_invalid_formats() {
         // 目的: 不正なURL形式のエラー処理
         // 検証: 様々な不正な形式で適切なエラーが返される
 
         // プロトコルなし
-        assert!(RepoInfo::from_url("github.com/user/repo").is_err());
+        assert!(RepoInfo::from_url("just-a-word").is_err());
+        assert!(RepoInfo::from_url("user/repo").is_err());
 
         // ユーザー/リポジトリ情報不足
         assert!(RepoInfo::from_url("https://github.com/user").is_err());
@@ -330,7 +815,9 @@ mod tests {
         let path = info.build_shared_path(&root, Path::new("config/database/settings.json"));
         assert_eq!(
             path,
-            PathBuf::from("/home/user/grm/.shared/github.com/test/repo/config/database/settings.json")
+            PathBuf::from(
+                "/home/user/grm/.shared/github.com/test/repo/config/database/settings.json"
+            )
         );
 
         // 深い階層
@@ -340,4 +827,24 @@ mod tests {
             PathBuf::from("/home/user/grm/.shared/github.com/test/repo/a/b/c/d/file.txt")
         );
     }
+
+    #[test]
+    fn test_validate_branch_name() {
+        // 目的: ブランチ名の検証
+        // 検証: 通常の名前とスラッシュ区切りは許可され、不正な名前は InvalidBranch になる
+
+        assert!(RepoInfo::validate_branch_name("main").is_ok());
+        assert!(RepoInfo::validate_branch_name("feature/login").is_ok());
+        assert!(RepoInfo::validate_branch_name("v1.0..fix").is_ok());
+
+        for branch in ["", "feat+fix", "/abs", "../escape", "feature/../../escape"] {
+            assert!(
+                matches!(
+                    RepoInfo::validate_branch_name(branch),
+                    Err(GrmError::InvalidBranch { .. })
+                ),
+                "{branch:?} should be rejected"
+            );
+        }
+    }
 }
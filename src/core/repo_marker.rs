@@ -0,0 +1,180 @@
+//! Marker recording where a managed worktree belongs
+//!
+//! `clone` and `worktree split` write it to `{worktree}/.grm/repo.toml`:
+//!
+//! ```toml
+//! host = "github.com"
+//! user = "user"
+//! repo = "repo"
+//! branch = "main"
+//! url = "https://github.com/user/repo"
+//! ```
+//!
+//! Unlike the directory name, the marker is unambiguous for repository names
+//! containing `+`. The `.grm/` directory is added to `.git/info/exclude` so that
+//! it does not show up as an untracked file.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::RepoInfo;
+use crate::core::ports::FileSystem;
+use crate::errors::GrmError;
+
+/// Directory of the marker inside a worktree
+pub const MARKER_DIR: &str = ".grm";
+/// File name of the marker inside `MARKER_DIR`
+pub const MARKER_FILE: &str = "repo.toml";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepoMarker {
+    pub host: String,
+    pub user: String,
+    pub repo: String,
+    pub branch: Option<String>,
+    /// URL the worktree was cloned from
+    pub url: String,
+}
+
+impl RepoMarker {
+    pub fn new(info: &RepoInfo, branch: &str, url: &str) -> Self {
+        Self {
+            host: info.host.clone(),
+            user: info.user.clone(),
+            repo: info.repo.clone(),
+            branch: Some(branch.to_string()),
+            url: url.to_string(),
+        }
+    }
+
+    pub fn path(repo_path: &Path) -> PathBuf {
+        repo_path.join(MARKER_DIR).join(MARKER_FILE)
+    }
+
+    /// Read the marker of `repo_path`, or `None` if it is missing or unreadable
+    pub fn load(fs: &dyn FileSystem, repo_path: &Path) -> Option<Self> {
+        let path = Self::path(repo_path);
+        if !fs.exists(&path) {
+            return None;
+        }
+
+        let content = fs.read_to_string(&path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Write the marker into `repo_path` and hide it from `git status`
+    pub fn save(&self, fs: &dyn FileSystem, repo_path: &Path) -> Result<(), GrmError> {
+        let content = toml::to_string(self).map_err(|e| GrmError::Io(std::io::Error::other(e)))?;
+        fs.create_dir(&repo_path.join(MARKER_DIR))?;
+        fs.write(&Self::path(repo_path), &content)?;
+
+        Self::exclude(fs, repo_path)
+    }
+
+    pub fn into_info(self) -> RepoInfo {
+        RepoInfo::new(self.host, self.user, self.repo, self.branch)
+    }
+
+    /// Add `MARKER_DIR` to `.git/info/exclude` of the repository at `repo_path`
+    ///
+    /// Worktrees share the exclude file of their main repository, so this only
+    /// acts where `.git` is a directory.
+    pub fn exclude(fs: &dyn FileSystem, repo_path: &Path) -> Result<(), GrmError> {
+        let git_dir = repo_path.join(".git");
+        if !fs.is_dir(&git_dir) {
+            return Ok(());
+        }
+
+        let entry = format!("/{MARKER_DIR}/");
+        let info_dir = git_dir.join("info");
+        let exclude_path = info_dir.join("exclude");
+        let mut content = if fs.exists(&exclude_path) {
+            fs.read_to_string(&exclude_path)?
+        } else {
+            String::new()
+        };
+        if content.lines().any(|line| line.trim() == entry) {
+            return Ok(());
+        }
+
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&entry);
+        content.push('\n');
+
+        fs.create_dir(&info_dir)?;
+        fs.write(&exclude_path, &content)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    #[test]
+    fn test_save_and_load() {
+        // 目的: マーカーの保存と読み込み
+        // 検証: `+` を含むリポジトリ名も含めて保存した内容がそのまま読み込まれる
+
+        let fs = MockFileSystem::new();
+        let repo_path = Path::new("/grm/github.com/user/c++lib+main");
+        fs.create_dir(repo_path).unwrap();
+        let info = RepoInfo::new(
+            "github.com".to_string(),
+            "user".to_string(),
+            "c++lib".to_string(),
+            None,
+        );
+        let marker = RepoMarker::new(&info, "main", "https://github.com/user/c++lib");
+
+        marker.save(&fs, repo_path).unwrap();
+        let loaded = RepoMarker::load(&fs, repo_path).unwrap();
+
+        assert_eq!(loaded, marker);
+        assert_eq!(loaded.into_info().repo, "c++lib");
+    }
+
+    #[test]
+    fn test_save_excludes_marker_once() {
+        // 目的: .git/info/exclude への追記
+        // 検証: 既存の内容を残して一度だけ追記される
+
+        let fs = MockFileSystem::new();
+        let repo_path = Path::new("/grm/github.com/user/repo+main");
+        fs.create_dir(&repo_path.join(".git/info")).unwrap();
+        fs.write(&repo_path.join(".git/info/exclude"), "*.log")
+            .unwrap();
+        let info = RepoInfo::from_url("https://github.com/user/repo").unwrap();
+        let marker = RepoMarker::new(&info, "main", "https://github.com/user/repo");
+
+        marker.save(&fs, repo_path).unwrap();
+        marker.save(&fs, repo_path).unwrap();
+
+        assert_eq!(
+            fs.read_to_string(&repo_path.join(".git/info/exclude"))
+                .unwrap(),
+            "*.log\n/.grm/\n"
+        );
+    }
+
+    #[test]
+    fn test_load_missing_or_broken() {
+        // 目的: マーカーが無い・壊れている場合の扱い
+        // 検証: どちらも None になる
+
+        let fs = MockFileSystem::new();
+        let repo_path = Path::new("/grm/github.com/user/repo+main");
+        fs.create_dir(&repo_path.join(MARKER_DIR)).unwrap();
+
+        let missing = RepoMarker::load(&fs, repo_path);
+        fs.write(&RepoMarker::path(repo_path), "host = ").unwrap();
+        let broken = RepoMarker::load(&fs, repo_path);
+
+        assert!(missing.is_none());
+        assert!(broken.is_none());
+    }
+}
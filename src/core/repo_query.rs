@@ -0,0 +1,142 @@
+use crate::core::RepoInfo;
+
+/// A user supplied query used to look up managed repositories
+///
+/// examples of supported query formats:
+/// - `repo`
+/// - `user/repo`
+/// - `host/user/repo`
+/// - `user/repo+branch`
+/// - any URL accepted by [`RepoInfo::from_url`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoQuery {
+    segments: Vec<String>,
+    branch: Option<String>,
+}
+
+impl RepoQuery {
+    pub fn parse(query: &str) -> Self {
+        let query = query.trim();
+
+        let (path, branch) = match query.split_once('+') {
+            Some((path, branch)) if !branch.is_empty() => (path, Some(branch.to_string())),
+            Some((path, _)) => (path, None),
+            None => (query, None),
+        };
+
+        let segments = match RepoInfo::from_url(path) {
+            Ok(info) => std::iter::once(info.host.as_str())
+                .chain(info.user.split('/'))
+                .chain(std::iter::once(info.repo.as_str()))
+                .map(str::to_string)
+                .collect(),
+            Err(_) => path
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+        };
+
+        Self { segments, branch }
+    }
+
+    /// Check if the repository matches this query
+    ///
+    /// The query segments are matched against the trailing components of
+    /// `{host}/{user}/{repo}`, and the branch (if any) must match exactly.
+    /// A nested user such as `group/subgroup` counts as several components.
+    pub fn matches(&self, info: &RepoInfo) -> bool {
+        let components = std::iter::once(info.host.as_str())
+            .chain(info.user.split('/'))
+            .chain(std::iter::once(info.repo.as_str()))
+            .collect::<Vec<_>>();
+
+        if self.segments.is_empty() || self.segments.len() > components.len() {
+            return false;
+        }
+
+        let offset = components.len() - self.segments.len();
+        let path_matches = self
+            .segments
+            .iter()
+            .zip(&components[offset..])
+            .all(|(segment, component)| segment == component);
+
+        let branch_matches = match &self.branch {
+            Some(branch) => info.branch.as_ref() == Some(branch),
+            None => true,
+        };
+
+        path_matches && branch_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(branch: Option<&str>) -> RepoInfo {
+        RepoInfo::new(
+            "github.com".to_string(),
+            "user".to_string(),
+            "repo".to_string(),
+            branch.map(str::to_string),
+        )
+    }
+
+    #[test]
+    fn test_matches_repo_name() {
+        let query = RepoQuery::parse("repo");
+        assert!(query.matches(&info(Some("main"))));
+
+        let query = RepoQuery::parse("other");
+        assert!(!query.matches(&info(Some("main"))));
+    }
+
+    #[test]
+    fn test_matches_partial_path() {
+        assert!(RepoQuery::parse("user/repo").matches(&info(None)));
+        assert!(RepoQuery::parse("github.com/user/repo").matches(&info(None)));
+        assert!(!RepoQuery::parse("gitlab.com/user/repo").matches(&info(None)));
+        assert!(!RepoQuery::parse("a/github.com/user/repo").matches(&info(None)));
+    }
+
+    #[test]
+    fn test_matches_branch() {
+        let query = RepoQuery::parse("repo+feature/foo");
+        assert!(query.matches(&info(Some("feature/foo"))));
+        assert!(!query.matches(&info(Some("main"))));
+        assert!(!query.matches(&info(None)));
+    }
+
+    #[test]
+    fn test_matches_url() {
+        let query = RepoQuery::parse("git@github.com:user/repo.git");
+        assert!(query.matches(&info(Some("main"))));
+
+        let query = RepoQuery::parse("https://gitlab.com/user/repo");
+        assert!(!query.matches(&info(Some("main"))));
+    }
+
+    #[test]
+    fn test_matches_nested_namespace() {
+        let info = RepoInfo::new(
+            "gitlab.com".to_string(),
+            "group/subgroup".to_string(),
+            "repo".to_string(),
+            None,
+        );
+
+        assert!(RepoQuery::parse("repo").matches(&info));
+        assert!(RepoQuery::parse("subgroup/repo").matches(&info));
+        assert!(RepoQuery::parse("gitlab.com/group/subgroup/repo").matches(&info));
+        assert!(RepoQuery::parse("https://gitlab.com/group/subgroup/repo").matches(&info));
+        assert!(!RepoQuery::parse("group/repo").matches(&info));
+    }
+
+    #[test]
+    fn test_empty_query_matches_nothing() {
+        assert!(!RepoQuery::parse("").matches(&info(None)));
+        assert!(!RepoQuery::parse("+main").matches(&info(Some("main"))));
+    }
+}
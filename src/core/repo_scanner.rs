@@ -1,8 +1,9 @@
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use crate::core::RepoInfo;
 use crate::core::ports::FileSystem;
+use crate::core::{GrmIgnore, Layout, RepoInfo, RepoMarker, RepoQuery};
 
 #[derive(Debug, thiserror::Error)]
 pub enum ScanError {
@@ -10,69 +11,306 @@ pub enum ScanError {
     Io(String),
 }
 
+/// A repository found under the managed root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoEntry {
+    pub path: PathBuf,
+    /// `None` if the path does not follow the managed directory layout
+    pub info: Option<RepoInfo>,
+}
+
 pub struct RepoScanner {
     fs: Arc<dyn FileSystem>,
+    max_depth: Option<usize>,
+    layout: Layout,
 }
 
 impl RepoScanner {
     pub fn new(fs: Arc<dyn FileSystem>) -> Self {
-        Self { fs }
+        Self {
+            fs,
+            max_depth: None,
+            layout: Layout::default(),
+        }
+    }
+
+    /// Stop descending below `max_depth` levels under the scanned root
+    ///
+    /// Repositories deeper than that are silently skipped, except for worktrees
+    /// of branches containing `/`, e.g. below a `{repo}+{branch}` directory.
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Read worktree paths with `layout` instead of the default one
+    #[must_use]
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = layout;
+        self
     }
 }
 
 impl RepoScanner {
+    /// Find git repositories under `root`
+    ///
+    /// Directories matching `{root}/.grmignore` are not scanned.
+    /// The subdirectories of `root` are scanned in parallel if the file system
+    /// adapter can be shared between threads.
     pub fn scan_repositories(&self, root: &Path) -> Result<Vec<PathBuf>, ScanError> {
-        let mut repos = Vec::new();
-        match self.fs.read_dir(root) {
-            Ok(entries) => {
-                let dirs = entries
-                    .into_iter()
-                    .filter(|p| !self.fs.is_symlink(p) && self.fs.is_dir(p))
-                    .collect::<Vec<_>>();
-
+        let ignore =
+            GrmIgnore::load(self.fs.as_ref(), root).map_err(|e| ScanError::Io(e.to_string()))?;
+        let dirs = sub_directories(self.fs.as_ref(), root)?;
+        let ancestors = self.fs.canonicalize(root).into_iter().collect::<Vec<_>>();
+
+        match self.fs.as_sync() {
+            Some(fs) if dirs.len() > 1 => {
+                scan_parallel(fs, &dirs, self.max_depth, &self.layout, &ignore, &ancestors)
+            }
+            _ => {
+                let mut repos = Vec::new();
                 for dir in dirs {
-                    if self.fs.is_git_repository(&dir) {
-                        repos.push(dir);
-                    } else {
-                        let sub_repos = self.scan_repositories(&dir)?;
-                        repos.extend(sub_repos);
-                    }
+                    repos.extend(scan_directory(
+                        self.fs.as_ref(),
+                        dir,
+                        1,
+                        self.max_depth,
+                        &self.layout,
+                        &ignore,
+                        &mut ancestors.clone(),
+                    )?);
                 }
-
                 Ok(repos)
             }
-            Err(e) => Err(ScanError::Io(e.to_string())),
         }
     }
 
+    /// Scan repositories and parse their location into ``RepoInfo``
+    pub fn scan_entries(&self, root: &Path) -> Result<Vec<RepoEntry>, ScanError> {
+        let mut entries = self
+            .scan_repositories(root)?
+            .into_iter()
+            .map(|path| {
+                let info = RepoInfo::from_path_with(root, &path, &self.layout).ok();
+                RepoEntry { path, info }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(entries)
+    }
+
+    /// Like `scan_entries`, but read ``RepoInfo`` from the `.grm/repo.toml` markers
+    ///
+    /// The marker is authoritative where present; repositories without one
+    /// fall back to parsing their location.
+    pub fn scan_repositories_fast(&self, root: &Path) -> Result<Vec<RepoEntry>, ScanError> {
+        let mut entries = self
+            .scan_repositories(root)?
+            .into_iter()
+            .map(|path| {
+                let info = match RepoMarker::load(self.fs.as_ref(), &path) {
+                    Some(marker) => Some(marker.into_info()),
+                    None => RepoInfo::from_path_with(root, &path, &self.layout).ok(),
+                };
+                RepoEntry { path, info }
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(entries)
+    }
+
+    /// Find the worktrees of the repository `repo_info`, with or without a branch suffix
+    ///
+    /// Each candidate's host, user and repository are compared exactly, so that
+    /// e.g. `repo+main` and `repository+main` are never mixed up. The marker is
+    /// used where present, which also tells `repo` apart from a `repo+extra`.
     pub fn scan_worktrees(
         &self,
         root: &Path,
         repo_info: &RepoInfo,
     ) -> Result<Vec<PathBuf>, ScanError> {
-        let mut repos = Vec::new();
-        let all_repos = self.scan_repositories(root)?;
-
-        // root/host/user/repo+
-        let repo_prefix = repo_info
-            .build_repo_path(root, "")
-            .to_string_lossy()
-            .to_string();
-
-        for repo_path in all_repos {
-            if repo_path.to_string_lossy().starts_with(&repo_prefix) {
-                repos.push(repo_path);
-            }
-        }
+        let repos = self
+            .scan_repositories_fast(root)?
+            .into_iter()
+            .filter(|entry| {
+                entry.info.as_ref().is_some_and(|info| {
+                    same_or_unknown(&info.host, &repo_info.host)
+                        && same_or_unknown(&info.user, &repo_info.user)
+                        && info.repo == repo_info.repo
+                })
+            })
+            .map(|entry| entry.path)
+            .collect();
+
+        Ok(repos)
+    }
+
+    /// Find managed repositories matching a query
+    ///
+    /// Repositories outside the managed layout are ignored.
+    pub fn scan_matching(&self, root: &Path, query: &RepoQuery) -> Result<Vec<PathBuf>, ScanError> {
+        let mut repos = self
+            .scan_repositories(root)?
+            .into_iter()
+            .filter(|path| {
+                RepoInfo::from_path_with(root, path, &self.layout)
+                    .is_ok_and(|info| query.matches(&info))
+            })
+            .collect::<Vec<_>>();
+        repos.sort();
 
         Ok(repos)
     }
 }
 
+/// Whether `a` and `b` are equal, or either is empty as read with a layout without that token
+fn same_or_unknown(a: &str, b: &str) -> bool {
+    a.is_empty() || b.is_empty() || a == b
+}
+
+/// Whether `path` is a bare clone, i.e. a `*.git` directory with git's own layout
+pub fn is_bare_repository(fs: &dyn FileSystem, path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "git")
+        && fs.exists(&path.join("HEAD"))
+        && fs.is_dir(&path.join("objects"))
+}
+
+fn sub_directories(fs: &dyn FileSystem, path: &Path) -> Result<Vec<PathBuf>, ScanError> {
+    let entries = fs
+        .read_dir(path)
+        .map_err(|e| ScanError::Io(e.to_string()))?;
+
+    Ok(entries
+        .into_iter()
+        .filter(|p| !fs.is_symlink(p) && fs.is_dir(p))
+        .collect())
+}
+
+/// Repositories at or below `dir`, without descending into repositories
+///
+/// `depth` is the level of `dir` below the scanned root.
+/// Directories matching `ignore` are skipped along with everything below them.
+/// `ancestors` holds the canonical paths of the directories above `dir`.
+/// Symlinked directories are never entered, but a bind mount or a Windows
+/// junction can still lead back to one of them, so such a directory is skipped,
+/// as is one which cannot be resolved at all.
+fn scan_directory(
+    fs: &dyn FileSystem,
+    dir: PathBuf,
+    depth: usize,
+    max_depth: Option<usize>,
+    layout: &Layout,
+    ignore: &GrmIgnore,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<Vec<PathBuf>, ScanError> {
+    if ignore.is_ignored(&dir) {
+        return Ok(Vec::new());
+    }
+    let Ok(canonical) = fs.canonicalize(&dir) else {
+        return Ok(Vec::new());
+    };
+    if ancestors.contains(&canonical) {
+        return Ok(Vec::new());
+    }
+    if fs.is_git_repository(&dir) || is_bare_repository(fs, &dir) {
+        return Ok(vec![dir]);
+    }
+    if max_depth.is_some_and(|max_depth| {
+        depth >= max_depth && !continues_branch(layout, &dir, depth - max_depth)
+    }) {
+        return Ok(Vec::new());
+    }
+
+    let mut repos = Vec::new();
+    ancestors.push(canonical);
+    for sub_dir in sub_directories(fs, &dir)? {
+        repos.extend(scan_directory(
+            fs,
+            sub_dir,
+            depth + 1,
+            max_depth,
+            layout,
+            ignore,
+            ancestors,
+        )?);
+    }
+    ancestors.pop();
+    Ok(repos)
+}
+
+/// Whether `dir`, `below` levels under the depth limit, lies within a `{repo}+{branch}` directory
+///
+/// A branch name containing `/` is laid out as nested directories such as
+/// `repo+feature/foobar`, so the worktree is found below the depth limit.
+fn continues_branch(layout: &Layout, dir: &Path, below: usize) -> bool {
+    dir.ancestors()
+        .nth(below)
+        .and_then(Path::file_name)
+        .is_some_and(|name| layout.continues_branch(&name.to_string_lossy()))
+}
+
+/// Scan `dirs` on a bounded number of threads, keeping the order of `dirs`
+fn scan_parallel(
+    fs: &(dyn FileSystem + Sync),
+    dirs: &[PathBuf],
+    max_depth: Option<usize>,
+    layout: &Layout,
+    ignore: &GrmIgnore,
+    ancestors: &[PathBuf],
+) -> Result<Vec<PathBuf>, ScanError> {
+    let workers = std::thread::available_parallelism()
+        .map_or(1, std::num::NonZeroUsize::get)
+        .min(dirs.len());
+    let next = AtomicUsize::new(0);
+
+    let mut results = std::thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut scanned = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(dir) = dirs.get(index) else {
+                            break;
+                        };
+                        let repos = scan_directory(
+                            fs,
+                            dir.clone(),
+                            1,
+                            max_depth,
+                            layout,
+                            ignore,
+                            &mut ancestors.to_vec(),
+                        );
+                        scanned.push((index, repos));
+                    }
+                    scanned
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Repository scan thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut repos = Vec::new();
+    for (_, result) in results {
+        repos.extend(result?);
+    }
+    Ok(repos)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::adapters::test_helpers::MockFileSystem;
+    use crate::core::grm_ignore::GRMIGNORE_FILE;
 
     #[test]
     fn test_scan_repositories_flat_structure() {
@@ -83,9 +321,9 @@ mod tests {
         let root = PathBuf::from("/grm");
 
         fs.add_dir(&root);
-        fs.add_git_repo(&root.join("repo1"));
-        fs.add_git_repo(&root.join("repo2"));
-        fs.add_git_repo(&root.join("repo3"));
+        fs.add_git_repo(root.join("repo1"));
+        fs.add_git_repo(root.join("repo2"));
+        fs.add_git_repo(root.join("repo3"));
 
         let scanner = RepoScanner::new(fs);
         let result = scanner.scan_repositories(&root);
@@ -109,14 +347,14 @@ mod tests {
         let root = PathBuf::from("/grm");
 
         fs.add_dir(&root);
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo1"));
-        fs.add_git_repo(&root.join("github.com/user/repo2"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo1"));
+        fs.add_git_repo(root.join("github.com/user/repo2"));
 
-        fs.add_dir(&root.join("gitlab.com"));
-        fs.add_dir(&root.join("gitlab.com/org"));
-        fs.add_git_repo(&root.join("gitlab.com/org/project"));
+        fs.add_dir(root.join("gitlab.com"));
+        fs.add_dir(root.join("gitlab.com/org"));
+        fs.add_git_repo(root.join("gitlab.com/org/project"));
 
         let scanner = RepoScanner::new(fs);
         let result = scanner.scan_repositories(&root);
@@ -130,6 +368,165 @@ mod tests {
         assert!(repos.contains(&root.join("gitlab.com/org/project")));
     }
 
+    #[test]
+    fn test_scan_repositories_git_file_and_directory() {
+        // 目的: `.git` ファイルを持つワークツリーのスキャン
+        // 検証: `.git` ディレクトリと同様に検出され、壊れたシンボリックリンクは無視される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_worktree(
+            root.join("github.com/user/repo+feature"),
+            root.join("github.com/user/repo+main/.git/worktrees/repo+feature"),
+        );
+        fs.add_dir(root.join("github.com/user/broken"));
+        fs.add_symlink(root.join("github.com/user/broken/.git"), "/missing");
+
+        let scanner = RepoScanner::new(fs);
+        let mut repos = scanner.scan_repositories(&root).unwrap();
+        repos.sort();
+
+        assert_eq!(
+            repos,
+            vec![
+                root.join("github.com/user/repo+feature"),
+                root.join("github.com/user/repo+main"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_worktrees_bare_clone() {
+        // 目的: `{repo}.git` のベアクローンのスキャン
+        // 検証: リポジトリとして検出され、同じリポジトリのワークツリーと一緒に見つかる。
+        //       HEAD や objects の無い `*.git` ディレクトリは対象外
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_bare_repo(root.join("github.com/user/repo.git"));
+        fs.add_bare_repo(root.join("github.com/user/other.git"));
+        fs.add_dir(root.join("github.com/user/notes.git"));
+
+        let scanner = RepoScanner::new(fs);
+        let info = RepoInfo::from_url("https://github.com/user/repo").unwrap();
+        let mut repos = scanner.scan_worktrees(&root, &info).unwrap();
+        repos.sort();
+
+        assert_eq!(
+            repos,
+            vec![
+                root.join("github.com/user/repo+main"),
+                root.join("github.com/user/repo.git"),
+            ]
+        );
+        assert_eq!(scanner.scan_repositories(&root).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_scan_repositories_symlink_cycle() {
+        // 目的: シンボリックリンクの循環や壊れたリンクを含むツリーのスキャン
+        // 検証: エラーにならず終了し、祖先に戻るジャンクションは辿らない
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        // user/loop -> user, a <-> b, broken -> /missing
+        fs.add_symlink(
+            root.join("github.com/user/loop"),
+            root.join("github.com/user"),
+        );
+        fs.add_symlink(root.join("a"), root.join("b"));
+        fs.add_symlink(root.join("b"), root.join("a"));
+        fs.add_symlink(root.join("broken"), "/missing");
+        // A junction is no symlink, but leads back to the root
+        fs.create_dir(&root.join("github.com/user/up/github.com/user"))
+            .unwrap();
+        fs.set_canonical(root.join("github.com/user/up"), &root);
+        fs.add_git_repo(root.join("github.com/user/up/github.com/user/repo+main"));
+
+        let scanner = RepoScanner::new(fs.clone());
+        let repos = scanner.scan_repositories(&root).unwrap();
+
+        assert_eq!(repos, vec![root.join("github.com/user/repo+main")]);
+        assert!(fs.canonicalize(&root.join("a")).is_err());
+        assert!(fs.canonicalize(&root.join("broken")).is_err());
+    }
+
+    #[test]
+    fn test_scan_repositories_large_tree() {
+        // 目的: 大量のリポジトリを含むツリーのスキャン
+        // 検証: 並列スキャンの結果が逐次スキャンと一致する
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        for host in 0..8 {
+            let host_dir = root.join(format!("host{host}.example.com"));
+            fs.add_dir(&host_dir);
+            for user in 0..8 {
+                let user_dir = host_dir.join(format!("user{user}"));
+                fs.add_dir(&user_dir);
+                for repo in 0..8 {
+                    fs.add_git_repo(user_dir.join(format!("repo{repo}+main")));
+                }
+            }
+        }
+
+        let scanner = RepoScanner::new(fs.clone());
+        let mut repos = scanner.scan_repositories(&root).unwrap();
+        repos.sort();
+
+        let mut expected = scan_directory(
+            fs.as_ref(),
+            root.clone(),
+            0,
+            None,
+            &Layout::default(),
+            &GrmIgnore::default(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        expected.sort();
+
+        assert_eq!(repos.len(), 512);
+        assert_eq!(repos, expected);
+    }
+
+    #[test]
+    fn test_scan_repositories_grmignore() {
+        // 目的: `.grmignore` による除外
+        // 検証: `archive/` 以下のリポジトリは並列・逐次どちらのスキャンでも検出されない
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.create_dir(&root.join("archive/github.com/user"))
+            .unwrap();
+        fs.add_git_repo(root.join("archive/github.com/user/old+main"));
+        fs.add_git_repo(root.join("archive/loose"));
+        fs.add_file_with_content(root.join(GRMIGNORE_FILE), "# old clones\narchive/\n");
+
+        let parallel = RepoScanner::new(fs.clone())
+            .scan_repositories(&root)
+            .unwrap();
+        fs.remove(&root.join("github.com")).unwrap();
+        let sequential = RepoScanner::new(fs).scan_repositories(&root).unwrap();
+
+        assert_eq!(parallel, vec![root.join("github.com/user/repo+main")]);
+        assert!(sequential.is_empty());
+    }
+
     #[test]
     fn test_scan_repositories_skip_symlinks() {
         // 目的: シンボリックリンクのスキップ
@@ -139,8 +536,8 @@ mod tests {
         let root = PathBuf::from("/grm");
 
         fs.add_dir(&root);
-        fs.add_git_repo(&root.join("real_repo"));
-        fs.add_symlink(&root.join("link_to_repo"), &root.join("real_repo"));
+        fs.add_git_repo(root.join("real_repo"));
+        fs.add_symlink(root.join("link_to_repo"), root.join("real_repo"));
 
         let scanner = RepoScanner::new(fs);
         let result = scanner.scan_repositories(&root);
@@ -162,11 +559,11 @@ mod tests {
         let root = PathBuf::from("/grm");
 
         fs.add_dir(&root);
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo1+main"));
-        fs.add_git_repo(&root.join("github.com/user/repo1+feature"));
-        fs.add_git_repo(&root.join("github.com/user/repo2+main"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo1+main"));
+        fs.add_git_repo(root.join("github.com/user/repo1+feature"));
+        fs.add_git_repo(root.join("github.com/user/repo2+main"));
 
         let scanner = RepoScanner::new(fs);
 
@@ -183,6 +580,62 @@ mod tests {
         assert!(!worktrees.contains(&root.join("github.com/user/repo2+main")));
     }
 
+    #[test]
+    fn test_scan_worktrees_exact_repository_name() {
+        // 目的: 名前が前方一致する別リポジトリとの区別
+        // 検証: `repo` の検索で `repository` のワークツリーは返されず、マーカーがあればそれに従う
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repository+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+extra+main"));
+        let other = RepoInfo::new(
+            "github.com".to_string(),
+            "user".to_string(),
+            "repo+extra".to_string(),
+            None,
+        );
+        RepoMarker::new(&other, "main", "https://github.com/user/repo+extra")
+            .save(fs.as_ref(), &root.join("github.com/user/repo+extra+main"))
+            .unwrap();
+
+        let scanner = RepoScanner::new(fs);
+
+        let repo_info = RepoInfo::from_url("https://github.com/user/repo").unwrap();
+        let worktrees = scanner.scan_worktrees(&root, &repo_info).unwrap();
+
+        assert_eq!(worktrees, vec![root.join("github.com/user/repo+main")]);
+    }
+
+    #[test]
+    fn test_scan_worktrees_includes_plain_directory() {
+        // 目的: ブランチ名なしのディレクトリの検出
+        // 検証: `{repo}` もワークツリーとして返される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo1"));
+        fs.add_git_repo(root.join("github.com/user/repo1+feature"));
+
+        let scanner = RepoScanner::new(fs);
+
+        let repo_info = RepoInfo::from_url("https://github.com/user/repo1").unwrap();
+        let worktrees = scanner.scan_worktrees(&root, &repo_info).unwrap();
+
+        assert_eq!(worktrees.len(), 2);
+        assert!(worktrees.contains(&root.join("github.com/user/repo1")));
+        assert!(worktrees.contains(&root.join("github.com/user/repo1+feature")));
+    }
+
     #[test]
     fn test_scan_repositories_empty() {
         // 目的: リポジトリなしの場合
@@ -192,7 +645,7 @@ mod tests {
         let root = PathBuf::from("/grm");
 
         fs.add_dir(&root);
-        fs.add_dir(&root.join("empty_dir"));
+        fs.add_dir(root.join("empty_dir"));
 
         let scanner = RepoScanner::new(fs);
         let result = scanner.scan_repositories(&root);
@@ -202,4 +655,183 @@ mod tests {
 
         assert_eq!(repos.len(), 0);
     }
+
+    #[test]
+    fn test_scan_entries_parses_layout() {
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("stray"));
+
+        let scanner = RepoScanner::new(fs);
+        let entries = scanner.scan_entries(&root).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, root.join("github.com/user/repo+main"));
+        assert_eq!(
+            entries[0].info,
+            Some(RepoInfo::new(
+                "github.com".to_string(),
+                "user".to_string(),
+                "repo".to_string(),
+                Some("main".to_string()),
+            ))
+        );
+        assert_eq!(entries[1].path, root.join("stray"));
+        assert_eq!(entries[1].info, None);
+    }
+
+    #[test]
+    fn test_scan_repositories_fast_prefers_marker() {
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/c++lib+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        let marked = RepoInfo::new(
+            "github.com".to_string(),
+            "user".to_string(),
+            "c++lib".to_string(),
+            None,
+        );
+        RepoMarker::new(&marked, "main", "https://github.com/user/c++lib")
+            .save(fs.as_ref(), &root.join("github.com/user/c++lib+main"))
+            .unwrap();
+
+        let scanner = RepoScanner::new(fs);
+        let entries = scanner.scan_repositories_fast(&root).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        let info = entries[0].info.as_ref().unwrap();
+        assert_eq!(info.repo, "c++lib");
+        assert_eq!(info.branch, Some("main".to_string()));
+        // Without a marker the layout is parsed as before
+        assert_eq!(entries[1].info.as_ref().unwrap().repo, "repo");
+    }
+
+    #[test]
+    fn test_scan_matching_by_query() {
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.add_dir(&root);
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+        fs.add_git_repo(root.join("github.com/user/other+main"));
+
+        let scanner = RepoScanner::new(fs);
+
+        let repos = scanner
+            .scan_matching(&root, &RepoQuery::parse("user/repo"))
+            .unwrap();
+        assert_eq!(
+            repos,
+            vec![
+                root.join("github.com/user/repo+feature"),
+                root.join("github.com/user/repo+main"),
+            ]
+        );
+
+        let repos = scanner
+            .scan_matching(&root, &RepoQuery::parse("repo+main"))
+            .unwrap();
+        assert_eq!(repos, vec![root.join("github.com/user/repo+main")]);
+    }
+
+    #[test]
+    fn test_scan_repositories_max_depth() {
+        // 目的: 探索深さの上限
+        // 検証: 上限より深いリポジトリは検出されず、エラーにもならない
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.create_dir(&root.join("home/user/projects/deep/nested"))
+            .unwrap();
+        fs.add_git_repo(root.join("home/user/projects/deep/nested/repo"));
+
+        let scanner = RepoScanner::new(fs.clone()).with_max_depth(Layout::default().depth());
+        let repos = scanner.scan_repositories(&root).unwrap();
+        let unlimited = RepoScanner::new(fs).scan_repositories(&root).unwrap();
+
+        assert_eq!(repos, vec![root.join("github.com/user/repo+main")]);
+        assert_eq!(unlimited.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_repositories_max_depth_slashed_branch() {
+        // 目的: `/` を含むブランチ名のワークツリーと探索深さの上限
+        // 検証: `repo+feature/foobar` は上限より深くても検出され、`repo+feature` 自体は検出されない
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+
+        fs.create_dir(&root.join("github.com/user/repo+feature"))
+            .unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature/foobar"));
+        fs.create_dir(&root.join("github.com/user/repo+fix/deep"))
+            .unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+fix/deep/nested"));
+
+        let scanner = RepoScanner::new(fs).with_max_depth(Layout::default().depth());
+        let entries = scanner.scan_entries(&root).unwrap();
+
+        let found = entries
+            .iter()
+            .map(|entry| {
+                let info = entry.info.as_ref().unwrap();
+                (info.repo.as_str(), info.branch.as_deref())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            vec![
+                ("repo", Some("feature/foobar")),
+                ("repo", Some("fix/deep/nested")),
+                ("repo", Some("main")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_worktrees_with_layout() {
+        // 目的: 設定したレイアウトでのワークツリー検索
+        // 検証: `{user}/{repo}@{branch}` のワークツリーが探索深さの上限内外で検出され、ホストの無いレイアウトでも URL と一致する
+
+        let fs = Arc::new(MockFileSystem::new());
+        let root = PathBuf::from("/grm");
+        let layout = Layout::parse("{user}/{repo}@{branch}").unwrap();
+
+        fs.create_dir(&root.join("user/repo@feature")).unwrap();
+        fs.add_git_repo(root.join("user/repo@main"));
+        fs.add_git_repo(root.join("user/repo@feature/foobar"));
+        fs.add_git_repo(root.join("user/other@main"));
+
+        let scanner = RepoScanner::new(fs)
+            .with_max_depth(layout.depth())
+            .with_layout(layout);
+        let info = RepoInfo::from_url("https://github.com/user/repo").unwrap();
+        let mut worktrees = scanner.scan_worktrees(&root, &info).unwrap();
+        worktrees.sort();
+
+        assert_eq!(
+            worktrees,
+            vec![
+                root.join("user/repo@feature/foobar"),
+                root.join("user/repo@main"),
+            ]
+        );
+    }
 }
@@ -0,0 +1,165 @@
+//! Group membership of shared resources
+//!
+//! The manifest is stored as TOML in the shared storage of a repository:
+//!
+//! ```toml
+//! [groups]
+//! ".env.app" = "app"
+//!
+//! [worktrees]
+//! feature-x = ["app"]
+//! ```
+//!
+//! Shared paths without a group are mounted into every worktree. Grouped
+//! paths are only mounted into worktrees assigned to that group.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::ports::FileSystem;
+use crate::errors::GrmError;
+
+/// File name of the manifest inside `{root}/.shared/{host}/{user}/{repo}`
+pub const MANIFEST_FILE: &str = ".grm-manifest.toml";
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShareManifest {
+    /// Shared path (relative to the repository root) to its group
+    #[serde(default)]
+    pub groups: BTreeMap<String, String>,
+    /// Branch name to the groups assigned to its worktree
+    #[serde(default)]
+    pub worktrees: BTreeMap<String, Vec<String>>,
+}
+
+impl ShareManifest {
+    /// Load the manifest, or an empty one if the file does not exist
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<Self, GrmError> {
+        if !fs.exists(path) {
+            return Ok(Self::default());
+        }
+
+        let content = fs.read_to_string(path)?;
+        toml::from_str(&content).map_err(|e| GrmError::InvalidManifest {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    pub fn save(&self, fs: &dyn FileSystem, path: &Path) -> Result<(), GrmError> {
+        let content = toml::to_string(self).map_err(|e| GrmError::InvalidManifest {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        fs.write(path, &content)?;
+        Ok(())
+    }
+
+    /// Put a shared path into a group
+    pub fn set_group(&mut self, relative_path: &Path, group: &str) {
+        self.groups.insert(
+            relative_path.to_string_lossy().to_string(),
+            group.to_string(),
+        );
+    }
+
+    /// Add groups to the worktree of `branch`
+    pub fn assign(&mut self, branch: &str, groups: &[String]) {
+        let assigned = self.worktrees.entry(branch.to_string()).or_default();
+        for group in groups {
+            if !assigned.contains(group) {
+                assigned.push(group.clone());
+            }
+        }
+    }
+
+    /// Group of a path, inherited from the closest grouped ancestor
+    pub fn group_of(&self, relative_path: &Path) -> Option<&str> {
+        relative_path
+            .ancestors()
+            .find_map(|ancestor| self.groups.get(ancestor.to_string_lossy().as_ref()))
+            .map(String::as_str)
+    }
+
+    /// Check if the worktree of `branch` belongs to `group`
+    pub fn has_group(&self, branch: Option<&str>, group: &str) -> bool {
+        branch
+            .and_then(|b| self.worktrees.get(b))
+            .is_some_and(|groups| groups.iter().any(|g| g == group))
+    }
+
+    /// Check if a shared path should be mounted into the worktree of `branch`
+    pub fn is_mounted(&self, relative_path: &Path, branch: Option<&str>) -> bool {
+        self.group_of(relative_path)
+            .is_none_or(|group| self.has_group(branch, group))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::MockFileSystem;
+
+    #[test]
+    fn test_load_missing_manifest_is_empty() {
+        let fs = MockFileSystem::new();
+
+        let manifest = ShareManifest::load(&fs, Path::new("/shared/.grm-manifest.toml")).unwrap();
+
+        assert_eq!(manifest, ShareManifest::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/shared/.grm-manifest.toml");
+        let mut manifest = ShareManifest::default();
+        manifest.set_group(Path::new(".env.app"), "app");
+        manifest.assign("feature-x", &["app".to_string()]);
+
+        manifest.save(&fs, path).unwrap();
+        let loaded = ShareManifest::load(&fs, path).unwrap();
+
+        assert_eq!(loaded, manifest);
+    }
+
+    #[test]
+    fn test_load_invalid_manifest() {
+        let fs = MockFileSystem::new();
+        let path = Path::new("/shared/.grm-manifest.toml");
+        fs.add_file_with_content(path, "groups = 1");
+
+        let result = ShareManifest::load(&fs, path);
+
+        assert!(matches!(result, Err(GrmError::InvalidManifest { .. })));
+    }
+
+    #[test]
+    fn test_is_mounted_mixed_groups() {
+        let mut manifest = ShareManifest::default();
+        manifest.set_group(Path::new(".env.app"), "app");
+        manifest.set_group(Path::new("infra"), "infra");
+        manifest.assign("feature-x", &["app".to_string()]);
+
+        // ungrouped paths are mounted everywhere
+        assert!(manifest.is_mounted(Path::new("config.json"), Some("feature-x")));
+        assert!(manifest.is_mounted(Path::new("config.json"), None));
+        // grouped paths only where the group is assigned
+        assert!(manifest.is_mounted(Path::new(".env.app"), Some("feature-x")));
+        assert!(!manifest.is_mounted(Path::new(".env.app"), Some("main")));
+        // nested paths inherit the group of their directory
+        assert!(!manifest.is_mounted(Path::new("infra/.env"), Some("feature-x")));
+    }
+
+    #[test]
+    fn test_assign_does_not_duplicate() {
+        let mut manifest = ShareManifest::default();
+
+        manifest.assign("main", &["app".to_string()]);
+        manifest.assign("main", &["app".to_string(), "infra".to_string()]);
+
+        assert_eq!(manifest.worktrees["main"], vec!["app", "infra"]);
+    }
+}
@@ -4,15 +4,42 @@ use std::{
 };
 
 use crate::{
-    core::{RepoInfo, RepoScanner, ports::FileSystem},
+    core::{
+        Layout, RepoInfo, RepoScanner,
+        mount_manifest::MountManifest,
+        ports::FileSystem,
+        share_manifest::{MANIFEST_FILE, ShareManifest},
+    },
     errors::GrmError,
 };
 
+/// How `SharedResource::share` puts a resource into shared storage
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyMode {
+    /// Move the original, which disappears from the worktree until it is linked
+    #[default]
+    Move,
+    /// Copy the original, which stays readable until the symlink replaces it
+    Copy,
+}
+
+/// Outcome of `SharedResource::unshare`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct UnshareResult {
+    /// Number of symlinks removed
+    pub removed: usize,
+    /// Worktrees holding a real file or directory instead of the symlink, left untouched
+    pub diverged: Vec<PathBuf>,
+    /// Worktrees holding a symlink which points somewhere else than shared storage, left untouched
+    pub foreign: Vec<PathBuf>,
+}
+
 pub struct SharedResource {
     repo_info: RepoInfo,
     fs: Arc<dyn FileSystem>,
     scanner: RepoScanner,
     root: PathBuf,
+    layout: Layout,
 }
 
 impl SharedResource {
@@ -23,14 +50,24 @@ impl SharedResource {
             fs,
             scanner,
             root,
+            layout: Layout::default(),
         }
     }
 
+    /// Find worktrees and read their branch with `layout` instead of the default one
+    #[must_use]
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(layout.clone());
+        self.layout = layout;
+        self
+    }
+
     /// Check if a file or directory has conflicts in other worktrees
     ///
     /// # Arguments
     /// * `repo_root` - The root directory of the repository
     /// * `relative_path` - Path relative to the repository root
+    /// * `group` - Only worktrees assigned to this group are considered
     ///
     /// # Returns
     /// * `Ok(Vec<PathBuf>)` - List of conflicting paths in other worktrees
@@ -39,27 +76,26 @@ impl SharedResource {
         &self,
         repo_root: &Path,
         relative_path: &Path,
+        group: Option<&str>,
     ) -> Result<Vec<PathBuf>, GrmError> {
-        let current_dir = self.fs.current_dir()?;
-        let file = self.fs.normalize(relative_path, &current_dir)?;
-        let repo_relative_path = file
-            .strip_prefix(repo_root)
-            .map_err(|e| GrmError::NotFound(format!("{e}")))?;
+        let (_, repo_relative_path) = self.resolve(repo_root, relative_path)?;
 
         let shared_path = self
             .repo_info
-            .build_shared_path(&self.root, repo_relative_path);
+            .build_shared_path(&self.root, &repo_relative_path);
         if !self.fs.exists(&shared_path) {
             return Ok(Vec::new());
         }
 
         let mut conflicts = Vec::new();
-        let worktrees = self.scanner.scan_worktrees(&self.root, &self.repo_info)?;
+        let worktrees = self.link_targets(repo_root, group)?;
         for worktree in &worktrees {
-            let target_in_worktree = worktree.join(repo_relative_path);
-            if file == target_in_worktree {
+            // The source worktree may be reached through a symlink, so its
+            // path can differ textually from the one found by the scanner
+            if self.is_same_dir(worktree, repo_root) {
                 continue;
             }
+            let target_in_worktree = worktree.join(&repo_relative_path);
             if self.fs.exists(&target_in_worktree) || self.fs.is_symlink(&target_in_worktree) {
                 conflicts.push(target_in_worktree);
             }
@@ -68,12 +104,37 @@ impl SharedResource {
         Ok(conflicts)
     }
 
+    /// Resolve a path given on the command line against the current directory
+    ///
+    /// # Returns
+    /// The absolute path and the path relative to `repo_root`
+    fn resolve(&self, repo_root: &Path, path: &Path) -> Result<(PathBuf, PathBuf), GrmError> {
+        let current_dir = self.fs.current_dir()?;
+        let file = self.fs.normalize(path, &current_dir)?;
+        let repo_relative_path = file
+            .strip_prefix(repo_root)
+            .map_err(|_| GrmError::OutsideRepository {
+                path: path.display().to_string(),
+                repo_root: repo_root.display().to_string(),
+            })?
+            .to_path_buf();
+
+        Ok((file, repo_relative_path))
+    }
+
     /// Mount a shared file or directory for new worktrees
     ///
+    /// Grouped entries are only mounted if the worktree is assigned to their group.
+    /// If the worktree has a `.grm-shared` manifest, only the paths it lists are mounted.
+    ///
     /// # Arguments
     /// * `repo_root` - The root directory for managed repositories
-    pub fn mount(&self, repo_root: &Path) -> Result<(), GrmError> {
+    ///
+    /// # Returns
+    /// The number of symlinks created
+    pub fn mount(&self, repo_root: &Path) -> Result<usize, GrmError> {
         let shared_root = self.repo_info.build_shared_path(&self.root, Path::new(""));
+        let manifest_path = shared_root.join(MANIFEST_FILE);
 
         if !self.fs.exists(&shared_root) {
             return Err(GrmError::NotFound(format!(
@@ -82,17 +143,42 @@ impl SharedResource {
             )));
         }
 
+        let manifest = ShareManifest::load(self.fs.as_ref(), &manifest_path)?;
+        let mount_manifest = MountManifest::load(self.fs.as_ref(), repo_root)?;
+        let branch =
+            RepoInfo::from_path_resolved(self.fs.as_ref(), &self.root, repo_root, &self.layout)
+                .ok()
+                .and_then(|info| info.branch);
+
+        let mut linked = 0;
         let mut queue = vec![shared_root.clone()];
         while let Some(current_dir) = queue.pop() {
             for entry in self.fs.read_dir(&current_dir)? {
-                if self.fs.is_dir(&entry) {
-                    self.fs.create_dir(&repo_root.join(&entry))?;
+                if entry == manifest_path {
+                    continue;
+                }
+
+                let relative_path = entry
+                    .strip_prefix(&shared_root)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+                check_shareable(relative_path)?;
+                if !manifest.is_mounted(relative_path, branch.as_deref()) {
+                    continue;
+                }
+
+                let is_dir = self.fs.is_dir(&entry);
+                if let Some(mount_manifest) = &mount_manifest {
+                    let selected = mount_manifest.includes(relative_path)
+                        || (is_dir && mount_manifest.leads_to(relative_path));
+                    if !selected {
+                        continue;
+                    }
+                }
+
+                if is_dir {
+                    self.fs.create_dir(&repo_root.join(relative_path))?;
                     queue.push(entry);
                 } else {
-                    let relative_path = entry
-                        .strip_prefix(&shared_root)
-                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-
                     let target_path = repo_root.join(relative_path);
 
                     if self.fs.exists(&target_path) || self.fs.is_symlink(&target_path) {
@@ -100,10 +186,11 @@ impl SharedResource {
                     }
 
                     self.fs.create_symlink(&entry, &target_path)?;
+                    linked += 1;
                 }
             }
         }
-        Ok(())
+        Ok(linked)
     }
 
     /// Share a file or directory across all worktrees
@@ -111,19 +198,24 @@ impl SharedResource {
     /// # Arguments
     /// * `repo_root` - The root directory for managed repositories
     /// * `repo_relative_path` - Path relative to the repository root
+    /// * `group` - Only link into worktrees assigned to this group
+    /// * `mode` - Whether the original is moved or copied into shared storage
     ///
     /// # Returns
     /// * `Ok(())` - Successfully shared the resource
     /// * `Err(GrmError)` - If sharing fails
-    pub fn share(&self, repo_root: &Path, relative_path: &Path) -> Result<(), GrmError> {
-        let current_dir = self.fs.current_dir()?;
-        let file = self.fs.normalize(relative_path, &current_dir)?;
-        let repo_relative_path = file
-            .strip_prefix(repo_root)
-            .map_err(|e| GrmError::NotFound(format!("{e}")))?;
+    pub fn share(
+        &self,
+        repo_root: &Path,
+        relative_path: &Path,
+        group: Option<&str>,
+        mode: CopyMode,
+    ) -> Result<(), GrmError> {
+        let (file, repo_relative_path) = self.resolve(repo_root, relative_path)?;
+        check_shareable(&repo_relative_path)?;
         let shared_path = self
             .repo_info
-            .build_shared_path(&self.root, repo_relative_path);
+            .build_shared_path(&self.root, &repo_relative_path);
 
         if !self.fs.exists(&file) {
             return Err(GrmError::NotFound(format!(
@@ -132,12 +224,33 @@ impl SharedResource {
             )));
         }
 
+        if let Some(group) = group {
+            let manifest_path = self
+                .repo_info
+                .build_shared_path(&self.root, Path::new(MANIFEST_FILE));
+            let mut manifest = ShareManifest::load(self.fs.as_ref(), &manifest_path)?;
+            manifest.set_group(&repo_relative_path, group);
+            // The worktree the resource comes from keeps it
+            if let Some(branch) =
+                RepoInfo::from_path_resolved(self.fs.as_ref(), &self.root, repo_root, &self.layout)
+                    .ok()
+                    .and_then(|info| info.branch)
+            {
+                manifest.assign(&branch, &[group.to_string()]);
+            }
+
+            if let Some(parent) = manifest_path.parent() {
+                self.fs.create_dir(parent)?;
+            }
+            manifest.save(self.fs.as_ref(), &manifest_path)?;
+        }
+
         // Check if already shared
         if self.fs.is_symlink(&file) {
             return Ok(());
         }
 
-        // Move the file to shared storage if it's not a symlink
+        // Put the file into shared storage if it's not a symlink
         if let Some(parent) = shared_path.parent() {
             self.fs.create_dir(parent)?;
         }
@@ -146,23 +259,156 @@ impl SharedResource {
             self.fs.remove(&shared_path)?;
         }
 
-        self.fs.rename(&file, &shared_path)?;
-        let worktrees = self.scanner.scan_worktrees(&self.root, &self.repo_info)?;
+        match mode {
+            CopyMode::Move => self.fs.move_path(&file, &shared_path)?,
+            // The original is replaced by its symlink below, like any other worktree's copy
+            CopyMode::Copy => self.fs.copy(&file, &shared_path)?,
+        }
+        let mut worktrees = self.link_targets(repo_root, group)?;
+        // Link the source worktree first, as the resource is gone from it until then
+        worktrees.sort_by_key(|worktree| worktree != repo_root);
 
         // Create symlinks in all worktrees
-        for worktree in &worktrees {
-            let target_in_worktree = worktree.join(relative_path);
-
-            if self.fs.exists(&target_in_worktree) || self.fs.is_symlink(&target_in_worktree) {
-                self.fs.remove(&target_in_worktree)?;
+        for (linked, worktree) in worktrees.iter().enumerate() {
+            if let Err(e) = self.link(&shared_path, &worktree.join(&repo_relative_path)) {
+                self.restore_source(&shared_path, &file);
+                return Err(GrmError::PartialShare {
+                    path: repo_relative_path.display().to_string(),
+                    linked,
+                    total: worktrees.len(),
+                    source: Box::new(e),
+                });
             }
+        }
+
+        Ok(())
+    }
 
-            self.fs.create_symlink(&shared_path, &target_in_worktree)?;
+    /// Replace whatever is at `target_in_worktree` with a symlink to `shared_path`
+    fn link(&self, shared_path: &Path, target_in_worktree: &Path) -> Result<(), GrmError> {
+        if self.fs.exists(target_in_worktree) || self.fs.is_symlink(target_in_worktree) {
+            self.fs.remove(target_in_worktree)?;
         }
 
+        self.fs.create_symlink(shared_path, target_in_worktree)?;
         Ok(())
     }
 
+    /// Best effort to give the source worktree its resource back after a failed `share`
+    ///
+    /// Falls back to a copy of the shared storage if the symlink cannot be created.
+    fn restore_source(&self, shared_path: &Path, file: &Path) {
+        if self.fs.exists(file) || self.fs.is_symlink(file) {
+            return;
+        }
+
+        if self.fs.create_symlink(shared_path, file).is_err() {
+            let _ = self.fs.copy(shared_path, file);
+        }
+    }
+
+    /// Assign groups to the worktree of `branch`
+    ///
+    /// The assignment is recorded even before anything is shared, so later
+    /// `share` calls for these groups reach the worktree.
+    pub fn assign_groups(&self, branch: &str, groups: &[String]) -> Result<(), GrmError> {
+        let shared_root = self.repo_info.build_shared_path(&self.root, Path::new(""));
+        let manifest_path = shared_root.join(MANIFEST_FILE);
+
+        let mut manifest = ShareManifest::load(self.fs.as_ref(), &manifest_path)?;
+        manifest.assign(branch, groups);
+
+        self.fs.create_dir(&shared_root)?;
+        manifest.save(self.fs.as_ref(), &manifest_path)
+    }
+
+    /// List the files in shared storage with their group
+    ///
+    /// # Returns
+    /// * `Ok(Vec<(PathBuf, Option<String>)>)` - Paths relative to the repository root, sorted
+    /// * `Err(GrmError)` - If the shared storage cannot be read
+    pub fn list(&self) -> Result<Vec<(PathBuf, Option<String>)>, GrmError> {
+        let shared_root = self.repo_info.build_shared_path(&self.root, Path::new(""));
+        let manifest_path = shared_root.join(MANIFEST_FILE);
+
+        if !self.fs.exists(&shared_root) {
+            return Ok(Vec::new());
+        }
+
+        let manifest = ShareManifest::load(self.fs.as_ref(), &manifest_path)?;
+
+        let mut entries = Vec::new();
+        let mut queue = vec![shared_root.clone()];
+        while let Some(current_dir) = queue.pop() {
+            for entry in self.fs.read_dir(&current_dir)? {
+                if entry == manifest_path {
+                    continue;
+                }
+
+                if self.fs.is_dir(&entry) {
+                    queue.push(entry);
+                } else {
+                    let relative_path = entry
+                        .strip_prefix(&shared_root)
+                        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+                        .to_path_buf();
+                    let group = manifest.group_of(&relative_path).map(str::to_string);
+                    entries.push((relative_path, group));
+                }
+            }
+        }
+
+        entries.sort();
+        Ok(entries)
+    }
+
+    /// Worktrees which receive a link when sharing into `group`
+    ///
+    /// Without a group every worktree does. With a group, the worktree at
+    /// `repo_root` and the worktrees assigned to the group do.
+    fn link_targets(
+        &self,
+        repo_root: &Path,
+        group: Option<&str>,
+    ) -> Result<Vec<PathBuf>, GrmError> {
+        let worktrees = self.scanner.scan_worktrees(&self.root, &self.repo_info)?;
+        let Some(group) = group else {
+            return Ok(worktrees);
+        };
+
+        let manifest_path = self
+            .repo_info
+            .build_shared_path(&self.root, Path::new(MANIFEST_FILE));
+        let manifest = ShareManifest::load(self.fs.as_ref(), &manifest_path)?;
+
+        Ok(worktrees
+            .into_iter()
+            .filter(|worktree| {
+                self.is_same_dir(worktree, repo_root) || {
+                    let branch = RepoInfo::from_path_resolved(
+                        self.fs.as_ref(),
+                        &self.root,
+                        worktree,
+                        &self.layout,
+                    )
+                    .ok()
+                    .and_then(|info| info.branch);
+                    manifest.has_group(branch.as_deref(), group)
+                }
+            })
+            .collect())
+    }
+
+    /// Whether `a` and `b` are the same directory once symlinks are resolved
+    ///
+    /// Paths which cannot be resolved are compared as they are.
+    fn is_same_dir(&self, a: &Path, b: &Path) -> bool {
+        match (self.fs.canonicalize(a), self.fs.canonicalize(b)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => a == b,
+        }
+    }
+
     /// Unshare a file or directory from worktrees
     ///
     /// # Arguments
@@ -170,33 +416,47 @@ impl SharedResource {
     /// * `repo_relative_path` - Path relative to the repository root
     ///
     /// # Returns
-    /// * `Ok(usize)` - Number of symlinks removed
+    /// * `Ok(UnshareResult)` - Removed symlink count and worktrees with diverged copies
     /// * `Err(GrmError)` - If unsharing fails
-    pub fn unshare(&self, repo_root: &Path, relative_path: &Path) -> Result<usize, GrmError> {
-        let current_dir = self.fs.current_dir()?;
-        let file = self.fs.normalize(relative_path, &current_dir)?;
-
-        let repo_relative_path = file
-            .strip_prefix(repo_root)
-            .map_err(|e| GrmError::NotFound(format!("{e}")))?;
+    pub fn unshare(
+        &self,
+        repo_root: &Path,
+        relative_path: &Path,
+    ) -> Result<UnshareResult, GrmError> {
+        let (_, repo_relative_path) = self.resolve(repo_root, relative_path)?;
+        let shared_path = self
+            .repo_info
+            .build_shared_path(&self.root, &repo_relative_path);
 
-        let mut removed_count = 0;
+        let mut result = UnshareResult::default();
 
         let worktrees = self.scanner.scan_worktrees(&self.root, &self.repo_info)?;
         for worktree in &worktrees {
-            let target_in_worktree = worktree.join(repo_relative_path);
+            let target_in_worktree = worktree.join(&repo_relative_path);
 
             if !self.fs.exists(&target_in_worktree) && !self.fs.is_symlink(&target_in_worktree) {
                 continue;
             }
 
             if self.fs.is_symlink(&target_in_worktree) {
-                self.fs.remove(&target_in_worktree)?;
-                removed_count += 1;
+                // Only links made by `share`, a link of the user's own is kept
+                let points_to_shared = self
+                    .fs
+                    .read_link(&target_in_worktree)
+                    .is_ok_and(|target| target == shared_path);
+                if points_to_shared {
+                    self.fs.remove(&target_in_worktree)?;
+                    result.removed += 1;
+                } else {
+                    result.foreign.push(worktree.clone());
+                }
+            } else {
+                // The symlink was replaced, e.g. by an editor or `isolate`
+                result.diverged.push(worktree.clone());
             }
         }
 
-        Ok(removed_count)
+        Ok(result)
     }
 
     /// Isolate a shared file/directory in a specific worktree
@@ -210,16 +470,12 @@ impl SharedResource {
     /// * `Ok(())` - Successfully isolated the resource
     /// * `Err(GrmError)` - If isolation fails
     pub fn isolate(&self, repo_root: &Path, relative_path: &Path) -> Result<(), GrmError> {
-        let current_dir = self.fs.current_dir()?;
-        let file = self.fs.normalize(relative_path, &current_dir)?;
-        let repo_relative_path = file
-            .strip_prefix(repo_root)
-            .map_err(|e| GrmError::NotFound(format!("{e}")))?;
+        let (_, repo_relative_path) = self.resolve(repo_root, relative_path)?;
 
         let shared_path = self
             .repo_info
-            .build_shared_path(&self.root, repo_relative_path);
-        let absolute_target_path = repo_root.join(repo_relative_path);
+            .build_shared_path(&self.root, &repo_relative_path);
+        let absolute_target_path = repo_root.join(&repo_relative_path);
 
         if !self.fs.exists(&absolute_target_path) {
             return Err(GrmError::NotFound(format!(
@@ -244,12 +500,81 @@ impl SharedResource {
 
         Ok(())
     }
+
+    /// Isolate everything the worktree at `repo_root` links from shared storage
+    ///
+    /// Shared storage is walked, and each worktree path which is a symlink to
+    /// its entry is replaced by a copy. Symlinks pointing anywhere else are kept.
+    ///
+    /// # Returns
+    /// The isolated paths relative to `repo_root`, sorted
+    pub fn isolate_all(&self, repo_root: &Path) -> Result<Vec<PathBuf>, GrmError> {
+        let shared_root = self.repo_info.build_shared_path(&self.root, Path::new(""));
+        let manifest_path = shared_root.join(MANIFEST_FILE);
+
+        if !self.fs.exists(&shared_root) {
+            return Ok(Vec::new());
+        }
+
+        let mut isolated = Vec::new();
+        let mut queue = vec![shared_root.clone()];
+        while let Some(current_dir) = queue.pop() {
+            for entry in self.fs.read_dir(&current_dir)? {
+                if entry == manifest_path {
+                    continue;
+                }
+
+                let relative_path = entry
+                    .strip_prefix(&shared_root)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?
+                    .to_path_buf();
+                let target_path = repo_root.join(&relative_path);
+
+                let links_entry = self.fs.is_symlink(&target_path)
+                    && self
+                        .fs
+                        .read_link(&target_path)
+                        .is_ok_and(|target| target == entry);
+                if links_entry {
+                    self.fs.remove(&target_path)?;
+                    self.fs.copy(&entry, &target_path)?;
+                    isolated.push(relative_path);
+                } else if self.fs.is_dir(&entry) && !self.fs.is_symlink(&target_path) {
+                    // Mounted directories are real, with the links inside them
+                    queue.push(entry);
+                }
+            }
+        }
+
+        isolated.sort();
+        Ok(isolated)
+    }
+}
+
+/// Refuse paths whose sharing would break the worktree itself
+///
+/// Moving the worktree root or anything under `.git` into shared storage
+/// would take the repository away from git.
+fn check_shareable(repo_relative_path: &Path) -> Result<(), GrmError> {
+    let protected = |reason: &str| {
+        Err(GrmError::ProtectedPath {
+            path: repo_relative_path.display().to_string(),
+            reason: reason.to_string(),
+        })
+    };
+
+    match repo_relative_path.components().next() {
+        None => protected("it is the repository root"),
+        Some(first) if first.as_os_str() == ".git" => protected("it belongs to the git directory"),
+        Some(_) => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::adapters::test_helpers::MockFileSystem;
+    use crate::core::ports::FileSystemError;
 
     fn setup() -> (Arc<MockFileSystem>, RepoInfo, PathBuf) {
         let fs = Arc::new(MockFileSystem::new());
@@ -267,17 +592,17 @@ mod tests {
         let (fs, repo_info, root) = setup();
 
         // ワークツリーとファイルの準備
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
-        fs.add_git_repo(&root.join("github.com/user/repo+feature"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
 
         let repo_root = root.join("github.com/user/repo+main");
-        fs.add_file(&repo_root.join("config.json"));
+        fs.add_file(repo_root.join("config.json"));
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
-        let result = shared.share(&repo_root, Path::new("config.json"));
+        let result = shared.share(&repo_root, Path::new("config.json"), None, CopyMode::Move);
 
         assert!(result.is_ok(), "share failed: {:?}", result.err());
 
@@ -290,6 +615,142 @@ mod tests {
         assert!(fs.is_symlink(&root.join("github.com/user/repo+feature/config.json")));
     }
 
+    #[test]
+    fn test_share_copy_mode() {
+        // 目的: コピーによる共有
+        // 検証: 内容が共有ストレージにコピーされ、元のファイルはシンボリックリンクに置き換わる
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_file_with_content(repo_root.join("config.json"), "{}");
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.share(&repo_root, Path::new("config.json"), None, CopyMode::Copy);
+
+        assert!(result.is_ok(), "share failed: {:?}", result.err());
+
+        let shared_path = root.join(".shared/github.com/user/repo/config.json");
+        assert_eq!(fs.read_to_string(&shared_path).unwrap(), "{}");
+        assert_eq!(
+            fs.read_link(&repo_root.join("config.json")).unwrap(),
+            shared_path
+        );
+        assert!(fs.is_symlink(&root.join("github.com/user/repo+feature/config.json")));
+    }
+
+    #[test]
+    fn test_share_across_devices() {
+        // 目的: 共有ストレージが別のファイルシステムにある場合の共有
+        // 検証: rename が EXDEV で失敗してもコピーと削除で移動され、各ワークツリーにリンクが作成される
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.create_dir(&repo_root.join("config")).unwrap();
+        fs.add_file_with_content(repo_root.join("config/local.json"), "{}");
+        fs.set_current_dir(&repo_root);
+        // Resolving the path and creating the shared storage directory succeed, moving into it does not
+        fs.inject_error_after(
+            3,
+            FileSystemError::Io(std::io::Error::from(std::io::ErrorKind::CrossesDevices)),
+        );
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.share(&repo_root, Path::new("config"), None, CopyMode::Move);
+
+        assert!(result.is_ok(), "share failed: {:?}", result.err());
+        let shared_path = root.join(".shared/github.com/user/repo/config");
+        assert_eq!(
+            fs.read_to_string(&shared_path.join("local.json")).unwrap(),
+            "{}"
+        );
+        assert_eq!(
+            fs.read_link(&repo_root.join("config")).unwrap(),
+            shared_path
+        );
+        assert!(fs.is_symlink(&root.join("github.com/user/repo+feature/config")));
+    }
+
+    fn permission_denied() -> FileSystemError {
+        FileSystemError::Io(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+    }
+
+    #[test]
+    fn test_share_partial_failure_keeps_source_linked() {
+        // 目的: 一部のワークツリーでリンク作成に失敗した場合の共有
+        // 検証: リンク数を含むエラーが返り、元のワークツリーは共有ストレージを参照したまま残る
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+dev"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_file_with_content(repo_root.join("config.json"), "{}");
+        fs.set_current_dir(&repo_root);
+        fs.inject_symlink_error_after(1, permission_denied());
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.share(&repo_root, Path::new("config.json"), None, CopyMode::Move);
+
+        assert!(matches!(
+            result,
+            Err(GrmError::PartialShare {
+                linked: 1,
+                total: 3,
+                ..
+            })
+        ));
+        let shared_path = root.join(".shared/github.com/user/repo/config.json");
+        assert_eq!(fs.read_to_string(&shared_path).unwrap(), "{}");
+        assert_eq!(
+            fs.read_link(&repo_root.join("config.json")).unwrap(),
+            shared_path
+        );
+    }
+
+    #[test]
+    fn test_share_source_link_failure_restores_copy() {
+        // 目的: 元のワークツリーでリンク作成に失敗した場合の共有
+        // 検証: 共有ストレージの内容が元のワークツリーにコピーで戻される
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_file_with_content(repo_root.join("config.json"), "{}");
+        fs.set_current_dir(&repo_root);
+        for _ in 0..2 {
+            fs.inject_symlink_error_after(0, permission_denied());
+        }
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.share(&repo_root, Path::new("config.json"), None, CopyMode::Move);
+
+        assert!(matches!(
+            result,
+            Err(GrmError::PartialShare { linked: 0, .. })
+        ));
+        let source = repo_root.join("config.json");
+        assert!(!fs.is_symlink(&source));
+        assert_eq!(fs.read_to_string(&source).unwrap(), "{}");
+    }
+
     #[test]
     fn test_share_directory_success() {
         // 目的: ディレクトリ共有
@@ -297,17 +758,17 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
 
         let repo_root = root.join("github.com/user/repo+main");
-        fs.add_dir(&repo_root.join("shared_dir"));
-        fs.add_file(&repo_root.join("shared_dir/file.txt"));
+        fs.add_dir(repo_root.join("shared_dir"));
+        fs.add_file(repo_root.join("shared_dir/file.txt"));
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
-        let result = shared.share(&repo_root, Path::new("shared_dir"));
+        let result = shared.share(&repo_root, Path::new("shared_dir"), None, CopyMode::Move);
 
         assert!(result.is_ok());
 
@@ -322,35 +783,118 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
-        fs.add_git_repo(&root.join("github.com/user/repo+feature"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
 
         let repo_root = root.join("github.com/user/repo+main");
         let shared_file = root.join(".shared/github.com/user/repo/config.json");
-        fs.add_dir(&root.join(".shared"));
-        fs.add_dir(&root.join(".shared/github.com"));
-        fs.add_dir(&root.join(".shared/github.com/user"));
-        fs.add_dir(&root.join(".shared/github.com/user/repo"));
+        fs.add_dir(root.join(".shared"));
+        fs.add_dir(root.join(".shared/github.com"));
+        fs.add_dir(root.join(".shared/github.com/user"));
+        fs.add_dir(root.join(".shared/github.com/user/repo"));
         fs.add_file(&shared_file);
 
         // シンボリックリンクを作成
-        fs.add_symlink(&repo_root.join("config.json"), &shared_file);
-        fs.add_symlink(&root.join("github.com/user/repo+feature/config.json"), &shared_file);
+        fs.add_symlink(repo_root.join("config.json"), &shared_file);
+        fs.add_symlink(
+            root.join("github.com/user/repo+feature/config.json"),
+            &shared_file,
+        );
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
         let result = shared.unshare(&repo_root, Path::new("config.json"));
 
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), 2);
+        assert_eq!(result.unwrap().removed, 2);
 
         // シンボリックリンクが削除されている
         assert!(!fs.exists(&repo_root.join("config.json")));
         assert!(!fs.exists(&root.join("github.com/user/repo+feature/config.json")));
     }
 
+    #[test]
+    fn test_unshare_reports_diverged_worktrees() {
+        // 目的: シンボリックリンクが実体ファイルに置き換えられたワークツリーの検出
+        // 検証: リンクのみ削除され、実体ファイルを持つワークツリーが報告される
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        let feature = root.join("github.com/user/repo+feature");
+        let shared_file = root.join(".shared/github.com/user/repo/config.json");
+        fs.create_dir(shared_file.parent().unwrap()).unwrap();
+        fs.add_file(&shared_file);
+        fs.add_symlink(repo_root.join("config.json"), &shared_file);
+        fs.add_file_with_content(feature.join("config.json"), "edited");
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared
+            .unshare(&repo_root, Path::new("config.json"))
+            .unwrap();
+
+        assert_eq!(
+            result,
+            UnshareResult {
+                removed: 1,
+                diverged: vec![feature.clone()],
+                foreign: Vec::new(),
+            }
+        );
+        assert!(!fs.exists(&repo_root.join("config.json")));
+        assert_eq!(
+            fs.read_to_string(&feature.join("config.json")).unwrap(),
+            "edited"
+        );
+    }
+
+    #[test]
+    fn test_unshare_keeps_foreign_symlink() {
+        // 目的: 共有ストレージ以外を指すシンボリックリンクの扱い
+        // 検証: 共有ストレージを指すリンクのみ削除され、ユーザーが作成したリンクは残される
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        let feature = root.join("github.com/user/repo+feature");
+        let shared_file = root.join(".shared/github.com/user/repo/config.json");
+        fs.create_dir(shared_file.parent().unwrap()).unwrap();
+        fs.add_file(&shared_file);
+        fs.add_file("/home/testuser/config.json");
+        fs.add_symlink(repo_root.join("config.json"), &shared_file);
+        fs.add_symlink(feature.join("config.json"), "/home/testuser/config.json");
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared
+            .unshare(&repo_root, Path::new("config.json"))
+            .unwrap();
+
+        assert_eq!(
+            result,
+            UnshareResult {
+                removed: 1,
+                diverged: Vec::new(),
+                foreign: vec![feature.clone()],
+            }
+        );
+        assert_eq!(
+            fs.read_link(&feature.join("config.json")).unwrap(),
+            PathBuf::from("/home/testuser/config.json")
+        );
+    }
+
     #[test]
     fn test_isolate_success() {
         // 目的: シンボリックリンクを実体ファイルに置換
@@ -358,18 +902,18 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
 
         let repo_root = root.join("github.com/user/repo+main");
         let shared_file = root.join(".shared/github.com/user/repo/config.json");
-        fs.add_dir(&root.join(".shared"));
-        fs.add_dir(&root.join(".shared/github.com"));
-        fs.add_dir(&root.join(".shared/github.com/user"));
-        fs.add_dir(&root.join(".shared/github.com/user/repo"));
+        fs.add_dir(root.join(".shared"));
+        fs.add_dir(root.join(".shared/github.com"));
+        fs.add_dir(root.join(".shared/github.com/user"));
+        fs.add_dir(root.join(".shared/github.com/user/repo"));
         fs.add_file(&shared_file);
-        fs.add_symlink(&repo_root.join("config.json"), &shared_file);
+        fs.add_symlink(repo_root.join("config.json"), &shared_file);
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
@@ -382,6 +926,92 @@ mod tests {
         assert!(fs.exists(&repo_root.join("config.json")));
     }
 
+    #[test]
+    fn test_isolate_all_skips_foreign_symlinks() {
+        // 目的: ワークツリー内の共有ファイルの一括実体化
+        // 検証: 共有ストレージへのリンクだけが実体化され、他を指すリンクはそのまま残る
+
+        let (fs, repo_info, root) = setup();
+
+        let repo_root = root.join("github.com/user/repo+main");
+        let shared_root = root.join(".shared/github.com/user/repo");
+        fs.create_dir(&repo_root.join("config")).unwrap();
+        fs.add_git_repo(&repo_root);
+        fs.create_dir(&shared_root.join("config")).unwrap();
+        fs.add_file_with_content(shared_root.join(".env"), "TOKEN=1");
+        fs.add_file_with_content(shared_root.join("config/app.toml"), "port = 80");
+        fs.add_file(shared_root.join(".env.local"));
+        fs.add_symlink(repo_root.join(".env"), shared_root.join(".env"));
+        fs.add_symlink(
+            repo_root.join("config/app.toml"),
+            shared_root.join("config/app.toml"),
+        );
+        fs.add_symlink(repo_root.join(".env.local"), "/home/testuser/.env.local");
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let isolated = shared.isolate_all(&repo_root).unwrap();
+
+        assert_eq!(
+            isolated,
+            vec![PathBuf::from(".env"), PathBuf::from("config/app.toml")]
+        );
+        assert!(!fs.is_symlink(&repo_root.join(".env")));
+        assert_eq!(
+            fs.read_to_string(&repo_root.join(".env")).unwrap(),
+            "TOKEN=1"
+        );
+        assert!(!fs.is_symlink(&repo_root.join("config/app.toml")));
+        assert_eq!(
+            fs.read_link(&repo_root.join(".env.local")).unwrap(),
+            PathBuf::from("/home/testuser/.env.local")
+        );
+    }
+
+    #[test]
+    fn test_isolate_directory_with_symlinks() {
+        // 目的: シンボリックリンクを含むディレクトリの実体化
+        // 検証: 通常ファイルは複製され、内部のシンボリックリンクはリンクのまま保たれる
+
+        let (fs, repo_info, root) = setup();
+
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        let shared_dir = root.join(".shared/github.com/user/repo/config");
+        fs.create_dir(&shared_dir.join("nested")).unwrap();
+        fs.add_file_with_content(shared_dir.join("app.toml"), "app");
+        fs.add_file_with_content(shared_dir.join("nested/db.toml"), "db");
+        fs.add_symlink(shared_dir.join("current.toml"), "app.toml");
+        fs.add_symlink(shared_dir.join("nested/secrets"), "/etc/secrets");
+        fs.add_symlink(repo_root.join("config"), &shared_dir);
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.isolate(&repo_root, Path::new("config"));
+
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+
+        let isolated = repo_root.join("config");
+        assert!(!fs.is_symlink(&isolated));
+        assert!(fs.is_dir(&isolated));
+        assert_eq!(
+            fs.read_to_string(&isolated.join("nested/db.toml")).unwrap(),
+            "db"
+        );
+        assert!(fs.is_symlink(&isolated.join("current.toml")));
+        assert_eq!(
+            fs.read_link(&isolated.join("current.toml")).unwrap(),
+            PathBuf::from("app.toml")
+        );
+        assert_eq!(
+            fs.read_link(&isolated.join("nested/secrets")).unwrap(),
+            PathBuf::from("/etc/secrets")
+        );
+        // 共有ストレージ側は変更されない
+        assert!(fs.is_symlink(&shared_dir.join("current.toml")));
+    }
+
     #[test]
     fn test_conflicts_detection() {
         // 目的: 他のワークツリーとの競合検出
@@ -389,24 +1019,24 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
-        fs.add_git_repo(&root.join("github.com/user/repo+feature"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
 
         let repo_root = root.join("github.com/user/repo+main");
         let shared_file = root.join(".shared/github.com/user/repo/config.json");
-        fs.add_dir(&root.join(".shared"));
-        fs.add_dir(&root.join(".shared/github.com"));
-        fs.add_dir(&root.join(".shared/github.com/user"));
-        fs.add_dir(&root.join(".shared/github.com/user/repo"));
+        fs.add_dir(root.join(".shared"));
+        fs.add_dir(root.join(".shared/github.com"));
+        fs.add_dir(root.join(".shared/github.com/user"));
+        fs.add_dir(root.join(".shared/github.com/user/repo"));
         fs.add_file(&shared_file);
-        fs.add_file(&repo_root.join("config.json"));
-        fs.add_file(&root.join("github.com/user/repo+feature/config.json"));
+        fs.add_file(repo_root.join("config.json"));
+        fs.add_file(root.join("github.com/user/repo+feature/config.json"));
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
-        let result = shared.conflicts(&repo_root, Path::new("config.json"));
+        let result = shared.conflicts(&repo_root, Path::new("config.json"), None);
 
         assert!(result.is_ok());
         let conflicts = result.unwrap();
@@ -415,6 +1045,41 @@ mod tests {
         assert!(conflicts.contains(&root.join("github.com/user/repo+feature/config.json")));
     }
 
+    #[test]
+    fn test_conflicts_source_through_symlink() {
+        // 目的: シンボリックリンク経由で開いたワークツリーからの競合検出
+        // 検証: 元のワークツリー自身のファイルは競合として扱われない
+
+        let (fs, repo_info, root) = setup();
+
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+feature"));
+        fs.add_dir("/work");
+        fs.add_symlink("/work/repo", root.join("github.com/user/repo+main"));
+
+        let repo_root = PathBuf::from("/work/repo");
+        fs.add_dir(root.join(".shared"));
+        fs.add_dir(root.join(".shared/github.com"));
+        fs.add_dir(root.join(".shared/github.com/user"));
+        fs.add_dir(root.join(".shared/github.com/user/repo"));
+        fs.add_file(root.join(".shared/github.com/user/repo/config.json"));
+        fs.add_file(root.join("github.com/user/repo+main/config.json"));
+        fs.add_file(root.join("github.com/user/repo+feature/config.json"));
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let conflicts = shared
+            .conflicts(&repo_root, Path::new("config.json"), None)
+            .unwrap();
+
+        assert_eq!(
+            conflicts,
+            vec![root.join("github.com/user/repo+feature/config.json")]
+        );
+    }
+
     #[test]
     fn test_mount_success() {
         // 目的: 共有ストレージマウント
@@ -422,28 +1087,216 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+new"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+new"));
 
         let shared_root = root.join(".shared/github.com/user/repo");
-        fs.add_dir(&root.join(".shared"));
-        fs.add_dir(&root.join(".shared/github.com"));
-        fs.add_dir(&root.join(".shared/github.com/user"));
+        fs.add_dir(root.join(".shared"));
+        fs.add_dir(root.join(".shared/github.com"));
+        fs.add_dir(root.join(".shared/github.com/user"));
         fs.add_dir(&shared_root);
-        fs.add_file(&shared_root.join("config.json"));
+        fs.add_file(shared_root.join("config.json"));
+        fs.add_dir(shared_root.join("config"));
+        fs.add_file(shared_root.join("config/local.toml"));
 
         let repo_root = root.join("github.com/user/repo+new");
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
         let result = shared.mount(&repo_root);
 
-        assert!(result.is_ok());
+        // ディレクトリは数えず、リンクしたファイルのみ数える
+        assert_eq!(result.unwrap(), 2);
 
         // シンボリックリンクが作成されている
         assert!(fs.is_symlink(&repo_root.join("config.json")));
     }
 
+    fn setup_grouped_storage(fs: &MockFileSystem, root: &Path) -> PathBuf {
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+
+        let shared_root = root.join(".shared/github.com/user/repo");
+        fs.create_dir(&shared_root).unwrap();
+        fs.add_file(shared_root.join("config.json"));
+        fs.add_file(shared_root.join(".env.app"));
+        fs.add_file(shared_root.join(".env.infra"));
+        fs.add_file_with_content(
+            shared_root.join(MANIFEST_FILE),
+            r#"
+[groups]
+".env.app" = "app"
+".env.infra" = "infra"
+
+[worktrees]
+feature-x = ["app"]
+"#,
+        );
+        shared_root
+    }
+
+    #[test]
+    fn test_mount_mixed_grouped_and_ungrouped() {
+        // 目的: グループ付きの共有ストレージマウント
+        // 検証: 割り当てられたグループとグループなしのエントリのみリンクされる
+
+        let (fs, repo_info, root) = setup();
+        setup_grouped_storage(&fs, &root);
+
+        let repo_root = root.join("github.com/user/repo+feature-x");
+        fs.add_git_repo(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.mount(&repo_root);
+
+        assert!(result.is_ok(), "mount failed: {:?}", result.err());
+        assert!(fs.is_symlink(&repo_root.join("config.json")));
+        assert!(fs.is_symlink(&repo_root.join(".env.app")));
+        assert!(!fs.exists(&repo_root.join(".env.infra")));
+        // マニフェスト自体はマウントされない
+        assert!(!fs.exists(&repo_root.join(MANIFEST_FILE)));
+    }
+
+    #[test]
+    fn test_mount_unassigned_worktree_gets_ungrouped_only() {
+        // 目的: グループ未割り当てのワークツリーへのマウント
+        // 検証: グループなしのエントリのみリンクされる
+
+        let (fs, repo_info, root) = setup();
+        setup_grouped_storage(&fs, &root);
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_git_repo(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        shared.mount(&repo_root).unwrap();
+
+        assert!(fs.is_symlink(&repo_root.join("config.json")));
+        assert!(!fs.exists(&repo_root.join(".env.app")));
+        assert!(!fs.exists(&repo_root.join(".env.infra")));
+    }
+
+    fn setup_nested_storage(fs: &MockFileSystem, root: &Path) -> PathBuf {
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+
+        let shared_root = root.join(".shared/github.com/user/repo");
+        fs.create_dir(&shared_root.join("config/local")).unwrap();
+        fs.add_file(shared_root.join(".env"));
+        fs.add_file(shared_root.join("secrets.json"));
+        fs.add_file(shared_root.join("config/app.toml"));
+        fs.add_file(shared_root.join("config/local/db.toml"));
+        shared_root
+    }
+
+    #[test]
+    fn test_mount_with_mount_manifest() {
+        // 目的: .grm-shared があるワークツリーへのマウント
+        // 検証: 記載されたパスとその配下のみリンクされる
+
+        let (fs, repo_info, root) = setup();
+        setup_nested_storage(&fs, &root);
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_git_repo(&repo_root);
+        fs.add_file_with_content(
+            repo_root.join(".grm-shared"),
+            "paths = [\".env\", \"config/local\"]\n",
+        );
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.mount(&repo_root);
+
+        assert!(result.is_ok(), "mount failed: {:?}", result.err());
+        assert!(fs.is_symlink(&repo_root.join(".env")));
+        assert!(fs.is_symlink(&repo_root.join("config/local/db.toml")));
+        assert!(!fs.exists(&repo_root.join("secrets.json")));
+        assert!(!fs.exists(&repo_root.join("config/app.toml")));
+    }
+
+    #[test]
+    fn test_mount_without_mount_manifest() {
+        // 目的: .grm-shared がないワークツリーへのマウント
+        // 検証: すべての共有パスがリンクされる
+
+        let (fs, repo_info, root) = setup();
+        setup_nested_storage(&fs, &root);
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_git_repo(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        shared.mount(&repo_root).unwrap();
+
+        assert!(fs.is_symlink(&repo_root.join(".env")));
+        assert!(fs.is_symlink(&repo_root.join("secrets.json")));
+        assert!(fs.is_symlink(&repo_root.join("config/app.toml")));
+        assert!(fs.is_symlink(&repo_root.join("config/local/db.toml")));
+    }
+
+    #[test]
+    fn test_share_with_group_links_assigned_worktrees_only() {
+        // 目的: グループ指定での共有
+        // 検証: 元のワークツリーと割り当て済みワークツリーにのみリンクされ、マニフェストに記録される
+
+        let (fs, repo_info, root) = setup();
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_git_repo(root.join("github.com/user/repo+app-1"));
+        fs.add_git_repo(root.join("github.com/user/repo+infra-1"));
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        shared.assign_groups("app-1", &["app".to_string()]).unwrap();
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_file(repo_root.join(".env.app"));
+        fs.set_current_dir(&repo_root);
+
+        let result = shared.share(
+            &repo_root,
+            Path::new(".env.app"),
+            Some("app"),
+            CopyMode::Move,
+        );
+
+        assert!(result.is_ok(), "share failed: {:?}", result.err());
+        assert!(fs.is_symlink(&repo_root.join(".env.app")));
+        assert!(fs.is_symlink(&root.join("github.com/user/repo+app-1/.env.app")));
+        assert!(!fs.exists(&root.join("github.com/user/repo+infra-1/.env.app")));
+
+        let manifest = ShareManifest::load(
+            fs.as_ref(),
+            &root
+                .join(".shared/github.com/user/repo")
+                .join(MANIFEST_FILE),
+        )
+        .unwrap();
+        assert_eq!(manifest.group_of(Path::new(".env.app")), Some("app"));
+        assert!(manifest.has_group(Some("main"), "app"));
+    }
+
+    #[test]
+    fn test_list_shows_groups() {
+        // 目的: 共有ファイル一覧
+        // 検証: グループ付きとグループなしのエントリが列挙され、マニフェストは除外される
+
+        let (fs, repo_info, root) = setup();
+        setup_grouped_storage(&fs, &root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let entries = shared.list().unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                (PathBuf::from(".env.app"), Some("app".to_string())),
+                (PathBuf::from(".env.infra"), Some("infra".to_string())),
+                (PathBuf::from("config.json"), None),
+            ]
+        );
+    }
+
     #[test]
     fn test_share_file_not_found() {
         // 目的: 存在しないファイルのエラー
@@ -451,17 +1304,105 @@ mod tests {
 
         let (fs, repo_info, root) = setup();
 
-        fs.add_dir(&root.join("github.com"));
-        fs.add_dir(&root.join("github.com/user"));
-        fs.add_git_repo(&root.join("github.com/user/repo+main"));
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
 
         let repo_root = root.join("github.com/user/repo+main");
         fs.set_current_dir(&repo_root);
 
         let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
-        let result = shared.share(&repo_root, Path::new("nonexistent.txt"));
+        let result = shared.share(
+            &repo_root,
+            Path::new("nonexistent.txt"),
+            None,
+            CopyMode::Move,
+        );
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), GrmError::NotFound(_)));
     }
+
+    #[test]
+    fn test_path_outside_repository_is_refused() {
+        // 目的: リポジトリ外を指すパスの拒否
+        // 検証: share / unshare / isolate のいずれも OutsideRepository エラーを返し、ファイルは移動されない
+
+        let (fs, repo_info, root) = setup();
+
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+        fs.add_file(root.join("github.com/user/escape.txt"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let path = Path::new("../escape.txt");
+
+        assert!(matches!(
+            shared.share(&repo_root, path, None, CopyMode::Move),
+            Err(GrmError::OutsideRepository { .. })
+        ));
+        assert!(matches!(
+            shared.unshare(&repo_root, path),
+            Err(GrmError::OutsideRepository { .. })
+        ));
+        assert!(matches!(
+            shared.isolate(&repo_root, path),
+            Err(GrmError::OutsideRepository { .. })
+        ));
+        assert!(fs.exists(&root.join("github.com/user/escape.txt")));
+        assert!(!fs.exists(&root.join(".shared")));
+    }
+
+    #[test]
+    fn test_share_git_directory_or_root_is_refused() {
+        // 目的: `.git` やリポジトリルート自体の共有の拒否
+        // 検証: ProtectedPath エラーが返され、何も共有ストレージに移動されない
+
+        let (fs, repo_info, root) = setup();
+
+        fs.add_dir(root.join("github.com"));
+        fs.add_dir(root.join("github.com/user"));
+        fs.add_git_repo(root.join("github.com/user/repo+main"));
+
+        let repo_root = root.join("github.com/user/repo+main");
+        fs.add_file(repo_root.join(".git/config"));
+        fs.set_current_dir(&repo_root);
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+
+        for path in [".git", ".git/config", "."] {
+            let result = shared.share(&repo_root, Path::new(path), None, CopyMode::Move);
+            assert!(
+                matches!(result, Err(GrmError::ProtectedPath { .. })),
+                "{path}: {result:?}"
+            );
+        }
+        assert!(fs.is_dir(&repo_root.join(".git")));
+        assert!(!fs.exists(&root.join(".shared")));
+    }
+
+    #[test]
+    fn test_mount_refuses_git_directory_in_storage() {
+        // 目的: 共有ストレージに `.git` が紛れ込んでいる場合のマウント
+        // 検証: ProtectedPath エラーが返され、ワークツリーの `.git` は置き換えられない
+
+        let (fs, repo_info, root) = setup();
+
+        let repo_root = root.join("github.com/user/repo+new");
+        fs.create_dir(&root.join("github.com/user")).unwrap();
+        fs.add_git_repo(&repo_root);
+        let shared_root = root.join(".shared/github.com/user/repo");
+        fs.create_dir(&shared_root).unwrap();
+        fs.add_file(shared_root.join(".git"));
+
+        let shared = SharedResource::new(repo_info, fs.clone(), root.clone());
+        let result = shared.mount(&repo_root);
+
+        assert!(matches!(result, Err(GrmError::ProtectedPath { .. })));
+        assert!(!fs.is_symlink(&repo_root.join(".git")));
+    }
 }
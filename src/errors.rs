@@ -2,7 +2,7 @@ use thiserror::Error;
 
 use crate::{
     configs::ConfigError,
-    core::ports::{FileSystemError, GitError, InteractionError},
+    core::ports::{CommandError, FileSystemError, GitError, InteractionError},
     core::repo_info::RepositoryError,
     core::repo_scanner::ScanError,
 };
@@ -24,6 +24,9 @@ pub enum GrmError {
     #[error("Interaction error: {0}")]
     Interaction(#[from] InteractionError),
 
+    #[error("Command error: {0}")]
+    Command(#[from] CommandError),
+
     #[error("Scan error: {0}")]
     Scan(#[from] ScanError),
 
@@ -39,9 +42,191 @@ pub enum GrmError {
     #[error("Operation cancelled by user")]
     UserCancelled,
 
+    #[error(
+        "Confirmation required, but standard input is not a terminal\nPass {flag} to proceed without confirmation"
+    )]
+    ConfirmationRequired { flag: String },
+
     #[error("Not in a managed git repository")]
     NotInManagedRepository,
 
     #[error("Resource not found: {0}")]
     NotFound(String),
+
+    #[error("Path {path} is outside the repository root {repo_root}")]
+    OutsideRepository { path: String, repo_root: String },
+
+    #[error(
+        "Refusing to share sensitive path {path} (matches share_deny pattern `{pattern}`)\nUse --allow-sensitive to share it anyway"
+    )]
+    SensitivePath { path: String, pattern: String },
+
+    #[error("Refusing to share {path}: {reason}")]
+    ProtectedPath { path: String, reason: String },
+
+    #[error("Worktree is locked: {0}\nUnlock it with `git worktree unlock` before removing")]
+    WorktreeLocked(String),
+
+    #[error("Invalid share manifest {path}: {message}")]
+    InvalidManifest { path: String, message: String },
+
+    #[error("Linked {path} into only {linked} of {total} worktrees: {source}")]
+    PartialShare {
+        path: String,
+        linked: usize,
+        total: usize,
+        source: Box<GrmError>,
+    },
+
+    #[error("{0} critical check(s) failed")]
+    DoctorFailed(usize),
+
+    #[error("Command failed in {failed} of {total} worktrees")]
+    ExecFailed { failed: usize, total: usize },
+
+    #[error("Failed to clone {failed} of {total} repositories")]
+    CloneFailed { failed: usize, total: usize },
+
+    #[error("Failed to pull {failed} of {total} worktrees")]
+    PullFailed { failed: usize, total: usize },
+
+    #[error("Invalid branch name `{branch}`: {reason}")]
+    InvalidBranch { branch: String, reason: String },
+
+    #[error("Invalid format `{format}`: {reason}")]
+    InvalidFormat { format: String, reason: String },
+
+    #[error("Multiple repositories match {query}:\n{candidates}")]
+    AmbiguousRepository { query: String, candidates: String },
+
+    #[error(
+        "{flag} cannot be used with layout `{layout}`, which has no directory for a checkout without a branch"
+    )]
+    UnsupportedLayout { flag: String, layout: String },
+}
+
+impl GrmError {
+    /// Convert a failed prompt, telling the user which `flag` skips it when nobody can answer
+    pub fn from_prompt(error: InteractionError, flag: &str) -> Self {
+        match error {
+            InteractionError::NotInteractive => Self::ConfirmationRequired {
+                flag: flag.to_string(),
+            },
+            error => Self::Interaction(error),
+        }
+    }
+
+    /// Process exit code, so that scripts can tell failures apart
+    ///
+    /// `2` is shared with the usage errors reported by clap.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::ParseFailed(_)
+            | Self::InvalidBranch { .. }
+            | Self::InvalidFormat { .. }
+            | Self::AmbiguousRepository { .. }
+            | Self::UnsupportedLayout { .. } => 2,
+            Self::NotFound(_) | Self::UnmanagedRepository { .. } => 3,
+            Self::AlreadyExists(_) => 4,
+            Self::UserCancelled | Self::ConfirmationRequired { .. } => 5,
+            Self::NotInManagedRepository | Self::OutsideRepository { .. } => 6,
+            Self::Config(_)
+            | Self::Git(_)
+            | Self::FileSystem(_)
+            | Self::Interaction(_)
+            | Self::Command(_)
+            | Self::Scan(_)
+            | Self::Io(_)
+            | Self::SensitivePath { .. }
+            | Self::ProtectedPath { .. }
+            | Self::WorktreeLocked(_)
+            | Self::InvalidManifest { .. }
+            | Self::PartialShare { .. }
+            | Self::DoctorFailed(_)
+            | Self::ExecFailed { .. }
+            | Self::CloneFailed { .. }
+            | Self::PullFailed { .. } => 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code_distinct_variants() {
+        // 目的: スクリプトから判別できる終了コード
+        // 検証: 各バリアントが README に記載したコードに対応する
+
+        let cases = [
+            (
+                GrmError::ParseFailed(RepositoryError::Invalid("x".to_string())),
+                2,
+            ),
+            (
+                GrmError::InvalidBranch {
+                    branch: "a..b".to_string(),
+                    reason: "contains `..`".to_string(),
+                },
+                2,
+            ),
+            (
+                GrmError::AmbiguousRepository {
+                    query: "repo".to_string(),
+                    candidates: String::new(),
+                },
+                2,
+            ),
+            (GrmError::NotFound("x".to_string()), 3),
+            (
+                GrmError::UnmanagedRepository {
+                    url: "x".to_string(),
+                    searched_path: "y".to_string(),
+                },
+                3,
+            ),
+            (GrmError::AlreadyExists("x".to_string()), 4),
+            (GrmError::UserCancelled, 5),
+            (
+                GrmError::ConfirmationRequired {
+                    flag: "--force".to_string(),
+                },
+                5,
+            ),
+            (GrmError::NotInManagedRepository, 6),
+            (
+                GrmError::OutsideRepository {
+                    path: "x".to_string(),
+                    repo_root: "y".to_string(),
+                },
+                6,
+            ),
+        ];
+
+        for (error, code) in cases {
+            assert_eq!(error.exit_code(), code, "{error:?}");
+        }
+    }
+
+    #[test]
+    fn test_exit_code_unexpected_errors() {
+        // 目的: 想定外のエラーの終了コード
+        // 検証: IO などのエラーは 1 になる
+
+        assert_eq!(GrmError::Io(std::io::Error::other("x")).exit_code(), 1);
+        assert_eq!(
+            GrmError::Git(GitError::Execution("x".to_string())).exit_code(),
+            1
+        );
+        assert_eq!(GrmError::DoctorFailed(2).exit_code(), 1);
+        assert_eq!(
+            GrmError::ExecFailed {
+                failed: 1,
+                total: 2
+            }
+            .exit_code(),
+            1
+        );
+    }
 }
@@ -0,0 +1,13 @@
+//! Git Repository Manager
+//!
+//! The `grm` binary is a thin wrapper around `commands::Cli`. Other programs can
+//! build an `container::AppContainer` from a loaded `configs::Config` and call
+//! the use cases in `usecases` directly, e.g. `CloneRepositoryUseCase`.
+
+pub mod adapters;
+pub mod commands;
+pub mod configs;
+pub mod container;
+pub mod core;
+pub mod errors;
+pub mod usecases;
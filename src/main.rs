@@ -1,16 +1,8 @@
-mod adapters;
-mod commands;
-mod configs;
-mod container;
-mod core;
-mod errors;
-mod usecases;
-
-use crate::commands::Cli;
+use grm::commands::Cli;
 
 fn main() {
     if let Err(error) = Cli::execute() {
         eprintln!("Error: {error}");
-        std::process::exit(1);
+        std::process::exit(error.exit_code());
     }
 }
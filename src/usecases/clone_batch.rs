@@ -0,0 +1,202 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::core::ports::{Clock, CommandRunner, FileSystem, GitRepository, UserInteraction};
+use crate::errors::GrmError;
+use crate::usecases::CloneRepositoryUseCase;
+
+/// Outcome of cloning a list of URLs, in the order they were given
+#[derive(Debug, Default)]
+pub struct CloneSummary {
+    /// URL and the path it was cloned to
+    pub cloned: Vec<(String, PathBuf)>,
+    /// URL and why it could not be cloned
+    pub failed: Vec<(String, GrmError)>,
+}
+
+/// Clone several repositories at once for `grm clone -`
+///
+/// The adapters must be shareable between threads, as each clone runs
+/// `CloneRepositoryUseCase` on a worker thread.
+pub struct CloneBatchUseCase {
+    git: Arc<dyn GitRepository + Send + Sync>,
+    fs: Arc<dyn FileSystem + Send + Sync>,
+    ui: Arc<dyn UserInteraction + Send + Sync>,
+    runner: Arc<dyn CommandRunner + Send + Sync>,
+    retries: Option<(u32, Arc<dyn Clock + Send + Sync>)>,
+}
+
+impl CloneBatchUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository + Send + Sync>,
+        fs: Arc<dyn FileSystem + Send + Sync>,
+        ui: Arc<dyn UserInteraction + Send + Sync>,
+        runner: Arc<dyn CommandRunner + Send + Sync>,
+    ) -> Self {
+        Self {
+            git,
+            fs,
+            ui,
+            runner,
+            retries: None,
+        }
+    }
+
+    /// Retry each clone like `CloneRepositoryUseCase::with_retries`
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32, clock: Arc<dyn Clock + Send + Sync>) -> Self {
+        self.retries = Some((retries, clock));
+        self
+    }
+
+    /// Clone every URL in `urls`, running at most `jobs` clones at a time
+    ///
+    /// A failing URL is reported and does not stop the others.
+    ///
+    /// # Arguments
+    /// * `jobs` - Number of concurrent `git clone` processes, at least one
+    /// * `clone` - Clones one URL, e.g. with `CloneRepositoryUseCase::execute`
+    pub fn execute<F>(&self, urls: &[String], jobs: usize, clone: F) -> CloneSummary
+    where
+        F: Fn(&CloneRepositoryUseCase, &str) -> Result<PathBuf, GrmError> + Sync,
+    {
+        // Workers pull the next URL from a shared index, so no more than
+        // `jobs` git processes run at the same time
+        let workers = jobs.max(1).min(urls.len());
+        let next = AtomicUsize::new(0);
+
+        let mut results = std::thread::scope(|scope| {
+            let handles = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut usecase = CloneRepositoryUseCase::new(
+                            self.git.clone(),
+                            self.fs.clone(),
+                            self.ui.clone(),
+                            self.runner.clone(),
+                        );
+                        if let Some((retries, clock)) = &self.retries {
+                            usecase = usecase.with_retries(*retries, clock.clone());
+                        }
+                        let mut cloned = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(url) = urls.get(index) else {
+                                break;
+                            };
+                            cloned.push((index, clone(&usecase, url)));
+                        }
+                        cloned
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("Clone thread panicked"))
+                .collect::<Vec<_>>()
+        });
+        results.sort_by_key(|(index, _)| *index);
+
+        let mut summary = CloneSummary::default();
+        for (index, result) in results {
+            let url = urls[index].clone();
+            match result {
+                Ok(path) => summary.cloned.push((url, path)),
+                Err(e) => {
+                    self.ui.print_error(&format!("Failed to clone {url}: {e}"));
+                    summary.failed.push((url, e));
+                }
+            }
+        }
+
+        self.ui.print(&format!(
+            "Cloned {} of {} repositories",
+            summary.cloned.len(),
+            urls.len()
+        ));
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{
+        MockCommandRunner, MockFileSystem, MockGitRepository, MockUserInteraction,
+    };
+    use crate::configs::Config;
+    use crate::usecases::CloneOptions;
+    use std::path::Path;
+
+    #[test]
+    fn test_clone_batch_continues_after_failure() {
+        // 目的: 複数 URL の並列クローン
+        // 検証: 失敗した URL があっても残りはクローンされ、入力順に集計される
+
+        let git = Arc::new(MockGitRepository::new());
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let root = PathBuf::from("/home/testuser/grm");
+        fs.add_dir(&root);
+        fs.create_dir(&root.join("github.com/user/exists+main"))
+            .unwrap();
+        let config = Config::new(root);
+
+        let urls = [
+            "https://github.com/user/one",
+            "not-a-url",
+            "https://github.com/user/exists",
+            "https://github.com/user/two",
+            "https://gitlab.com/group/three",
+        ]
+        .map(String::from);
+        for url in &urls {
+            git.set_default_branch(url, "main");
+        }
+        let usecase = CloneBatchUseCase::new(
+            git.clone(),
+            fs,
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let summary = usecase.execute(&urls, 2, |clone, url| {
+            clone.execute(&config, url, &CloneOptions::default())
+        });
+
+        let cloned = summary
+            .cloned
+            .iter()
+            .map(|(url, _)| url.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cloned,
+            vec![
+                "https://github.com/user/one",
+                "https://github.com/user/two",
+                "https://gitlab.com/group/three",
+            ]
+        );
+        assert_eq!(
+            summary.cloned[2].1,
+            Path::new("/home/testuser/grm/gitlab.com/group/three+main")
+        );
+        assert_eq!(git.get_cloned_repos().len(), 3);
+        assert!(matches!(
+            summary.failed.as_slice(),
+            [
+                (_, GrmError::ParseFailed(_)),
+                (_, GrmError::AlreadyExists(_))
+            ]
+        ));
+        assert!(ui.has_printed("Cloned 3 of 5 repositories"));
+        assert!(
+            ui.get_error_messages()
+                .iter()
+                .any(|m| m.starts_with("Failed to clone not-a-url:"))
+        );
+    }
+}
@@ -1,15 +1,61 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
 use crate::configs::Config;
-use crate::core::RepoInfo;
+use crate::core::ports::{
+    Clock, CommandRunner, FileSystem, GitError, GitRepository, UserInteraction,
+};
+use crate::core::{RepoInfo, RepoMarker};
 use crate::errors::GrmError;
 
+/// Branches tried in order when the remote does not advertise its default branch
+const FALLBACK_BRANCHES: &[&str] = &["main", "master"];
+
+/// Wait before the first retry of a failed clone, doubled for each further one
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Where `CloneRepositoryUseCase::execute` puts the clone
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CloneTarget {
+    /// The `layout` directory of the branch
+    #[default]
+    Worktree,
+    /// A plain `{host}/{user}/{repo}` checkout
+    Plain,
+    /// `{host}/{user}/{repo}.git` without a working tree
+    Bare,
+    /// Like `Bare`, but cloned with `--mirror`, which also maps all refs
+    Mirror,
+}
+
+/// How `CloneRepositoryUseCase::execute` clones, mirroring the flags of `grm clone`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CloneOptions<'a> {
+    /// Destination of the clone, the `layout` directory of the branch by default
+    pub target: CloneTarget,
+    /// Branch to check out, the default branch of the remote if `None`
+    pub branch: Option<&'a str>,
+    /// Link `{host}/{user}/{repo}` to the worktree if it is the default branch
+    pub link: bool,
+    /// Shallow clone depth, `clone_depth` from the config if `None`
+    pub depth: Option<u32>,
+    /// Also clone and check out the submodules
+    pub recurse_submodules: bool,
+    /// Ask the remote for its default branch instead of using the cache
+    pub refresh: bool,
+    /// Shell command run in the clone, `post_clone` from the config if `None`
+    pub post_clone: Option<&'a str>,
+    /// Check out only these directories (cone mode), or everything if empty
+    pub sparse: &'a [String],
+}
+
 pub struct CloneRepositoryUseCase {
     git: Arc<dyn GitRepository>,
     fs: Arc<dyn FileSystem>,
     ui: Arc<dyn UserInteraction>,
+    runner: Arc<dyn CommandRunner>,
+    retries: Option<(u32, Arc<dyn Clock>)>,
 }
 
 impl CloneRepositoryUseCase {
@@ -17,25 +63,61 @@ impl CloneRepositoryUseCase {
         git: Arc<dyn GitRepository>,
         fs: Arc<dyn FileSystem>,
         ui: Arc<dyn UserInteraction>,
+        runner: Arc<dyn CommandRunner>,
     ) -> Self {
-        Self { git, fs, ui }
+        Self {
+            git,
+            fs,
+            ui,
+            runner,
+            retries: None,
+        }
     }
 
+    /// Retry a clone which failed on the network up to `retries` times
+    ///
+    /// The `clock` waits between the attempts, one second before the first
+    /// retry and twice as long before each further one.
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32, clock: Arc<dyn Clock>) -> Self {
+        self.retries = Some((retries, clock));
+        self
+    }
+
+    /// Clone a repository into the managed structure
     pub fn execute(
         &self,
         config: &Config,
         url: &str,
-        branch: Option<&str>,
+        options: &CloneOptions,
     ) -> Result<PathBuf, GrmError> {
-        let repo_info = RepoInfo::from_url(url)?;
+        match options.target {
+            CloneTarget::Bare => return self.execute_bare(config, url, false),
+            CloneTarget::Mirror => return self.execute_bare(config, url, true),
+            CloneTarget::Worktree | CloneTarget::Plain => {}
+        }
+
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+
+        if options.refresh {
+            self.git.forget_default_branch(&url);
+        }
 
-        let branch_name = if let Some(b) = branch {
+        let branch_name = if let Some(b) = options.branch {
             b.to_string()
         } else {
-            self.git.get_default_branch(url)?
+            self.default_branch(&url)?
         };
+        RepoInfo::validate_branch_name(&branch_name)?;
 
-        let dest_path = repo_info.build_repo_path(config.root(), &branch_name);
+        let dest_path = if options.target == CloneTarget::Plain {
+            repo_info
+                .build_repo_path_plain(config.root(), &config.layout)
+                .ok_or_else(|| unsupported_layout(config, "--no-branch-suffix"))?
+        } else {
+            repo_info.build_repo_path_with(config.root(), &branch_name, &config.layout)
+        };
 
         if self.fs.exists(&dest_path) {
             return Err(GrmError::AlreadyExists(dest_path.display().to_string()));
@@ -45,46 +127,442 @@ impl CloneRepositoryUseCase {
             self.fs.create_dir(parent)?;
         }
 
-        self.git
-            .clone_repository(url, &dest_path, Some(&branch_name))?;
+        let depth = options.depth.or(config.clone_depth);
+        self.retry(&url, &dest_path, || {
+            if options.sparse.is_empty() {
+                self.git.clone_repository(
+                    &url,
+                    &dest_path,
+                    Some(&branch_name),
+                    depth,
+                    options.recurse_submodules,
+                )
+            } else {
+                self.git
+                    .clone_sparse(&url, &dest_path, Some(&branch_name), depth, options.sparse)
+            }
+        })?;
+
+        // Discovery falls back to the directory name, so a missing marker only warns
+        let marker = RepoMarker::new(&repo_info, &branch_name, &url);
+        if let Err(e) = marker.save(self.fs.as_ref(), &dest_path) {
+            self.ui
+                .print_warning(&format!("Failed to write repository marker: {e}"));
+        }
 
         self.ui
             .print(&format!("Repository cloned to: {}", dest_path.display()));
 
+        // A plain clone already lives at the link path
+        if options.link && options.target == CloneTarget::Worktree {
+            // The clone itself succeeded, so a failing link only warns
+            if let Err(e) =
+                self.link_default_branch(config, &url, &repo_info, &dest_path, options.branch)
+            {
+                self.ui
+                    .print_warning(&format!("Failed to create default-branch link: {e}"));
+            }
+        }
+
+        if let Some(command) = options.post_clone.or(config.post_clone.as_deref()) {
+            self.run_post_clone(command, &dest_path);
+        }
+
         Ok(dest_path)
     }
+
+    /// Clone a repository without a working tree into `{host}/{user}/{repo}.git`
+    ///
+    /// # Arguments
+    /// * `mirror` - Clone with `--mirror` instead of `--bare`, which also maps all refs
+    pub fn execute_bare(
+        &self,
+        config: &Config,
+        url: &str,
+        mirror: bool,
+    ) -> Result<PathBuf, GrmError> {
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+
+        let flag = if mirror { "--mirror" } else { "--bare" };
+        let dest_path = repo_info
+            .build_bare_path(config.root(), &config.layout)
+            .ok_or_else(|| unsupported_layout(config, flag))?;
+        if self.fs.exists(&dest_path) {
+            return Err(GrmError::AlreadyExists(dest_path.display().to_string()));
+        }
+
+        if let Some(parent) = dest_path.parent() {
+            self.fs.create_dir(parent)?;
+        }
+
+        self.retry(&url, &dest_path, || {
+            self.git.clone_bare(&url, &dest_path, mirror)
+        })?;
+
+        self.ui
+            .print(&format!("Repository cloned to: {}", dest_path.display()));
+
+        Ok(dest_path)
+    }
+
+    /// Default branch of the remote
+    ///
+    /// Some servers do not advertise a symbolic `HEAD`, in which case the
+    /// `FALLBACK_BRANCHES` are looked up on the remote instead.
+    pub(crate) fn default_branch(&self, url: &str) -> Result<String, GrmError> {
+        match self.git.get_default_branch(url) {
+            Err(GitError::Parse(message)) => {
+                for candidate in FALLBACK_BRANCHES {
+                    if self.git.remote_branch_exists(url, candidate)? {
+                        self.ui.print_warning(&format!(
+                            "{url} does not advertise a default branch, using {candidate}"
+                        ));
+                        return Ok((*candidate).to_string());
+                    }
+                }
+                Err(GitError::Parse(message).into())
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// Run `clone` until it succeeds, it fails for good, or the retries run out
+    ///
+    /// The partial clone is removed after each failed attempt, so every
+    /// attempt starts from an empty destination.
+    fn retry(
+        &self,
+        url: &str,
+        dest_path: &Path,
+        clone: impl Fn() -> Result<(), GitError>,
+    ) -> Result<(), GrmError> {
+        let mut attempt = 0;
+        let mut delay = FIRST_RETRY_DELAY;
+        loop {
+            let Err(e) = clone() else {
+                return Ok(());
+            };
+            self.remove_partial_clone(dest_path);
+
+            let Some((retries, clock)) = &self.retries else {
+                return Err(e.into());
+            };
+            if attempt >= *retries || !e.is_transient() {
+                return Err(e.into());
+            }
+            attempt += 1;
+            self.ui.print_warning(&format!(
+                "Failed to clone {url}, retrying in {}s ({attempt}/{retries}): {e}",
+                delay.as_secs()
+            ));
+            clock.sleep(delay);
+            delay *= 2;
+        }
+    }
+
+    /// Remove whatever a failed `git clone` left at `dest_path`
+    ///
+    /// The destination did not exist before the clone, so anything there is
+    /// left over from it and would make the next attempt fail as `AlreadyExists`.
+    fn remove_partial_clone(&self, dest_path: &Path) {
+        if !self.fs.exists(dest_path) {
+            return;
+        }
+        if let Err(e) = self.fs.remove(dest_path) {
+            self.ui.print_warning(&format!(
+                "Failed to remove {} after the failed clone: {e}",
+                dest_path.display()
+            ));
+        }
+    }
+
+    /// Run the post-clone hook through the shell
+    ///
+    /// The clone is kept even if the hook fails, so failures only warn.
+    fn run_post_clone(&self, command: &str, dest_path: &Path) {
+        #[cfg(unix)]
+        let (shell, flag) = ("sh", "-c");
+        #[cfg(windows)]
+        let (shell, flag) = ("cmd", "/C");
+
+        self.ui
+            .print(&format!("Running post-clone hook: {command}"));
+        match self.runner.run(
+            shell,
+            &[flag.to_string(), command.to_string()],
+            dest_path,
+            &[],
+        ) {
+            Ok(0) => {}
+            Ok(code) => self
+                .ui
+                .print_warning(&format!("Post-clone hook exited with status {code}")),
+            Err(e) => self
+                .ui
+                .print_warning(&format!("Failed to run post-clone hook: {e}")),
+        }
+    }
+
+    /// Point `{host}/{user}/{repo}` at the default-branch worktree
+    fn link_default_branch(
+        &self,
+        config: &Config,
+        url: &str,
+        repo_info: &RepoInfo,
+        dest_path: &Path,
+        branch: Option<&str>,
+    ) -> Result<(), GrmError> {
+        if let Some(b) = branch
+            && self.default_branch(url)? != b
+        {
+            self.ui.print(&format!(
+                "Skipping default-branch link: {b} is not the default branch"
+            ));
+            return Ok(());
+        }
+
+        let link_path = repo_info
+            .build_repo_path_plain(config.root(), &config.layout)
+            .ok_or_else(|| unsupported_layout(config, "--link"))?;
+        if self.fs.is_symlink(&link_path) {
+            self.fs.remove(&link_path)?;
+        } else if self.fs.exists(&link_path) {
+            return Err(GrmError::AlreadyExists(link_path.display().to_string()));
+        }
+
+        self.fs.create_symlink(dest_path, &link_path)?;
+        self.ui.print(&format!(
+            "Linked: {} -> {}",
+            link_path.display(),
+            dest_path.display()
+        ));
+
+        Ok(())
+    }
+}
+
+/// Error for a `flag` which needs the plain `{repo}` directory the layout does not have
+fn unsupported_layout(config: &Config, flag: &str) -> GrmError {
+    GrmError::UnsupportedLayout {
+        flag: flag.to_string(),
+        layout: config.layout.to_string(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
-    use crate::core::ports::GitError;
+    use crate::adapters::test_helpers::{
+        MockClock, MockCommandRunner, MockFileSystem, MockGitRepository, MockUserInteraction,
+    };
+    use crate::core::Layout;
+    use crate::core::repo_marker::MARKER_DIR;
+    use crate::usecases::{
+        ListOptions, ListRepositoriesUseCase, ListStyle, RemoveRepositoryUseCase,
+    };
 
-    fn setup() -> (Arc<MockGitRepository>, Arc<MockFileSystem>, Arc<MockUserInteraction>, Config) {
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
         let git = Arc::new(MockGitRepository::new());
         let fs = Arc::new(MockFileSystem::new());
         let ui = Arc::new(MockUserInteraction::new());
 
         let root = PathBuf::from("/home/testuser/grm");
         fs.add_dir(&root);
-        let config = Config { root };
+        let config = Config::new(root);
 
         (git, fs, ui, config)
     }
 
+    #[test]
+    fn test_clone_without_branch_suffix() {
+        // 目的: ブランチ名なしのディレクトリへのクローン
+        // 検証: `{repo}+{branch}` ではなく `{repo}` にクローンされ、リンクは作成されない
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                link: true,
+                target: CloneTarget::Plain,
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        let dest = result.unwrap();
+        assert_eq!(
+            dest,
+            PathBuf::from("/home/testuser/grm/github.com/user/repo")
+        );
+        assert_eq!(git.get_cloned_repos()[0].1, dest);
+        assert!(!fs.is_symlink(&dest));
+    }
+
+    #[test]
+    fn test_clone_local_file_url() {
+        // 目的: file:// URL でのローカルのリポジトリのクローン
+        // 検証: `local/{parent}/{repo}+{branch}` にクローンされ、git には URL がそのまま渡される
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "file:///tmp/mirrors/vendor/lib.git";
+        git.set_default_branch(url, "main");
+
+        let result = usecase.execute(&config, url, &CloneOptions::default());
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        let dest = result.unwrap();
+        assert_eq!(
+            dest,
+            PathBuf::from("/home/testuser/grm/local/vendor/lib+main")
+        );
+        assert_eq!(git.get_cloned_repos()[0].0, url);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_clone_local_file_url_with_git_cli() {
+        // 目的: 実際の git と FS を使った file:// URL のクローン
+        // 検証: `local/{parent}/{repo}+{branch}` にクローンされ、デフォルトブランチがチェックアウトされる
+
+        use crate::adapters::{GitCli, UnixFs};
+        use std::process::Command;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let upstream = temp_dir.path().join("vendor/lib");
+        std::fs::create_dir_all(&upstream).unwrap();
+        for args in [
+            &["init", "--initial-branch=test"][..],
+            &["config", "user.email", "you@example.com"],
+            &["config", "user.name", "Your Name"],
+            &["commit", "--allow-empty", "-m", "Initial commit"],
+        ] {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&upstream)
+                .output()
+                .unwrap();
+            assert!(output.status.success(), "git {args:?} failed");
+        }
+
+        let root = temp_dir.path().join("grm");
+        std::fs::create_dir(&root).unwrap();
+        let config = Config::new(root.clone());
+        let git = Arc::new(GitCli::new());
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            Arc::new(UnixFs::new()),
+            Arc::new(MockUserInteraction::new()),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = format!("file://{}", upstream.display());
+        let result = usecase.execute(&config, &url, &CloneOptions::default());
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        let dest = result.unwrap();
+        assert_eq!(dest, root.join("local/vendor/lib+test"));
+        assert!(dest.join(".git").exists());
+        assert_eq!(git.get_current_branch(&dest).unwrap(), "test");
+    }
+
+    #[test]
+    fn test_clone_without_branch_suffix_already_exists() {
+        // 目的: ブランチ名なしのディレクトリが既に存在する場合
+        // 検証: AlreadyExists エラーが返され、クローンされない
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+        fs.add_git_repo("/home/testuser/grm/github.com/user/repo");
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                target: CloneTarget::Plain,
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(matches!(result, Err(GrmError::AlreadyExists(_))));
+        assert!(git.get_cloned_repos().is_empty());
+    }
+
+    #[test]
+    fn test_clone_shorthand_uses_https_url() {
+        // 目的: スキームなしの host/user/repo 形式でのクローン
+        // 検証: https:// を補完したURLでクローンされる
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        git.set_default_branch("https://github.com/user/repo", "main");
+
+        let result = usecase.execute(&config, "github.com/user/repo", &CloneOptions::default());
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        let cloned = git.get_cloned_repos();
+        assert_eq!(cloned[0].0, "https://github.com/user/repo");
+        assert_eq!(
+            cloned[0].1,
+            PathBuf::from("/home/testuser/grm/github.com/user/repo+main")
+        );
+    }
+
     #[test]
     fn test_clone_success_with_default_branch() {
         // 目的: HTTPSリポジトリをデフォルトブランチでクローン成功
         // 検証: 正しいパスにクローンされ、メッセージが表示される
 
         let (git, fs, ui, config) = setup();
-        let usecase = CloneRepositoryUseCase::new(git.clone(), fs.clone(), ui.clone());
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
 
         let url = "https://github.com/user/repo.git";
         git.set_default_branch(url, "main");
 
-        let result = usecase.execute(&config, url, None);
+        let result = usecase.execute(&config, url, &CloneOptions::default());
 
         assert!(result.is_ok(), "clone failed: {:?}", result.err());
         let dest = result.unwrap();
@@ -107,11 +585,23 @@ mod tests {
         // 検証: 指定したブランチでクローンされる
 
         let (git, fs, ui, config) = setup();
-        let usecase = CloneRepositoryUseCase::new(git.clone(), fs.clone(), ui.clone());
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
 
         let url = "git@github.com:user/repo.git";
 
-        let result = usecase.execute(&config, url, Some("feature/test"));
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                branch: Some("feature/test"),
+                ..CloneOptions::default()
+            },
+        );
 
         assert!(result.is_ok(), "clone failed: {:?}", result.err());
         let dest = result.unwrap();
@@ -130,7 +620,12 @@ mod tests {
         // 検証: AlreadyExistsエラーが返される
 
         let (git, fs, ui, config) = setup();
-        let usecase = CloneRepositoryUseCase::new(git.clone(), fs.clone(), ui.clone());
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
 
         let url = "https://github.com/user/repo.git";
         git.set_default_branch(url, "main");
@@ -138,7 +633,7 @@ mod tests {
         let dest_path = PathBuf::from("/home/testuser/grm/github.com/user/repo+main");
         fs.add_dir(&dest_path);
 
-        let result = usecase.execute(&config, url, None);
+        let result = usecase.execute(&config, url, &CloneOptions::default());
 
         assert!(result.is_err());
         match result.unwrap_err() {
@@ -155,31 +650,835 @@ mod tests {
         // 検証: ParseFailedエラーが返される
 
         let (git, fs, ui, config) = setup();
-        let usecase = CloneRepositoryUseCase::new(git, fs, ui);
+        let usecase = CloneRepositoryUseCase::new(git, fs, ui, Arc::new(MockCommandRunner::new()));
 
-        let result = usecase.execute(&config, "invalid-url", None);
+        let result = usecase.execute(&config, "invalid-url", &CloneOptions::default());
 
         assert!(result.is_err());
         // Invalid URL should result in ParseFailed error
         assert!(matches!(result.unwrap_err(), GrmError::ParseFailed(_)));
     }
 
+    #[test]
+    fn test_clone_invalid_branch() {
+        // 目的: 不正なブランチ名を検出
+        // 検証: パス構築前に InvalidBranch エラーが返され、クローンされない
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        let url = "https://github.com/user/repo";
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                branch: Some("a+b"),
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(matches!(result, Err(GrmError::InvalidBranch { .. })));
+        assert!(git.get_cloned_repos().is_empty());
+    }
+
     #[test]
     fn test_clone_git_failure() {
         // 目的: Git操作失敗時のエラーハンドリング
         // 検証: GitErrorが適切に伝播される
 
         let (git, fs, ui, config) = setup();
-        let usecase = CloneRepositoryUseCase::new(git.clone(), fs, ui);
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
 
         let url = "https://github.com/user/repo.git";
         git.set_default_branch(url, "main");
         git.inject_error(GitError::Execution("Network error".into()));
 
-        let result = usecase.execute(&config, url, None);
+        let result = usecase.execute(&config, url, &CloneOptions::default());
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), GrmError::Git(_)));
     }
-}
 
+    #[test]
+    fn test_clone_failure_removes_partial_destination() {
+        // 目的: クローン途中で失敗した場合の後始末
+        // 検証: 作りかけのディレクトリが削除され、再試行が AlreadyExists にならない
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui,
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+        git.set_partial_clone(fs.clone());
+        git.inject_error(GitError::Execution("early EOF".into()));
+        let dest = Path::new("/home/testuser/grm/github.com/user/repo+main");
+
+        let failed = usecase.execute(&config, url, &CloneOptions::default());
+        let left_over = fs.exists(dest);
+        let retried = usecase.execute(&config, url, &CloneOptions::default());
+
+        assert!(matches!(failed, Err(GrmError::Git(_))));
+        assert!(!left_over);
+        assert_eq!(retried.unwrap(), dest);
+    }
+
+    #[test]
+    fn test_clone_retries_network_failures() {
+        // 目的: --retries 指定時のネットワーク障害からの再試行
+        // 検証: 2回失敗した後に成功し、試行の間に作りかけのディレクトリが削除され、待ち時間が倍になる
+
+        let (git, fs, ui, config) = setup();
+        let clock = Arc::new(MockClock::new());
+        let started = clock.now();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        )
+        .with_retries(3, clock.clone());
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+        git.set_partial_clone(fs.clone());
+        git.fail_next_clones([
+            GitError::Failed {
+                status: 128,
+                stderr: "fatal: unable to access 'https://github.com/user/repo/': Could not resolve host: github.com".into(),
+            },
+            GitError::Failed {
+                status: 128,
+                stderr: "error: RPC failed; curl 18 transfer closed\nfatal: early EOF".into(),
+            },
+        ]);
+
+        let result = usecase.execute(&config, url, &CloneOptions::default());
+
+        assert_eq!(
+            result.unwrap(),
+            PathBuf::from("/home/testuser/grm/github.com/user/repo+main")
+        );
+        assert_eq!(git.get_cloned_repos().len(), 1);
+        assert_eq!(
+            clock.now().duration_since(started).unwrap(),
+            Duration::from_secs(3)
+        );
+        let warnings = ui.get_warning_messages();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].starts_with(&format!("Failed to clone {url}, retrying in 2s (2/3)")));
+    }
+
+    #[test]
+    fn test_clone_does_not_retry_other_failures() {
+        // 目的: ネットワーク以外の失敗時の動作
+        // 検証: 認証エラーは再試行されずにそのまま失敗する
+
+        let (git, fs, ui, config) = setup();
+        let clock = Arc::new(MockClock::new());
+        let started = clock.now();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        )
+        .with_retries(3, clock.clone());
+
+        let url = "https://github.com/user/private";
+        git.set_default_branch(url, "main");
+        git.set_partial_clone(fs.clone());
+        git.fail_next_clones([GitError::Failed {
+            status: 128,
+            stderr: "fatal: Authentication failed for 'https://github.com/user/private/'".into(),
+        }]);
+
+        let result = usecase.execute(&config, url, &CloneOptions::default());
+
+        assert!(matches!(
+            result,
+            Err(GrmError::Git(GitError::Failed { .. }))
+        ));
+        assert!(!fs.exists(Path::new("/home/testuser/grm/github.com/user/private+main")));
+        assert_eq!(clock.now(), started);
+        assert!(ui.get_warning_messages().is_empty());
+    }
+
+    #[test]
+    fn test_clone_bare_failure_removes_partial_destination() {
+        // 目的: ベアクローン途中で失敗した場合の後始末
+        // 検証: 作りかけの `{repo}.git` が削除され、既存のクローンには触れない
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui,
+            Arc::new(MockCommandRunner::new()),
+        );
+        fs.create_dir(Path::new("/home/testuser/grm/github.com/user/repo+main"))
+            .unwrap();
+        git.set_partial_clone(fs.clone());
+        git.inject_error(GitError::Execution("early EOF".into()));
+
+        let result = usecase.execute_bare(&config, "https://github.com/user/repo", false);
+
+        assert!(matches!(result, Err(GrmError::Git(_))));
+        assert!(!fs.exists(Path::new("/home/testuser/grm/github.com/user/repo.git")));
+        assert!(fs.exists(Path::new("/home/testuser/grm/github.com/user/repo+main")));
+    }
+
+    #[test]
+    fn test_clone_with_link_creates_default_branch_link() {
+        // 目的: --link指定時にデフォルトブランチへのリンクを作成
+        // 検証: {repo} がクローン先を指すシンボリックリンクになる
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+
+        let dest = usecase
+            .execute(
+                &config,
+                url,
+                &CloneOptions {
+                    link: true,
+                    ..CloneOptions::default()
+                },
+            )
+            .unwrap();
+
+        let link = PathBuf::from("/home/testuser/grm/github.com/user/repo");
+        assert!(fs.is_symlink(&link));
+        assert_eq!(fs.read_link(&link).unwrap(), dest);
+    }
+
+    #[test]
+    fn test_clone_with_link_replaces_stale_link() {
+        // 目的: 既存のリンクを張り替える
+        // 検証: 古いリンク先から新しいクローン先に更新される
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "develop");
+        let link = PathBuf::from("/home/testuser/grm/github.com/user/repo");
+        fs.add_symlink(&link, "/home/testuser/grm/github.com/user/repo+main");
+
+        let dest = usecase
+            .execute(
+                &config,
+                url,
+                &CloneOptions {
+                    link: true,
+                    ..CloneOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(fs.read_link(&link).unwrap(), dest);
+    }
+
+    #[test]
+    fn test_clone_with_link_skips_non_default_branch() {
+        // 目的: デフォルト以外のブランチではリンクを作らない
+        // 検証: クローンは成功し、リンクは作成されない
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                branch: Some("feature"),
+                link: true,
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert!(!fs.exists(&PathBuf::from("/home/testuser/grm/github.com/user/repo")));
+        assert!(ui.has_printed("Skipping default-branch link"));
+    }
+
+    #[test]
+    fn test_clone_with_link_does_not_replace_directory() {
+        // 目的: リンク位置に実ディレクトリがある場合は上書きしない
+        // 検証: クローンは成功し、警告が出力される
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+        let link = PathBuf::from("/home/testuser/grm/github.com/user/repo");
+        fs.add_dir(&link);
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                link: true,
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert!(!fs.is_symlink(&link));
+        assert!(
+            ui.get_warning_messages()
+                .iter()
+                .any(|m| m.contains("default-branch link"))
+        );
+    }
+
+    #[test]
+    fn test_clone_uses_config_defaults() {
+        // 目的: default_host と clone_depth の設定値の利用
+        // 検証: user/repo が default_host で補完され、depth 未指定時は clone_depth が使われる
+
+        let (git, fs, ui, mut config) = setup();
+        config.default_host = Some("gitlab.com".to_string());
+        config.clone_depth = Some(1);
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        git.set_default_branch("https://gitlab.com/user/repo", "main");
+
+        let result = usecase.execute(&config, "user/repo", &CloneOptions::default());
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        assert_eq!(git.get_cloned_repos()[0].0, "https://gitlab.com/user/repo");
+        assert_eq!(git.get_clone_depths(), vec![Some(1)]);
+    }
+
+    #[test]
+    fn test_clone_depth_overrides_config() {
+        // 目的: --depth 指定時の動作
+        // 検証: 設定の clone_depth より引数の depth が優先される
+
+        let (git, fs, ui, mut config) = setup();
+        config.clone_depth = Some(1);
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        let url = "https://github.com/user/repo.git";
+        git.set_default_branch(url, "main");
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                depth: Some(10),
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(git.get_clone_depths(), vec![Some(10)]);
+    }
+
+    #[test]
+    fn test_clone_recurse_submodules() {
+        // 目的: --recurse-submodules 指定時の動作
+        // 検証: 指定したクローンだけがサブモジュール込みでクローンされる
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        git.set_default_branch("https://github.com/user/app", "main");
+        git.set_default_branch("https://github.com/user/lib", "main");
+
+        let with = usecase.execute(
+            &config,
+            "https://github.com/user/app",
+            &CloneOptions {
+                recurse_submodules: true,
+                ..CloneOptions::default()
+            },
+        );
+        let without = usecase.execute(
+            &config,
+            "https://github.com/user/lib",
+            &CloneOptions::default(),
+        );
+
+        assert!(with.is_ok() && without.is_ok());
+        assert_eq!(
+            git.get_submodule_clones(),
+            vec![PathBuf::from("/home/testuser/grm/github.com/user/app+main")]
+        );
+    }
+
+    #[test]
+    fn test_clone_sparse() {
+        // 目的: --sparse 指定時の動作
+        // 検証: 指定したディレクトリだけのスパースチェックアウトでクローンされ、未指定時は通常のクローンになる
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        git.set_default_branch("https://github.com/user/monorepo", "main");
+        git.set_default_branch("https://github.com/user/lib", "main");
+        let sparse = vec!["services/api".to_string(), "libs/common".to_string()];
+
+        let with = usecase.execute(
+            &config,
+            "https://github.com/user/monorepo",
+            &CloneOptions {
+                sparse: &sparse,
+                ..CloneOptions::default()
+            },
+        );
+        let without = usecase.execute(
+            &config,
+            "https://github.com/user/lib",
+            &CloneOptions::default(),
+        );
+
+        assert_eq!(git.get_sparse_paths(&with.unwrap()), Some(sparse));
+        assert_eq!(git.get_sparse_paths(&without.unwrap()), None);
+        assert_eq!(git.get_cloned_repos().len(), 2);
+    }
+
+    #[test]
+    fn test_clone_refresh_forgets_cached_default_branch() {
+        // 目的: --refresh 指定時の動作
+        // 検証: 展開後の URL のキャッシュが破棄され、未指定時は破棄されない
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+        git.set_default_branch("https://github.com/user/other", "main");
+
+        usecase
+            .execute(&config, "github.com/user/other", &CloneOptions::default())
+            .unwrap();
+        let result = usecase.execute(
+            &config,
+            "github.com/user/repo",
+            &CloneOptions {
+                refresh: true,
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(git.get_forgotten_default_branches(), vec![url.to_string()]);
+    }
+
+    #[test]
+    fn test_clone_runs_post_clone_hook() {
+        // 目的: クローン後フックの実行
+        // 検証: 設定のコマンドがクローン先をカレントディレクトリとしてシェル経由で実行される
+
+        let (git, fs, ui, mut config) = setup();
+        config.post_clone = Some("direnv allow".to_string());
+        let runner = Arc::new(MockCommandRunner::new());
+        let usecase = CloneRepositoryUseCase::new(git.clone(), fs, ui, runner.clone());
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+
+        let dest = usecase
+            .execute(&config, url, &CloneOptions::default())
+            .unwrap();
+
+        let commands = runner.get_commands();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].current_dir, dest);
+        assert_eq!(
+            commands[0].args.last().map(String::as_str),
+            Some("direnv allow")
+        );
+    }
+
+    #[test]
+    fn test_clone_post_clone_flag_overrides_config() {
+        // 目的: --post-clone による設定の上書き
+        // 検証: 引数のコマンドが設定より優先される
+
+        let (git, fs, ui, mut config) = setup();
+        config.post_clone = Some("direnv allow".to_string());
+        let runner = Arc::new(MockCommandRunner::new());
+        let usecase = CloneRepositoryUseCase::new(git.clone(), fs, ui, runner.clone());
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+
+        usecase
+            .execute(
+                &config,
+                url,
+                &CloneOptions {
+                    post_clone: Some("npm install"),
+                    ..CloneOptions::default()
+                },
+            )
+            .unwrap();
+
+        assert_eq!(
+            runner.get_commands()[0].args.last().map(String::as_str),
+            Some("npm install")
+        );
+    }
+
+    #[test]
+    fn test_clone_post_clone_failure_keeps_clone() {
+        // 目的: クローン後フックが失敗した場合の動作
+        // 検証: 警告が出力され、クローン自体は成功として扱われる
+
+        let (git, fs, ui, config) = setup();
+        let runner = Arc::new(MockCommandRunner::new());
+        runner.set_exit_code(1);
+        let usecase = CloneRepositoryUseCase::new(git.clone(), fs, ui.clone(), runner);
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+
+        let result = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                post_clone: Some("false"),
+                ..CloneOptions::default()
+            },
+        );
+
+        assert!(result.is_ok(), "clone failed: {:?}", result.err());
+        assert_eq!(git.get_cloned_repos().len(), 1);
+        assert_eq!(
+            ui.get_warning_messages(),
+            vec!["Post-clone hook exited with status 1".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_clone_without_post_clone_runs_nothing() {
+        // 目的: フック未設定時の動作
+        // 検証: コマンドが実行されない
+
+        let (git, fs, ui, config) = setup();
+        let runner = Arc::new(MockCommandRunner::new());
+        let usecase = CloneRepositoryUseCase::new(git.clone(), fs, ui, runner.clone());
+
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+
+        usecase
+            .execute(&config, url, &CloneOptions::default())
+            .unwrap();
+
+        assert!(runner.get_commands().is_empty());
+    }
+
+    #[test]
+    fn test_clone_falls_back_to_master() {
+        // 目的: デフォルトブランチを公開しないリモートからのクローン
+        // 検証: main が無ければ master が使われる
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs,
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let url = "https://github.com/user/repo";
+        git.add_remote_branch(url, "master");
+
+        let dest = usecase
+            .execute(&config, url, &CloneOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            dest,
+            PathBuf::from("/home/testuser/grm/github.com/user/repo+master")
+        );
+        assert_eq!(
+            ui.get_warning_messages(),
+            vec![format!(
+                "{url} does not advertise a default branch, using master"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_clone_fallback_prefers_main() {
+        // 目的: フォールバック候補の優先順位
+        // 検証: main と master の両方がある場合は main が使われる
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        let url = "https://github.com/user/repo";
+        git.add_remote_branch(url, "master");
+        git.add_remote_branch(url, "main");
+
+        let dest = usecase
+            .execute(&config, url, &CloneOptions::default())
+            .unwrap();
+
+        assert_eq!(
+            dest,
+            PathBuf::from("/home/testuser/grm/github.com/user/repo+main")
+        );
+    }
+
+    #[test]
+    fn test_clone_fallback_without_candidates() {
+        // 目的: フォールバック候補も存在しない場合
+        // 検証: 元の解析エラーが返され、クローンされない
+
+        let (git, fs, ui, config) = setup();
+        let usecase =
+            CloneRepositoryUseCase::new(git.clone(), fs, ui, Arc::new(MockCommandRunner::new()));
+
+        let url = "https://github.com/user/repo";
+        git.add_remote_branch(url, "develop");
+
+        let result = usecase.execute(&config, url, &CloneOptions::default());
+
+        assert!(matches!(result, Err(GrmError::Git(GitError::Parse(_)))));
+        assert!(git.get_cloned_repos().is_empty());
+    }
+
+    #[test]
+    fn test_clone_bare_and_mirror() {
+        // 目的: ワーキングツリーなしのクローン
+        // 検証: `{repo}.git` に --bare / --mirror でクローンされ、既存の場合はエラーになる
+
+        let (git, fs, ui, config) = setup();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        let bare = usecase.execute_bare(&config, "https://github.com/user/repo", false);
+        let mirror = usecase.execute_bare(&config, "https://github.com/user/cache.git", true);
+        fs.add_bare_repo("/home/testuser/grm/github.com/user/repo.git");
+        let existing = usecase.execute_bare(&config, "https://github.com/user/repo", true);
+
+        assert_eq!(
+            git.get_bare_clones(),
+            vec![
+                (
+                    "https://github.com/user/repo".to_string(),
+                    PathBuf::from("/home/testuser/grm/github.com/user/repo.git"),
+                    false,
+                ),
+                (
+                    "https://github.com/user/cache.git".to_string(),
+                    PathBuf::from("/home/testuser/grm/github.com/user/cache.git"),
+                    true,
+                ),
+            ]
+        );
+        assert!(bare.is_ok() && mirror.is_ok());
+        assert!(matches!(existing, Err(GrmError::AlreadyExists(_))));
+        assert!(git.get_cloned_repos().is_empty());
+    }
+
+    #[test]
+    fn test_clone_plain_paths_follow_layout() {
+        // 目的: レイアウトに従ったブランチ無しのパス
+        // 検証: --no-branch-suffix・--link・--bare は `{branch}` と直前の文字列を除いたパスを使う
+
+        let (git, fs, ui, mut config) = setup();
+        config.layout = Layout::parse("src/{host}/{user}/{repo}@{branch}").unwrap();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+        git.set_default_branch("https://github.com/user/repo", "main");
+        git.set_default_branch("https://github.com/user/plain", "main");
+
+        let linked = usecase
+            .execute(
+                &config,
+                "https://github.com/user/repo",
+                &CloneOptions {
+                    link: true,
+                    ..CloneOptions::default()
+                },
+            )
+            .unwrap();
+        let plain = usecase.execute(
+            &config,
+            "https://github.com/user/plain",
+            &CloneOptions {
+                target: CloneTarget::Plain,
+                ..CloneOptions::default()
+            },
+        );
+        let bare = usecase.execute(
+            &config,
+            "https://github.com/user/plain",
+            &CloneOptions {
+                target: CloneTarget::Bare,
+                ..CloneOptions::default()
+            },
+        );
+
+        let link = PathBuf::from("/home/testuser/grm/src/github.com/user/repo");
+        assert_eq!(fs.read_link(&link).unwrap(), linked);
+        assert_eq!(
+            plain.unwrap(),
+            PathBuf::from("/home/testuser/grm/src/github.com/user/plain")
+        );
+        assert_eq!(
+            bare.unwrap(),
+            PathBuf::from("/home/testuser/grm/src/github.com/user/plain.git")
+        );
+    }
+
+    #[test]
+    fn test_clone_plain_paths_rejected_without_directory() {
+        // 目的: ブランチ無しのパスを持たないレイアウト
+        // 検証: --no-branch-suffix と --bare はクローン前に拒否され、--link は警告だけでワークツリーの親を置き換えない
+
+        let (git, fs, ui, mut config) = setup();
+        config.layout = Layout::parse("{host}/{user}/{repo}/{branch}").unwrap();
+        let usecase = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+        let url = "https://github.com/user/repo";
+        git.set_default_branch(url, "main");
+
+        let plain = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                target: CloneTarget::Plain,
+                ..CloneOptions::default()
+            },
+        );
+        let bare = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                target: CloneTarget::Bare,
+                ..CloneOptions::default()
+            },
+        );
+        assert!(git.get_cloned_repos().is_empty());
+        let linked = usecase.execute(
+            &config,
+            url,
+            &CloneOptions {
+                link: true,
+                ..CloneOptions::default()
+            },
+        );
+
+        match plain {
+            Err(GrmError::UnsupportedLayout { flag, layout }) => {
+                assert_eq!(flag, "--no-branch-suffix");
+                assert_eq!(layout, "{host}/{user}/{repo}/{branch}");
+            }
+            other => panic!("Expected UnsupportedLayout error, got {other:?}"),
+        }
+        assert!(matches!(bare, Err(GrmError::UnsupportedLayout { .. })));
+        assert_eq!(
+            linked.unwrap(),
+            PathBuf::from("/home/testuser/grm/github.com/user/repo/main")
+        );
+        assert!(!fs.is_symlink(Path::new("/home/testuser/grm/github.com/user/repo")));
+        assert_eq!(ui.get_warning_messages().len(), 1);
+    }
+
+    #[test]
+    fn test_clone_list_remove_with_layout() {
+        // 目的: 設定したレイアウトでの clone・list・remove の一貫性
+        // 検証: `{host}/{user}/{repo}/{branch}` に従ってクローンされ、マーカーが無くても list と remove が同じパスを扱う
+
+        let (git, fs, ui, mut config) = setup();
+        config.layout = Layout::parse("{host}/{user}/{repo}/{branch}").unwrap();
+        git.set_default_branch("https://github.com/user/repo", "main");
+        let clone = CloneRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+        let list = ListRepositoriesUseCase::new(git.clone(), fs.clone(), ui.clone());
+        let remove = RemoveRepositoryUseCase::new(git.clone(), fs.clone(), ui.clone());
+
+        let cloned = clone.execute(
+            &config,
+            "https://github.com/user/repo",
+            &CloneOptions {
+                branch: Some("feature/foobar"),
+                ..CloneOptions::default()
+            },
+        );
+        let worktree = PathBuf::from("/home/testuser/grm/github.com/user/repo/feature/foobar");
+        // A clone made before markers existed is read from its path alone
+        fs.add_file(worktree.join(".git"));
+        fs.remove(&worktree.join(MARKER_DIR)).unwrap();
+        let listed = list.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Json,
+                ..ListOptions::default()
+            },
+        );
+        let removed = remove.execute(&config, "github.com/user/repo", None, true, false, false);
+
+        assert_eq!(cloned.unwrap(), worktree);
+        assert!(listed.is_ok(), "Failed with error: {:?}", listed.err());
+        let json = ui.get_printed_messages().join("\n");
+        assert!(json.contains("\"branch\": \"feature/foobar\""), "{json}");
+        assert!(json.contains("\"repo\": \"repo\""), "{json}");
+        assert_eq!(removed.unwrap().removed, 1);
+        assert!(!fs.exists(&worktree));
+        assert!(!fs.exists(Path::new("/home/testuser/grm/github.com")));
+    }
+}
@@ -0,0 +1,330 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::configs::{Config, ConfigError};
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::errors::GrmError;
+
+/// File written into the root to check that it is writable
+const PROBE_FILE: &str = ".grm-doctor";
+
+pub struct DoctorUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl DoctorUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Print a checklist of the environment grm depends on
+    ///
+    /// # Arguments
+    /// * `config` - Outcome of `Config::load`, reported instead of aborting on error
+    ///
+    /// # Returns
+    /// `GrmError::DoctorFailed` if any critical check failed
+    pub fn execute(&self, config: Result<&Config, &ConfigError>) -> Result<(), GrmError> {
+        let mut failed = 0;
+
+        match self.git.version() {
+            Ok(version) => self.pass(&format!("git {version}")),
+            Err(e) => {
+                self.fail(
+                    &format!("git is not available: {e}"),
+                    "Install git and make sure it is on PATH",
+                );
+                failed += 1;
+            }
+        }
+
+        let config = match config {
+            Ok(config) => {
+                self.pass(&format!("config loaded (root from {})", config.source));
+                config
+            }
+            Err(e) => {
+                self.fail(
+                    &format!("config could not be loaded: {e}"),
+                    "Fix the setting in ~/.grmrc, ~/.gitconfig or GRM_ROOT",
+                );
+                return Err(GrmError::DoctorFailed(failed + 1));
+            }
+        };
+
+        let root = config.root();
+        if !self.fs.is_dir(root) {
+            self.fail(
+                &format!("root directory does not exist: {}", root.display()),
+                "Create it with `grm root --create` or `grm init`",
+            );
+            return Err(GrmError::DoctorFailed(failed + 1));
+        }
+        self.pass(&format!("root directory exists: {}", root.display()));
+
+        if self.is_writable(root) {
+            self.pass("root directory is writable");
+        } else {
+            self.fail(
+                &format!("root directory is not writable: {}", root.display()),
+                "Check the permissions of the root directory",
+            );
+            failed += 1;
+        }
+
+        let shared = root.join(".shared");
+        if self.fs.is_dir(&shared) {
+            self.pass(&format!("shared storage exists: {}", shared.display()));
+        } else {
+            // Not an error: it is created by the first `worktree share`
+            self.ui
+                .print("[info] shared storage not created yet (created by `grm worktree share`)");
+        }
+
+        // Not critical either: grm works, only the link leads nowhere
+        let dangling = self.dangling_links(root, config.layout.depth());
+        if dangling.is_empty() {
+            self.pass("no dangling default-branch links");
+        }
+        for link in dangling {
+            self.warn(
+                &format!("dangling default-branch link: {}", link.display()),
+                "Re-run `grm clone --link` for the repository, or remove the link",
+            );
+        }
+
+        if failed > 0 {
+            return Err(GrmError::DoctorFailed(failed));
+        }
+
+        Ok(())
+    }
+
+    fn is_writable(&self, root: &Path) -> bool {
+        let probe = root.join(PROBE_FILE);
+        let writable = self.fs.write(&probe, "").is_ok();
+        if writable {
+            let _ = self.fs.remove(&probe);
+        }
+        writable
+    }
+
+    /// Symlinks down to `depth` levels below `root` whose target does not exist
+    ///
+    /// Worktrees and hidden directories such as `.shared` are not entered, so only
+    /// the `{host}/{user}/{repo}` links made by `--link` are found.
+    fn dangling_links(&self, root: &Path, depth: usize) -> Vec<PathBuf> {
+        let mut dangling = Vec::new();
+        let mut queue = vec![(root.to_path_buf(), 0)];
+        while let Some((dir, level)) = queue.pop() {
+            let Ok(children) = self.fs.read_dir(&dir) else {
+                continue;
+            };
+            for child in children {
+                if self.fs.is_symlink(&child) {
+                    let Ok(target) = self.fs.read_link(&child) else {
+                        continue;
+                    };
+                    // A relative target is relative to the directory of the link
+                    if !self.fs.exists(&dir.join(target)) {
+                        dangling.push(child);
+                    }
+                } else if level + 1 < depth
+                    && self.fs.is_dir(&child)
+                    && !self.fs.is_git_repository(&child)
+                    && !child
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+                {
+                    queue.push((child, level + 1));
+                }
+            }
+        }
+        dangling.sort();
+        dangling
+    }
+
+    fn pass(&self, message: &str) {
+        self.ui.print(&format!("[ok] {message}"));
+    }
+
+    // Failures go to stderr so they survive --quiet
+    fn fail(&self, message: &str, hint: &str) {
+        self.ui.print_error(&format!("[fail] {message}"));
+        self.ui.print_error(&format!("       hint: {hint}"));
+    }
+
+    /// Like `fail`, for problems which do not make doctor fail
+    fn warn(&self, message: &str, hint: &str) {
+        self.ui.print_error(&format!("[warn] {message}"));
+        self.ui.print_error(&format!("       hint: {hint}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::configs::ConfigSource;
+    use crate::core::ports::FileSystemError;
+    use std::path::PathBuf;
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        mock_fs.create_dir(Path::new("/test_root/.shared")).unwrap();
+        let config = Config {
+            source: ConfigSource::Grmrc,
+            ..Config::new(PathBuf::from("/test_root"))
+        };
+
+        (mock_git, mock_fs, mock_ui, config)
+    }
+
+    #[test]
+    fn test_doctor_all_checks_pass() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = DoctorUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Ok(&config));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_ui.has_printed("[ok] git 2.43.0"));
+        assert!(mock_ui.has_printed("[ok] config loaded (root from ~/.grmrc)"));
+        assert!(mock_ui.has_printed("[ok] root directory is writable"));
+        assert!(mock_ui.has_printed("[ok] shared storage exists: /test_root/.shared"));
+        assert!(mock_ui.has_printed("[ok] no dangling default-branch links"));
+        assert!(mock_ui.get_error_messages().is_empty());
+        assert!(!mock_fs.exists(Path::new("/test_root/.grm-doctor")));
+    }
+
+    #[test]
+    fn test_doctor_git_missing_and_root_not_writable() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.set_git_missing();
+        mock_fs.inject_error(FileSystemError::PathError("Permission denied".to_string()));
+        let usecase = DoctorUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Ok(&config));
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::DoctorFailed(2))));
+        let errors = mock_ui.get_error_messages();
+        assert!(
+            errors
+                .iter()
+                .any(|m| m.starts_with("[fail] git is not available"))
+        );
+        assert!(
+            errors.contains(&"       hint: Install git and make sure it is on PATH".to_string())
+        );
+        assert!(errors.contains(&"[fail] root directory is not writable: /test_root".to_string()));
+    }
+
+    #[test]
+    fn test_doctor_missing_root() {
+        // Arrange
+        let (mock_git, _, mock_ui, config) = setup();
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let usecase = DoctorUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Ok(&config));
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::DoctorFailed(1))));
+        assert!(
+            mock_ui
+                .get_error_messages()
+                .contains(&"[fail] root directory does not exist: /test_root".to_string())
+        );
+    }
+
+    #[test]
+    fn test_doctor_shared_not_created_is_not_critical() {
+        // Arrange
+        let (mock_git, _, mock_ui, config) = setup();
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        let usecase = DoctorUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Ok(&config));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_ui.has_printed(
+            "[info] shared storage not created yet (created by `grm worktree share`)"
+        ));
+    }
+
+    #[test]
+    fn test_doctor_dangling_default_branch_link() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        for dir in ["/test_root/github.com", "/test_root/github.com/user"] {
+            mock_fs.add_dir(dir);
+        }
+        mock_fs.add_git_repo("/test_root/github.com/user/live+main");
+        mock_fs.add_symlink(
+            "/test_root/github.com/user/live",
+            "/test_root/github.com/user/live+main",
+        );
+        mock_fs.add_symlink("/test_root/github.com/user/gone", "gone+main");
+        let usecase = DoctorUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Ok(&config));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_error_messages(),
+            vec![
+                "[warn] dangling default-branch link: /test_root/github.com/user/gone".to_string(),
+                "       hint: Re-run `grm clone --link` for the repository, or remove the link"
+                    .to_string(),
+            ]
+        );
+        assert!(!mock_ui.has_printed("[ok] no dangling default-branch links"));
+    }
+
+    #[test]
+    fn test_doctor_config_error() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, _) = setup();
+        let error = ConfigError::Parse("Failed to parse .grmrc: expected `=`".to_string());
+        let usecase = DoctorUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(Err(&error));
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::DoctorFailed(1))));
+        assert!(mock_ui.has_printed("[ok] git 2.43.0"));
+        assert!(
+            mock_ui
+                .get_error_messages()
+                .iter()
+                .any(|m| m.contains("Failed to parse .grmrc"))
+        );
+    }
+}
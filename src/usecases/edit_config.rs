@@ -0,0 +1,179 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::configs::{Config, ConfigError, grmrc_value};
+use crate::core::ports::{FileSystem, UserInteraction};
+use crate::errors::GrmError;
+
+pub struct EditConfigUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl EditConfigUseCase {
+    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
+        Self { fs, ui }
+    }
+
+    /// Print the effective value of `key`, wherever it was configured
+    ///
+    /// Nothing is printed for an optional setting which is not set.
+    pub fn get(&self, config: &Config, key: &str) -> Result<(), GrmError> {
+        if let Some(value) = config.get(key)? {
+            self.ui.print_result(&value);
+        }
+        Ok(())
+    }
+
+    /// Set `key` to `value` in `~/.grmrc`, keeping the other keys
+    ///
+    /// The file is created if it does not exist.
+    /// A `root` relative to the current directory or starting with `~` is stored as an absolute path.
+    pub fn set(&self, key: &str, value: &str) -> Result<(), GrmError> {
+        let value = if key == "root" {
+            let root = self
+                .fs
+                .normalize(Path::new(value), &self.fs.current_dir()?)?;
+            grmrc_value(key, &root.display().to_string())?
+        } else {
+            grmrc_value(key, value)?
+        };
+
+        let grmrc_path = self.fs.home_dir()?.join(".grmrc");
+        let mut table = if self.fs.exists(&grmrc_path) {
+            self.fs
+                .read_to_string(&grmrc_path)?
+                .parse::<toml::Table>()
+                .map_err(|e| ConfigError::Parse(format!("Failed to parse .grmrc: {e}")))?
+        } else {
+            toml::Table::new()
+        };
+        table.insert(key.to_string(), value.clone());
+
+        let content = toml::to_string(&table)
+            .map_err(|e| ConfigError::Parse(format!("Failed to write .grmrc: {e}")))?;
+        self.fs.write(&grmrc_path, &content)?;
+
+        self.ui
+            .print(&format!("Set {key} = {value} in {}", grmrc_path.display()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+    use std::path::PathBuf;
+
+    const GRMRC: &str = "/home/testuser/.grmrc";
+
+    #[test]
+    fn test_set_root_normalizes_and_keeps_other_keys() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_file_with_content(
+            GRMRC,
+            "root = \"/old\"\ndefault_host = \"github.com\"\nclone_depth = 1\n",
+        );
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = EditConfigUseCase::new(mock_fs.clone(), mock_ui.clone());
+
+        // Act
+        let result = usecase.set("root", "~/code");
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let table = mock_fs
+            .read_to_string(Path::new(GRMRC))
+            .unwrap()
+            .parse::<toml::Table>()
+            .unwrap();
+        assert_eq!(table["root"].as_str(), Some("/home/testuser/code"));
+        assert_eq!(table["default_host"].as_str(), Some("github.com"));
+        assert_eq!(table["clone_depth"].as_integer(), Some(1));
+        assert!(mock_ui.has_printed("Set root = \"/home/testuser/code\""));
+    }
+
+    #[test]
+    fn test_set_creates_grmrc() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/home/testuser");
+        let usecase = EditConfigUseCase::new(mock_fs.clone(), Arc::new(MockUserInteraction::new()));
+
+        // Act
+        let result = usecase
+            .set("share_deny", "*.pem, .env")
+            .and_then(|()| usecase.set("default_link", "true"));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let table = mock_fs
+            .read_to_string(Path::new(GRMRC))
+            .unwrap()
+            .parse::<toml::Table>()
+            .unwrap();
+        assert_eq!(
+            table["share_deny"],
+            toml::Value::Array(vec!["*.pem".into(), ".env".into()])
+        );
+        assert_eq!(table["default_link"].as_bool(), Some(true));
+    }
+
+    #[test]
+    fn test_set_rejects_unknown_key_and_invalid_value() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_file_with_content(GRMRC, "root = \"/old\"\n");
+        let usecase = EditConfigUseCase::new(mock_fs.clone(), Arc::new(MockUserInteraction::new()));
+
+        // Act
+        let unknown = usecase.set("roots", "/new");
+        let invalid = usecase.set("clone_depth", "shallow");
+        let layout = usecase.set("layout", "{host}/{user}/{repo}");
+
+        // Assert
+        assert!(matches!(
+            unknown,
+            Err(GrmError::Config(ConfigError::UnknownKey(_)))
+        ));
+        assert!(matches!(
+            invalid,
+            Err(GrmError::Config(ConfigError::InvalidValue { .. }))
+        ));
+        assert!(matches!(
+            layout,
+            Err(GrmError::Config(ConfigError::InvalidValue { ref reason, .. }))
+                if reason.contains("{branch}")
+        ));
+        assert_eq!(
+            mock_fs.read_to_string(Path::new(GRMRC)).unwrap(),
+            "root = \"/old\"\n"
+        );
+    }
+
+    #[test]
+    fn test_get_prints_effective_value() {
+        // Arrange
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = EditConfigUseCase::new(Arc::new(MockFileSystem::new()), mock_ui.clone());
+        let config = Config {
+            clone_depth: Some(1),
+            ..Config::new(PathBuf::from("/test_root"))
+        };
+
+        // Act
+        let result = usecase
+            .get(&config, "root")
+            .and_then(|()| usecase.get(&config, "clone_depth"))
+            .and_then(|()| usecase.get(&config, "default_host"));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec!["/test_root".to_string(), "1".to_string()]
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{CommandRunner, FileSystem, OutputStream, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+
+pub struct ExecUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl ExecUseCase {
+    pub fn new(
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+        runner: Arc<dyn CommandRunner>,
+    ) -> Self {
+        Self { fs, ui, runner }
+    }
+
+    /// Run `command` in every worktree of the repository at `url`
+    ///
+    /// Output lines are prefixed with the worktree path relative to the root.
+    ///
+    /// # Arguments
+    /// * `command` - Program followed by its arguments
+    /// * `fail_fast` - Stop at the first worktree where the command fails
+    pub fn execute(
+        &self,
+        config: &Config,
+        url: &str,
+        command: &[String],
+        fail_fast: bool,
+    ) -> Result<(), GrmError> {
+        let Some((program, args)) = command.split_first() else {
+            return Err(GrmError::NotFound("No command given".to_string()));
+        };
+
+        let root = config.root();
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+
+        let scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
+        let mut worktrees = scanner.scan_worktrees(root, &repo_info)?;
+        worktrees.sort();
+
+        if worktrees.is_empty() {
+            let searched_path = root.join(&repo_info.host).join(&repo_info.user);
+            return Err(GrmError::UnmanagedRepository {
+                url,
+                searched_path: searched_path.display().to_string(),
+            });
+        }
+
+        let total = worktrees.len();
+        let mut failed = 0;
+        for worktree in worktrees {
+            let prefix = worktree
+                .strip_prefix(root)
+                .unwrap_or(&worktree)
+                .display()
+                .to_string();
+
+            let result = self
+                .runner
+                .run_captured(program, args, &worktree, &mut |stream, line| match stream {
+                    OutputStream::Stdout => self.ui.print_result(&format!("[{prefix}] {line}")),
+                    OutputStream::Stderr => self.ui.print_error(&format!("[{prefix}] {line}")),
+                });
+
+            match result {
+                Ok(0) => continue,
+                Ok(code) => {
+                    self.ui
+                        .print_error(&format!("[{prefix}] {program} exited with status {code}"));
+                }
+                Err(e) => self.ui.print_error(&format!("[{prefix}] {e}")),
+            }
+
+            failed += 1;
+            if fail_fast {
+                break;
+            }
+        }
+
+        if failed > 0 {
+            return Err(GrmError::ExecFailed { failed, total });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockCommandRunner, MockFileSystem, MockUserInteraction};
+    use std::path::PathBuf;
+
+    const URL: &str = "https://github.com/user/repo";
+
+    fn setup() -> (
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Arc<MockCommandRunner>,
+        Config,
+    ) {
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let runner = Arc::new(MockCommandRunner::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        fs.add_git_repo("/test_root/github.com/user/other+main");
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (fs, ui, runner, config)
+    }
+
+    fn command() -> Vec<String> {
+        vec!["git".to_string(), "pull".to_string()]
+    }
+
+    #[test]
+    fn test_exec_runs_in_each_worktree() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        runner.add_output(OutputStream::Stdout, "Already up to date.");
+        runner.add_output(OutputStream::Stderr, "warning");
+        let usecase = ExecUseCase::new(fs, ui.clone(), runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL, &command(), false);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let dirs = runner
+            .get_commands()
+            .into_iter()
+            .map(|c| c.current_dir)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dirs,
+            vec![
+                PathBuf::from("/test_root/github.com/user/repo+feature"),
+                PathBuf::from("/test_root/github.com/user/repo+main"),
+            ]
+        );
+        assert_eq!(runner.get_commands()[0].args, vec!["pull".to_string()]);
+        assert!(ui.has_printed("[github.com/user/repo+main] Already up to date."));
+        assert!(
+            ui.get_error_messages()
+                .contains(&"[github.com/user/repo+feature] warning".to_string())
+        );
+    }
+
+    #[test]
+    fn test_exec_reports_failures() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        runner.set_exit_code_at("/test_root/github.com/user/repo+feature", 1);
+        let usecase = ExecUseCase::new(fs, ui.clone(), runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL, &command(), false);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(GrmError::ExecFailed {
+                failed: 1,
+                total: 2
+            })
+        ));
+        assert_eq!(runner.get_commands().len(), 2);
+        assert!(
+            ui.get_error_messages()
+                .contains(&"[github.com/user/repo+feature] git exited with status 1".to_string())
+        );
+    }
+
+    #[test]
+    fn test_exec_fail_fast() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        runner.set_exit_code(2);
+        let usecase = ExecUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL, &command(), true);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(GrmError::ExecFailed {
+                failed: 1,
+                total: 2
+            })
+        ));
+        assert_eq!(runner.get_commands().len(), 1);
+    }
+
+    #[test]
+    fn test_exec_unmanaged_repository() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        let usecase = ExecUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/missing",
+            &command(),
+            false,
+        );
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UnmanagedRepository { .. })));
+        assert!(runner.get_commands().is_empty());
+    }
+}
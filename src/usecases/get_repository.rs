@@ -0,0 +1,305 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{Clock, CommandRunner, FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+use crate::usecases::{CloneOptions, CloneRepositoryUseCase, SplitWorktreeUseCase};
+
+pub struct GetRepositoryUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+    runner: Arc<dyn CommandRunner>,
+    retries: Option<(u32, Arc<dyn Clock>)>,
+}
+
+impl GetRepositoryUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+        runner: Arc<dyn CommandRunner>,
+    ) -> Self {
+        Self {
+            git,
+            fs,
+            ui,
+            runner,
+            retries: None,
+        }
+    }
+
+    /// Retry the clone like `CloneRepositoryUseCase::with_retries`
+    #[must_use]
+    pub fn with_retries(mut self, retries: u32, clock: Arc<dyn Clock>) -> Self {
+        self.retries = Some((retries, clock));
+        self
+    }
+
+    /// Make sure a worktree for `branch` of `url` exists and print its path
+    ///
+    /// - No worktree of the repository exists: clone the branch like `grm clone`
+    /// - The repository exists but not the branch: add a worktree to the existing clone
+    /// - The worktree already exists: nothing to do
+    ///
+    /// The path is printed with `print_result`, so the output can be used as
+    /// `cd $(grm get ...)` once `print` goes to stderr.
+    pub fn execute(
+        &self,
+        config: &Config,
+        url: &str,
+        branch: Option<&str>,
+    ) -> Result<PathBuf, GrmError> {
+        let root = config.root();
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+
+        let mut clone = CloneRepositoryUseCase::new(
+            Arc::clone(&self.git),
+            Arc::clone(&self.fs),
+            Arc::clone(&self.ui),
+            Arc::clone(&self.runner),
+        );
+        if let Some((retries, clock)) = &self.retries {
+            clone = clone.with_retries(*retries, Arc::clone(clock));
+        }
+
+        let branch_name = if let Some(b) = branch {
+            b.to_string()
+        } else {
+            clone.default_branch(&url)?
+        };
+        RepoInfo::validate_branch_name(&branch_name)?;
+
+        let dest_path = repo_info.build_repo_path_with(root, &branch_name, &config.layout);
+
+        if !self.fs.exists(&dest_path) {
+            let worktrees = if self.fs.exists(root) {
+                let scanner =
+                    RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
+                scanner.scan_worktrees(root, &repo_info)?
+            } else {
+                Vec::new()
+            };
+
+            if let Some(existing) = worktrees.first() {
+                let remote_url = self
+                    .git
+                    .get_remote_url(existing, None)
+                    .unwrap_or_else(|_| url.clone());
+                let splitter = SplitWorktreeUseCase::new(
+                    Arc::clone(&self.git),
+                    Arc::clone(&self.fs),
+                    Arc::clone(&self.ui),
+                );
+                splitter.split(config, existing, None, &remote_url, &branch_name, &[], None)?;
+            } else {
+                let options = CloneOptions {
+                    branch: Some(&branch_name),
+                    ..CloneOptions::default()
+                };
+                clone.execute(config, &url, &options)?;
+            }
+        }
+
+        self.ui.print_result(&dest_path.display().to_string());
+
+        Ok(dest_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::StderrInteraction;
+    use crate::adapters::test_helpers::{
+        MockClock, MockCommandRunner, MockFileSystem, MockGitRepository, MockUserInteraction,
+    };
+    use crate::core::RepoMarker;
+    use crate::core::ports::GitError;
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let git = Arc::new(MockGitRepository::new());
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+
+        fs.add_dir("/test_root");
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (git, fs, ui, config)
+    }
+
+    #[test]
+    fn test_get_clones_when_missing() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        // As wired by `grm get`, progress goes to stderr
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            Arc::new(StderrInteraction::new(ui.clone())),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        // Act
+        let result = usecase.execute(&config, "https://github.com/user/repo", Some("dev"));
+
+        // Assert
+        let dest = result.unwrap();
+        assert_eq!(dest, PathBuf::from("/test_root/github.com/user/repo+dev"));
+        assert_eq!(
+            git.get_cloned_repos(),
+            vec![("https://github.com/user/repo".to_string(), dest.clone())]
+        );
+        assert!(git.get_worktrees().is_empty());
+        assert!(RepoMarker::load(fs.as_ref(), &dest).is_some());
+        assert_eq!(ui.get_printed_messages(), vec![dest.display().to_string()]);
+        assert_eq!(
+            ui.get_error_messages(),
+            vec![format!("Repository cloned to: {}", dest.display())]
+        );
+    }
+
+    #[test]
+    fn test_get_clone_falls_back_to_master() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        let url = "https://github.com/user/repo";
+        git.add_remote_branch(url, "master");
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        // Act
+        let result = usecase.execute(&config, url, None);
+
+        // Assert
+        let dest = result.unwrap();
+        assert_eq!(
+            dest,
+            PathBuf::from("/test_root/github.com/user/repo+master")
+        );
+        assert_eq!(
+            git.get_cloned_repos(),
+            vec![(url.to_string(), dest.clone())]
+        );
+    }
+
+    #[test]
+    fn test_get_clone_retries_network_failures() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        git.set_partial_clone(fs.clone());
+        git.fail_next_clones([GitError::Failed {
+            status: 128,
+            stderr: "fatal: unable to access 'https://github.com/user/repo/': Could not resolve host: github.com".into(),
+        }]);
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        )
+        .with_retries(1, Arc::new(MockClock::new()));
+
+        // Act
+        let result = usecase.execute(&config, "https://github.com/user/repo", Some("dev"));
+
+        // Assert
+        let dest = result.unwrap();
+        assert_eq!(
+            git.get_cloned_repos(),
+            vec![("https://github.com/user/repo".to_string(), dest)]
+        );
+        assert_eq!(ui.get_warning_messages().len(), 1);
+    }
+
+    #[test]
+    fn test_get_writes_repository_markers() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        let url = "https://github.com/user/my+repo";
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        // Act
+        let cloned = usecase.execute(&config, url, Some("main")).unwrap();
+        let split = usecase.execute(&config, url, Some("dev")).unwrap();
+
+        // Assert
+        for (path, branch) in [(cloned, "main"), (split, "dev")] {
+            let marker = RepoMarker::load(fs.as_ref(), &path).unwrap();
+            assert_eq!(marker.repo, "my+repo");
+            assert_eq!(marker.branch.as_deref(), Some(branch));
+        }
+    }
+
+    #[test]
+    fn test_get_splits_existing_repository() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        git.set_remote_url(
+            "/test_root/github.com/user/repo+main",
+            "https://github.com/user/repo",
+        );
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        // Act
+        let result = usecase.execute(&config, "git@github.com:user/repo.git", Some("dev"));
+
+        // Assert
+        let dest = result.unwrap();
+        assert_eq!(dest, PathBuf::from("/test_root/github.com/user/repo+dev"));
+        assert!(git.get_cloned_repos().is_empty());
+        assert_eq!(git.get_worktrees(), vec![dest.clone()]);
+        assert_eq!(ui.get_printed_messages(), vec![dest.display().to_string()]);
+    }
+
+    #[test]
+    fn test_get_existing_worktree_prints_path() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        git.set_default_branch("https://github.com/user/repo", "main");
+        let usecase = GetRepositoryUseCase::new(
+            git.clone(),
+            fs.clone(),
+            ui.clone(),
+            Arc::new(MockCommandRunner::new()),
+        );
+
+        // Act
+        let result = usecase.execute(&config, "https://github.com/user/repo", None);
+
+        // Assert
+        let dest = result.unwrap();
+        assert_eq!(dest, PathBuf::from("/test_root/github.com/user/repo+main"));
+        assert!(git.get_cloned_repos().is_empty());
+        assert!(git.get_worktrees().is_empty());
+        assert_eq!(ui.get_printed_messages(), vec![dest.display().to_string()]);
+    }
+}
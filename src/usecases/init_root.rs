@@ -0,0 +1,345 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::configs::{Config, grmrc_template};
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+
+/// Outcome of adopting the repositories found under `--adopt` directories
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AdoptSummary {
+    /// Original path and its new managed path
+    pub adopted: Vec<(PathBuf, PathBuf)>,
+    /// Path and the reason it was left in place
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Path and the existing managed path it collides with
+    pub conflicts: Vec<(PathBuf, PathBuf)>,
+}
+
+pub struct InitRootUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl InitRootUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Create the root and `~/.grmrc`, then move repositories under `adopt` into the root
+    ///
+    /// An existing root or config is left untouched, so this can be run again.
+    ///
+    /// # Arguments
+    /// * `root` - Root to create, the configured root if `None`
+    /// * `adopt` - Directories to search for repositories to adopt
+    pub fn execute(
+        &self,
+        config: &Config,
+        root: Option<&str>,
+        adopt: &[String],
+    ) -> Result<AdoptSummary, GrmError> {
+        let current_dir = self.fs.current_dir()?;
+        let root = match root {
+            Some(root) => self.fs.normalize(Path::new(root), &current_dir)?,
+            None => config.root().to_path_buf(),
+        };
+
+        if self.fs.exists(&root) {
+            self.ui
+                .print(&format!("Root already exists: {}", root.display()));
+        } else {
+            self.fs.create_dir(&root)?;
+            self.ui.print(&format!("Created root: {}", root.display()));
+        }
+
+        let grmrc_path = self.fs.home_dir()?.join(".grmrc");
+        if self.fs.exists(&grmrc_path) {
+            self.ui.print(&format!(
+                "Config already exists: {} (left unchanged)",
+                grmrc_path.display()
+            ));
+        } else {
+            self.fs.write(&grmrc_path, &grmrc_template(&root))?;
+            self.ui
+                .print(&format!("Wrote config: {}", grmrc_path.display()));
+        }
+
+        let mut summary = AdoptSummary::default();
+        for dir in adopt {
+            let dir = self.fs.normalize(Path::new(dir), &current_dir)?;
+            for repo in self.find_repositories(&dir)? {
+                self.adopt(config, &root, &repo, &mut summary)?;
+            }
+        }
+
+        if !adopt.is_empty() {
+            self.print_summary(&summary);
+        }
+
+        Ok(summary)
+    }
+
+    fn find_repositories(&self, dir: &Path) -> Result<Vec<PathBuf>, GrmError> {
+        if self.fs.is_git_repository(dir) {
+            return Ok(vec![dir.to_path_buf()]);
+        }
+
+        let mut repos = RepoScanner::new(Arc::clone(&self.fs)).scan_repositories(dir)?;
+        repos.sort();
+        Ok(repos)
+    }
+
+    /// Move `repo` to `{root}/{host}/{user}/{repo}+{branch}`, or the configured layout, recording the outcome
+    fn adopt(
+        &self,
+        config: &Config,
+        root: &Path,
+        repo: &Path,
+        summary: &mut AdoptSummary,
+    ) -> Result<(), GrmError> {
+        let Ok(remote_url) = self.git.get_remote_url(repo, None) else {
+            summary
+                .skipped
+                .push((repo.to_path_buf(), "no remote".to_string()));
+            return Ok(());
+        };
+
+        let repo_info = match RepoInfo::from_url_with(&remote_url, config.normalize_host) {
+            Ok(info) => info,
+            Err(e) => {
+                summary.skipped.push((repo.to_path_buf(), e.to_string()));
+                return Ok(());
+            }
+        };
+
+        let worktrees = self.git.worktree_details(repo)?;
+        if worktrees.len() > 1 {
+            // Moving the main worktree would break the links of the others
+            summary
+                .skipped
+                .push((repo.to_path_buf(), "has linked worktrees".to_string()));
+            return Ok(());
+        }
+
+        let Some(branch) = worktrees
+            .into_iter()
+            .find(|worktree| worktree.path == repo)
+            .and_then(|worktree| worktree.branch)
+        else {
+            summary
+                .skipped
+                .push((repo.to_path_buf(), "detached HEAD".to_string()));
+            return Ok(());
+        };
+
+        let dest_path = repo_info.build_repo_path_with(root, &branch, &config.layout);
+        if dest_path == repo {
+            summary
+                .skipped
+                .push((repo.to_path_buf(), "already managed".to_string()));
+            return Ok(());
+        }
+        if self.fs.exists(&dest_path) {
+            summary.conflicts.push((repo.to_path_buf(), dest_path));
+            return Ok(());
+        }
+
+        if let Some(parent) = dest_path.parent() {
+            self.fs.create_dir(parent)?;
+        }
+        self.fs.move_path(repo, &dest_path)?;
+        summary.adopted.push((repo.to_path_buf(), dest_path));
+
+        Ok(())
+    }
+
+    fn print_summary(&self, summary: &AdoptSummary) {
+        for (from, to) in &summary.adopted {
+            self.ui
+                .print(&format!("Adopted: {} -> {}", from.display(), to.display()));
+        }
+        for (path, reason) in &summary.skipped {
+            self.ui
+                .print(&format!("Skipped: {} ({reason})", path.display()));
+        }
+        for (path, existing) in &summary.conflicts {
+            self.ui.print(&format!(
+                "Conflict: {} ({} already exists)",
+                path.display(),
+                existing.display()
+            ));
+        }
+
+        self.ui.print(&format!(
+            "{} adopted, {} skipped, {} conflicting",
+            summary.adopted.len(),
+            summary.skipped.len(),
+            summary.conflicts.len()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::ports::WorktreeInfo;
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let git = Arc::new(MockGitRepository::new());
+        let mut fs = MockFileSystem::new();
+        fs.set_home_dir("/home/testuser");
+        fs.add_dir("/home/testuser");
+        fs.set_current_dir("/home/testuser");
+        let fs = Arc::new(fs);
+        let ui = Arc::new(MockUserInteraction::new());
+
+        let config = Config::new(PathBuf::from("/home/testuser/grm"));
+
+        (git, fs, ui, config)
+    }
+
+    fn add_repo(git: &MockGitRepository, fs: &MockFileSystem, path: &str, branch: &str) {
+        fs.add_git_repo(path);
+        git.add_worktree_detail_at(
+            path,
+            WorktreeInfo {
+                path: PathBuf::from(path),
+                branch: Some(branch.to_string()),
+                ..WorktreeInfo::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_init_creates_root_and_config() {
+        // 目的: ルートと設定ファイルの作成
+        // 検証: ルートが作成され、指定ルートを含む .grmrc が書き込まれる
+
+        let (git, fs, ui, config) = setup();
+        let usecase = InitRootUseCase::new(git.clone(), fs.clone(), ui.clone());
+
+        let result = usecase.execute(&config, Some("~/repos"), &[]);
+
+        assert!(result.is_ok(), "init failed: {:?}", result.err());
+        assert!(fs.is_dir(Path::new("/home/testuser/repos")));
+        let content = fs
+            .read_to_string(Path::new("/home/testuser/.grmrc"))
+            .unwrap();
+        let parsed: toml::Table = toml::from_str(&content).unwrap();
+        assert_eq!(parsed["root"].as_str(), Some("/home/testuser/repos"));
+    }
+
+    #[test]
+    fn test_init_leaves_existing_root_and_config() {
+        // 目的: 再実行時の既存ルートと設定ファイルの扱い
+        // 検証: 既存の設定ファイルは変更されず、通知が表示される
+
+        let (git, fs, ui, config) = setup();
+        fs.add_dir("/home/testuser/grm");
+        fs.add_file_with_content("/home/testuser/.grmrc", "root = \"~/grm\"\n");
+        let usecase = InitRootUseCase::new(git.clone(), fs.clone(), ui.clone());
+
+        let result = usecase.execute(&config, None, &[]);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            fs.read_to_string(Path::new("/home/testuser/.grmrc"))
+                .unwrap(),
+            "root = \"~/grm\"\n"
+        );
+        let messages = ui.get_printed_messages();
+        assert!(messages.iter().any(|m| m.contains("Root already exists")));
+        assert!(messages.iter().any(|m| m.contains("left unchanged")));
+    }
+
+    #[test]
+    fn test_init_adopts_repositories() {
+        // 目的: 既存リポジトリの取り込み
+        // 検証: リモートのあるリポジトリは管理構造へ移動し、それ以外はスキップ・衝突として報告される
+
+        let (git, fs, ui, config) = setup();
+        fs.add_dir("/home/testuser/src");
+        add_repo(&git, &fs, "/home/testuser/src/app", "main");
+        git.set_remote_url("/home/testuser/src/app", "https://github.com/user/app.git");
+        add_repo(&git, &fs, "/home/testuser/src/scratch", "main");
+        add_repo(&git, &fs, "/home/testuser/src/lib", "develop");
+        git.set_remote_url("/home/testuser/src/lib", "git@github.com:user/lib.git");
+        fs.add_git_repo("/home/testuser/grm/github.com/user/lib+develop");
+
+        let usecase = InitRootUseCase::new(git.clone(), fs.clone(), ui.clone());
+
+        let summary = usecase
+            .execute(&config, None, &["src".to_string()])
+            .unwrap();
+
+        assert_eq!(
+            summary.adopted,
+            vec![(
+                PathBuf::from("/home/testuser/src/app"),
+                PathBuf::from("/home/testuser/grm/github.com/user/app+main")
+            )]
+        );
+        assert_eq!(
+            summary.skipped,
+            vec![(
+                PathBuf::from("/home/testuser/src/scratch"),
+                "no remote".to_string()
+            )]
+        );
+        assert_eq!(
+            summary.conflicts,
+            vec![(
+                PathBuf::from("/home/testuser/src/lib"),
+                PathBuf::from("/home/testuser/grm/github.com/user/lib+develop")
+            )]
+        );
+        assert!(fs.is_git_repository(Path::new("/home/testuser/grm/github.com/user/app+main")));
+        assert!(!fs.exists(Path::new("/home/testuser/src/app")));
+        assert!(fs.exists(Path::new("/home/testuser/src/lib")));
+        assert!(
+            ui.get_printed_messages()
+                .contains(&"1 adopted, 1 skipped, 1 conflicting".to_string())
+        );
+    }
+
+    #[test]
+    fn test_init_skips_repository_with_linked_worktrees() {
+        // 目的: リンクされたワークツリーを持つリポジトリの扱い
+        // 検証: 移動せずにスキップされる
+
+        let (git, fs, ui, config) = setup();
+        add_repo(&git, &fs, "/home/testuser/src/app", "main");
+        git.set_remote_url("/home/testuser/src/app", "https://github.com/user/app.git");
+        git.add_worktree_detail_at(
+            "/home/testuser/src/app",
+            WorktreeInfo {
+                path: PathBuf::from("/home/testuser/src/app-feature"),
+                branch: Some("feature".to_string()),
+                ..WorktreeInfo::default()
+            },
+        );
+
+        let usecase = InitRootUseCase::new(git.clone(), fs.clone(), ui.clone());
+
+        let summary = usecase
+            .execute(&config, None, &["/home/testuser/src/app".to_string()])
+            .unwrap();
+
+        assert!(summary.adopted.is_empty());
+        assert_eq!(summary.skipped[0].1, "has linked worktrees");
+        assert!(fs.exists(Path::new("/home/testuser/src/app")));
+    }
+}
@@ -1,57 +1,359 @@
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::sync::Arc;
 
+use glob::{MatchOptions, Pattern};
+use serde::Serialize;
+
 use crate::configs::Config;
-use crate::core::RepoScanner;
-use crate::core::ports::{FileSystem, UserInteraction};
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoEntry, RepoScanner};
 use crate::errors::GrmError;
+use crate::usecases::show_stats::format_size;
+
+/// A repository in the `grm list --json` output
+///
+/// Repositories outside the managed layout have no `host`, `user` and `repo`.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct RepositoryListing {
+    pub host: Option<String>,
+    pub user: Option<String>,
+    pub repo: Option<String>,
+    pub branch: Option<String>,
+    /// Branch actually checked out, only filled with `--show-branch`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_branch: Option<String>,
+    /// Disk usage in bytes, only filled with `--size`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    pub path: String,
+}
+
+impl From<RepoEntry> for RepositoryListing {
+    fn from(entry: RepoEntry) -> Self {
+        let path = entry.path.display().to_string();
+        match entry.info {
+            Some(info) => Self {
+                host: Some(info.host),
+                user: Some(info.user),
+                repo: Some(info.repo),
+                branch: info.branch,
+                current_branch: None,
+                size: None,
+                path,
+            },
+            None => Self {
+                host: None,
+                user: None,
+                repo: None,
+                branch: None,
+                current_branch: None,
+                size: None,
+                path,
+            },
+        }
+    }
+}
+
+/// A field of a repository substituted into a `grm list --format` template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Host,
+    User,
+    Repo,
+    Branch,
+    Path,
+    RelPath,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(Field),
+}
+
+/// Parsed `grm list --format` template, e.g. `{user}/{repo}@{branch}`
+///
+/// `{{` and `}}` stand for literal braces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListFormat(Vec<Segment>);
+
+impl ListFormat {
+    /// Parse `template`, rejecting unknown placeholders and unbalanced braces
+    pub fn parse(template: &str) -> Result<Self, GrmError> {
+        let invalid = |reason: String| GrmError::InvalidFormat {
+            format: template.to_string(),
+            reason,
+        };
+
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| invalid("unclosed `{`".to_string()))?;
+                    let field = match &rest[..end] {
+                        "host" => Field::Host,
+                        "user" => Field::User,
+                        "repo" => Field::Repo,
+                        "branch" => Field::Branch,
+                        "path" => Field::Path,
+                        "relpath" => Field::RelPath,
+                        name => {
+                            return Err(invalid(format!(
+                                "unknown placeholder `{{{name}}}` (expected host, user, repo, branch, path or relpath)"
+                            )));
+                        }
+                    };
+                    chars = rest[end + 1..].chars();
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(field));
+                }
+                '}' => return Err(invalid("unmatched `}`".to_string())),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self(segments))
+    }
+
+    /// Fill in the fields of `entry`, which are empty if it is outside the managed layout
+    fn render(&self, root: &Path, entry: &RepoEntry) -> String {
+        let info = entry.info.as_ref();
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Field(Field::Host) => info.map(|i| i.host.clone()).unwrap_or_default(),
+                Segment::Field(Field::User) => info.map(|i| i.user.clone()).unwrap_or_default(),
+                Segment::Field(Field::Repo) => info.map(|i| i.repo.clone()).unwrap_or_default(),
+                Segment::Field(Field::Branch) => {
+                    info.and_then(|i| i.branch.clone()).unwrap_or_default()
+                }
+                Segment::Field(Field::Path) => entry.path.display().to_string(),
+                Segment::Field(Field::RelPath) => relative_path(root, &entry.path),
+            })
+            .collect()
+    }
+}
+
+/// What `ListRepositoriesUseCase::execute` prints for the repositories
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListStyle {
+    /// One path per line
+    #[default]
+    Paths,
+    /// A JSON array of ``RepositoryListing``
+    Json,
+    /// Each repository once, followed by its indented branches
+    Branches,
+}
+
+/// How `ListRepositoriesUseCase::execute` lists, mirroring the flags of `grm list`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ListOptions<'a> {
+    /// Shape of the output, one path per line by default
+    pub style: ListStyle,
+    /// Print absolute paths instead of paths relative to the root
+    pub full_path: bool,
+    /// Ask git for the checked out branch, which may differ from the `+branch` suffix
+    pub show_branch: bool,
+    /// Show the disk usage of each repository, which walks every file
+    pub size: bool,
+    /// Glob matched against the path relative to the root (e.g. `github.com/**/*+main`)
+    pub filter: Option<&'a str>,
+    /// Template printed for each repository instead of its path, see ``ListFormat``
+    pub format: Option<&'a str>,
+}
 
 pub struct ListRepositoriesUseCase {
+    git: Arc<dyn GitRepository>,
     fs: Arc<dyn FileSystem>,
     ui: Arc<dyn UserInteraction>,
 }
 
 impl ListRepositoriesUseCase {
-    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
-        Self { fs, ui }
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
     }
 
-    pub fn execute(&self, config: &Config, full_path: bool) -> Result<(), GrmError> {
+    /// Print the managed repositories
+    pub fn execute(&self, config: &Config, options: &ListOptions) -> Result<(), GrmError> {
+        let ListOptions {
+            style,
+            full_path,
+            show_branch,
+            size,
+            filter,
+            format,
+        } = *options;
+        let format = format.map(ListFormat::parse).transpose()?;
+        let filter = filter
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|e| GrmError::InvalidFormat {
+                    format: pattern.to_string(),
+                    reason: e.msg.to_string(),
+                })
+            })
+            .transpose()?;
         let root = config.root();
-        let scanner = RepoScanner::new(Arc::clone(&self.fs));
+        // A root pointing at e.g. $HOME must not make the listing walk the whole tree
+        let scanner = RepoScanner::new(Arc::clone(&self.fs))
+            .with_max_depth(config.layout.depth())
+            .with_layout(config.layout.clone());
+
+        let mut entries = if self.fs.exists(root) {
+            scanner.scan_repositories_fast(root)?
+        } else {
+            Vec::new()
+        };
+        if let Some(pattern) = filter {
+            let options = MatchOptions {
+                require_literal_separator: true,
+                ..MatchOptions::default()
+            };
+            entries.retain(|entry| {
+                let relative = entry.path.strip_prefix(root).unwrap_or(&entry.path);
+                pattern.matches_path_with(relative, options)
+            });
+        }
 
-        if !self.fs.exists(root) {
-            self.ui.print("Nothing to display");
+        if style == ListStyle::Json {
+            let listings = entries
+                .into_iter()
+                .map(|entry| {
+                    let current_branch = show_branch.then(|| self.current_branch(&entry.path));
+                    let size = size.then(|| self.fs.size(&entry.path).ok()).flatten();
+                    RepositoryListing {
+                        current_branch,
+                        size,
+                        ..RepositoryListing::from(entry)
+                    }
+                })
+                .collect::<Vec<_>>();
+            let json = serde_json::to_string_pretty(&listings)
+                .map_err(|e| GrmError::Io(std::io::Error::other(e)))?;
+            self.ui.print_result(&json);
             return Ok(());
         }
 
-        let mut repositories = scanner.scan_repositories(root)?;
-
-        if repositories.is_empty() {
+        if entries.is_empty() {
             self.ui.print("Nothing to display");
             return Ok(());
         }
 
-        repositories.sort();
+        if style == ListStyle::Branches {
+            for (repo, branches) in group_by_repository(root, entries) {
+                self.ui.print_result(&repo);
+                for branch in branches {
+                    self.ui.print_result(&format!("  {branch}"));
+                }
+            }
+            return Ok(());
+        }
+
+        if let Some(format) = format {
+            for entry in &entries {
+                self.ui.print_result(&format.render(root, entry));
+            }
+            return Ok(());
+        }
 
-        for repo in repositories {
-            if full_path {
-                self.ui.print(&repo.display().to_string());
+        for RepoEntry { path: repo, .. } in entries {
+            let mut line = if full_path {
+                repo.display().to_string()
             } else {
                 match repo.strip_prefix(root) {
-                    Ok(relative) => self.ui.print(&relative.display().to_string()),
-                    Err(_) => self.ui.print(&repo.display().to_string()),
+                    Ok(relative) => relative.display().to_string(),
+                    Err(_) => repo.display().to_string(),
                 }
+            };
+            if show_branch {
+                line = format!("{line} ({})", self.current_branch(&repo));
+            }
+            if size {
+                // Size first, like `du`, so that the paths stay aligned
+                let size = self
+                    .fs
+                    .size(&repo)
+                    .map_or_else(|_| "unknown".to_string(), format_size);
+                line = format!("{size:>10}  {line}");
             }
+            self.ui.print_result(&line);
         }
 
         Ok(())
     }
+
+    /// Checked out branch, or `unknown` if git cannot tell
+    fn current_branch(&self, repo: &Path) -> String {
+        self.git
+            .get_current_branch(repo)
+            .unwrap_or_else(|_| "unknown".to_string())
+    }
+}
+
+/// Worktrees grouped by `{host}/{user}/{repo}`, sorted by repository and then branch
+///
+/// A worktree without a branch, e.g. cloned with `--no-branch-suffix`, adds its
+/// repository without a branch. Repositories outside the managed layout are
+/// keyed by their path relative to `root`.
+fn group_by_repository(root: &Path, entries: Vec<RepoEntry>) -> BTreeMap<String, Vec<String>> {
+    let mut groups = BTreeMap::<String, Vec<String>>::new();
+    for entry in entries {
+        match entry.info {
+            Some(info) => {
+                let branches = groups
+                    .entry(format!("{}/{}/{}", info.host, info.user, info.repo))
+                    .or_default();
+                branches.extend(info.branch);
+            }
+            None => {
+                groups.entry(relative_path(root, &entry.path)).or_default();
+            }
+        }
+    }
+    for branches in groups.values_mut() {
+        branches.sort();
+    }
+    groups
+}
+
+/// `path` relative to `root` with `/` separators
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
     use std::path::PathBuf;
 
     #[test]
@@ -66,14 +368,15 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
 
         let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
             Arc::new(mock_fs),
             mock_ui.clone(),
         );
 
-        let config = Config { root: PathBuf::from("/test_root") };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, false);
+        let result = usecase.execute(&config, &ListOptions::default());
 
         // Assert
         assert!(result.is_ok());
@@ -96,14 +399,21 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
 
         let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
             Arc::new(mock_fs),
             mock_ui.clone(),
         );
 
-        let config = Config { root: PathBuf::from("/test_root") };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, true);
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                full_path: true,
+                ..ListOptions::default()
+            },
+        );
 
         // Assert
         assert!(result.is_ok());
@@ -113,6 +423,90 @@ mod tests {
         assert!(messages.contains(&"/test_root/nested/repo2".to_string()));
     }
 
+    #[test]
+    fn test_list_repositories_json() {
+        // Arrange
+        let mock_fs = MockFileSystem::new();
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/unmanaged");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            Arc::new(mock_fs),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Json,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let messages = mock_ui.get_printed_messages();
+        assert_eq!(messages.len(), 1);
+        let parsed: serde_json::Value = serde_json::from_str(&messages[0]).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!([
+                {
+                    "host": "github.com",
+                    "user": "user",
+                    "repo": "repo",
+                    "branch": "main",
+                    "path": "/test_root/github.com/user/repo+main"
+                },
+                {
+                    "host": null,
+                    "user": null,
+                    "repo": null,
+                    "branch": null,
+                    "path": "/test_root/unmanaged"
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_list_repositories_json_empty() {
+        // Arrange
+        let mock_fs = MockFileSystem::new();
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            Arc::new(mock_fs),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/nonexistent_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Json,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(mock_ui.get_printed_messages(), vec!["[]".to_string()]);
+    }
+
     #[test]
     fn test_list_repositories_empty() {
         // Arrange
@@ -122,14 +516,15 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
 
         let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
             Arc::new(mock_fs),
             mock_ui.clone(),
         );
 
-        let config = Config { root: PathBuf::from("/test_root") };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, false);
+        let result = usecase.execute(&config, &ListOptions::default());
 
         // Assert
         assert!(result.is_ok());
@@ -146,14 +541,15 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
 
         let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
             Arc::new(mock_fs),
             mock_ui.clone(),
         );
 
-        let config = Config { root: PathBuf::from("/nonexistent_root") };
+        let config = Config::new(PathBuf::from("/nonexistent_root"));
 
         // Act
-        let result = usecase.execute(&config, false);
+        let result = usecase.execute(&config, &ListOptions::default());
 
         // Assert
         assert!(result.is_ok());
@@ -161,4 +557,306 @@ mod tests {
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0], "Nothing to display");
     }
+
+    #[test]
+    fn test_list_repositories_show_branch() {
+        // Arrange
+        let mock_fs = MockFileSystem::new();
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_git = Arc::new(MockGitRepository::new());
+        mock_git.set_current_branch("/test_root/github.com/user/repo+main", "main");
+        mock_git.set_current_branch("/test_root/github.com/user/repo+feature", "drifted");
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = ListRepositoriesUseCase::new(mock_git, Arc::new(mock_fs), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                show_branch: true,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "github.com/user/repo+feature (drifted)".to_string(),
+                "github.com/user/repo+main (main)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_repositories_show_branch_json() {
+        // Arrange
+        let mock_fs = MockFileSystem::new();
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_git_repo("/test_root/unmanaged");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            Arc::new(mock_fs),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Json,
+                show_branch: true,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let parsed: serde_json::Value =
+            serde_json::from_str(&mock_ui.get_printed_messages()[0]).unwrap();
+        assert_eq!(parsed[0]["current_branch"], "unknown");
+    }
+
+    #[test]
+    fn test_list_repositories_size() {
+        // Arrange
+        let mock_fs = MockFileSystem::new();
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_git_repo("/test_root/large");
+        mock_fs.add_git_repo("/test_root/small");
+        mock_fs.add_file_with_size("/test_root/large/data.bin", 3 * 1024 * 1024);
+        mock_fs.add_file_with_size("/test_root/small/README.md", 1536);
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            Arc::new(mock_fs),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let text = usecase.execute(
+            &config,
+            &ListOptions {
+                size: true,
+                ..ListOptions::default()
+            },
+        );
+        let json = usecase.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Json,
+                size: true,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(text.is_ok(), "Failed with error: {:?}", text.err());
+        assert!(json.is_ok(), "Failed with error: {:?}", json.err());
+        let messages = mock_ui.get_printed_messages();
+        assert!(messages.contains(&"   3.0 MiB  large".to_string()));
+        assert!(messages.contains(&"   1.5 KiB  small".to_string()));
+        let parsed: serde_json::Value = serde_json::from_str(&messages[2]).unwrap();
+        assert_eq!(parsed[0]["size"], 3 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_list_repositories_filter() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        for dir in [
+            "/test_root/github.com",
+            "/test_root/github.com/user",
+            "/test_root/gitlab.com",
+            "/test_root/gitlab.com/group",
+        ] {
+            mock_fs.add_dir(dir);
+        }
+        mock_fs.add_git_repo("/test_root/github.com/user/app+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/app+feature");
+        mock_fs.add_git_repo("/test_root/github.com/user/lib+main");
+        mock_fs.add_git_repo("/test_root/gitlab.com/group/app+main");
+
+        let config = Config::new(PathBuf::from("/test_root"));
+        let list = |filter: &str| {
+            let mock_ui = Arc::new(MockUserInteraction::new());
+            let usecase = ListRepositoriesUseCase::new(
+                Arc::new(MockGitRepository::new()),
+                mock_fs.clone(),
+                mock_ui.clone(),
+            );
+            usecase
+                .execute(
+                    &config,
+                    &ListOptions {
+                        filter: Some(filter),
+                        ..ListOptions::default()
+                    },
+                )
+                .unwrap();
+            mock_ui.get_printed_messages()
+        };
+
+        // Act
+        let main_on_github = list("github.com/**/*+main");
+        let apps = list("*/*/app+*");
+        let none = list("bitbucket.org/**");
+        let classes = list("*/*/[!a]*+main");
+
+        // Assert
+        assert_eq!(
+            main_on_github,
+            vec![
+                "github.com/user/app+main".to_string(),
+                "github.com/user/lib+main".to_string(),
+            ]
+        );
+        assert_eq!(
+            apps,
+            vec![
+                "github.com/user/app+feature".to_string(),
+                "github.com/user/app+main".to_string(),
+                "gitlab.com/group/app+main".to_string(),
+            ]
+        );
+        assert_eq!(none, vec!["Nothing to display".to_string()]);
+        assert_eq!(classes, vec!["github.com/user/lib+main".to_string()]);
+    }
+
+    #[test]
+    fn test_list_repositories_format() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs
+            .create_dir(Path::new("/test_root/github.com/user"))
+            .unwrap();
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/unmanaged");
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs,
+            mock_ui.clone(),
+        );
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                format: Some("{user}/{repo}@{branch} {{{relpath}}} {path}"),
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "user/repo@main {github.com/user/repo+main} /test_root/github.com/user/repo+main"
+                    .to_string(),
+                "/@ {unmanaged} /test_root/unmanaged".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_repositories_branches() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs
+            .create_dir(Path::new("/test_root/github.com/user/repo+feature"))
+            .unwrap();
+        mock_fs
+            .create_dir(Path::new("/test_root/gitlab.com/group"))
+            .unwrap();
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature/foobar");
+        mock_fs.add_git_repo("/test_root/github.com/user/plain");
+        mock_fs.add_git_repo("/test_root/gitlab.com/group/app+main");
+        mock_fs.add_git_repo("/test_root/unmanaged");
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs,
+            mock_ui.clone(),
+        );
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            &ListOptions {
+                style: ListStyle::Branches,
+                ..ListOptions::default()
+            },
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "github.com/user/plain",
+                "github.com/user/repo",
+                "  feature/foobar",
+                "  main",
+                "gitlab.com/group/app",
+                "  main",
+                "unmanaged",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_format_rejects_invalid_template() {
+        // Arrange
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = ListRepositoriesUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            Arc::new(MockFileSystem::new()),
+            mock_ui.clone(),
+        );
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let results = ["{owner}/{repo}", "{repo", "repo}"].map(|format| {
+            usecase.execute(
+                &config,
+                &ListOptions {
+                    format: Some(format),
+                    ..ListOptions::default()
+                },
+            )
+        });
+
+        // Assert
+        for result in results {
+            assert!(
+                matches!(result, Err(GrmError::InvalidFormat { .. })),
+                "{result:?}"
+            );
+        }
+        assert!(mock_ui.get_printed_messages().is_empty());
+    }
 }
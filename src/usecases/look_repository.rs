@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::RepoInfo;
+use crate::core::ports::{CommandRunner, FileSystem, UserInteraction};
+use crate::errors::GrmError;
+use crate::usecases::resolve_path::find_candidates;
+
+pub struct LookRepositoryUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+    runner: Arc<dyn CommandRunner>,
+}
+
+impl LookRepositoryUseCase {
+    pub fn new(
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+        runner: Arc<dyn CommandRunner>,
+    ) -> Self {
+        Self { fs, ui, runner }
+    }
+
+    /// Open `shell` inside the repository matching `query`
+    ///
+    /// When several worktrees match, the user is asked to pick one.
+    ///
+    /// # Returns
+    /// * `Ok(i32)` - Exit code of the subshell
+    pub fn execute(&self, config: &Config, query: &str, shell: &str) -> Result<i32, GrmError> {
+        let mut candidates = find_candidates(&self.fs, config, query)?;
+
+        let index = match candidates.len() {
+            0 => {
+                return Err(GrmError::NotFound(format!("No repository matches {query}")));
+            }
+            1 => 0,
+            _ => {
+                let options = candidates
+                    .iter()
+                    .map(|path| {
+                        let relative = path.strip_prefix(config.root()).unwrap_or(path);
+                        relative.display().to_string()
+                    })
+                    .collect::<Vec<_>>();
+                self.ui
+                    .select("Select a repository", &options)?
+                    .ok_or(GrmError::UserCancelled)?
+            }
+        };
+        let path = candidates.swap_remove(index);
+
+        let repo_info = RepoInfo::from_path_with(config.root(), &path, &config.layout)?;
+        let envs = vec![
+            (
+                "GRM_REPO".to_string(),
+                format!("{}/{}/{}", repo_info.host, repo_info.user, repo_info.repo),
+            ),
+            (
+                "GRM_BRANCH".to_string(),
+                repo_info.branch.unwrap_or_default(),
+            ),
+        ];
+
+        let code = self.runner.run(shell, &[], &path, &envs)?;
+
+        Ok(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockCommandRunner, MockFileSystem, MockUserInteraction};
+    use std::path::PathBuf;
+
+    fn setup() -> (
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Arc<MockCommandRunner>,
+        Config,
+    ) {
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let runner = Arc::new(MockCommandRunner::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (fs, ui, runner, config)
+    }
+
+    #[test]
+    fn test_look_single_match() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        let usecase = LookRepositoryUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, "repo+main", "/bin/zsh");
+
+        // Assert
+        assert_eq!(result.unwrap(), 0);
+        let commands = runner.get_commands();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].program, "/bin/zsh");
+        assert_eq!(
+            commands[0].current_dir,
+            PathBuf::from("/test_root/github.com/user/repo+main")
+        );
+        assert!(
+            commands[0]
+                .envs
+                .contains(&("GRM_REPO".to_string(), "github.com/user/repo".to_string()))
+        );
+        assert!(
+            commands[0]
+                .envs
+                .contains(&("GRM_BRANCH".to_string(), "main".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_look_prompts_when_ambiguous() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        ui.set_select(1);
+        let usecase = LookRepositoryUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, "repo", "sh");
+
+        // Assert
+        assert!(result.is_ok());
+        let commands = runner.get_commands();
+        assert_eq!(
+            commands[0].current_dir,
+            PathBuf::from("/test_root/github.com/user/repo+main")
+        );
+    }
+
+    #[test]
+    fn test_look_cancelled_selection() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        ui.set_select_cancel();
+        let usecase = LookRepositoryUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, "repo", "sh");
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UserCancelled)));
+        assert!(runner.get_commands().is_empty());
+    }
+
+    #[test]
+    fn test_look_propagates_exit_code() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        runner.set_exit_code(42);
+        let usecase = LookRepositoryUseCase::new(fs, ui, runner);
+
+        // Act
+        let result = usecase.execute(&config, "repo+feature", "sh");
+
+        // Assert
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_look_not_found() {
+        // Arrange
+        let (fs, ui, runner, config) = setup();
+        let usecase = LookRepositoryUseCase::new(fs, ui, runner.clone());
+
+        // Act
+        let result = usecase.execute(&config, "missing", "sh");
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotFound(_))));
+        assert!(runner.get_commands().is_empty());
+    }
+}
@@ -1,14 +1,43 @@
+pub mod clone_batch;
 pub mod clone_repository;
+pub mod doctor;
+pub mod edit_config;
+pub mod exec;
+pub mod get_repository;
+pub mod init_root;
 pub mod list_repositories;
+pub mod look_repository;
+pub mod move_worktree;
+pub mod prune_repositories;
+pub mod pull;
 pub mod remove_repository;
+pub mod resolve_path;
 pub mod show_root;
+pub mod show_stats;
+pub mod status;
+pub mod whereis;
 pub mod worktree;
 
-pub use clone_repository::CloneRepositoryUseCase;
-pub use list_repositories::ListRepositoriesUseCase;
+pub use clone_batch::CloneBatchUseCase;
+pub use clone_repository::{CloneOptions, CloneRepositoryUseCase, CloneTarget};
+pub use doctor::DoctorUseCase;
+pub use edit_config::EditConfigUseCase;
+pub use exec::ExecUseCase;
+pub use get_repository::GetRepositoryUseCase;
+pub use init_root::InitRootUseCase;
+pub use list_repositories::{ListOptions, ListRepositoriesUseCase, ListStyle};
+pub use look_repository::LookRepositoryUseCase;
+pub use move_worktree::MoveWorktreeUseCase;
+pub use prune_repositories::PruneRepositoriesUseCase;
+pub use pull::PullUseCase;
 pub use remove_repository::RemoveRepositoryUseCase;
+pub use resolve_path::ResolvePathUseCase;
 pub use show_root::ShowRootUseCase;
+pub use show_stats::{ShowStatsUseCase, StatsFormat};
+pub use status::StatusUseCase;
+pub use whereis::WhereisUseCase;
 pub use worktree::{
-    IsolateFilesUseCase, RemoveWorktreeUseCase, ShareFilesUseCase, SplitWorktreeUseCase,
+    IsolateFilesUseCase, ListSharedUseCase, ListWorktreesUseCase, PruneWorktreesUseCase,
+    RemoveWorktreeUseCase, ShareFilesUseCase, SplitWorktreeUseCase, SwitchWorktreeUseCase,
     UnshareFilesUseCase,
 };
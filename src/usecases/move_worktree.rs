@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::RepoInfo;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::errors::GrmError;
+
+pub struct MoveWorktreeUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl MoveWorktreeUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Move `{repo}+{old_branch}` to `{repo}+{new_branch}`
+    ///
+    /// # Arguments
+    /// * `rename_branch` - Also rename the git branch checked out in the worktree
+    ///
+    /// # Returns
+    /// The new worktree path
+    pub fn execute(
+        &self,
+        config: &Config,
+        url: &str,
+        old_branch: &str,
+        new_branch: &str,
+        rename_branch: bool,
+    ) -> Result<PathBuf, GrmError> {
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+        RepoInfo::validate_branch_name(new_branch)?;
+
+        let old_path = repo_info.build_repo_path_with(config.root(), old_branch, &config.layout);
+        let new_path = repo_info.build_repo_path_with(config.root(), new_branch, &config.layout);
+
+        if !self.fs.exists(&old_path) {
+            return Err(GrmError::NotFound(format!(
+                "Worktree does not exist: {}",
+                old_path.display()
+            )));
+        }
+        if self.fs.exists(&new_path) || self.fs.is_symlink(&new_path) {
+            return Err(GrmError::AlreadyExists(new_path.display().to_string()));
+        }
+
+        if let Some(parent) = new_path.parent() {
+            self.fs.create_dir(parent)?;
+        }
+        self.fs.rename(&old_path, &new_path)?;
+        // The moved worktree and its siblings still point at each other through the old path
+        self.git.repair_worktrees(&new_path)?;
+        self.ui.print(&format!(
+            "Moved: {} -> {}",
+            old_path.display(),
+            new_path.display()
+        ));
+
+        if rename_branch {
+            self.git.rename_branch(&new_path, old_branch, new_branch)?;
+            self.ui
+                .print(&format!("Renamed branch: {old_branch} -> {new_branch}"));
+        }
+
+        // Keep the default-branch link pointing at the moved worktree
+        if let Some(link_path) = repo_info.build_repo_path_plain(config.root(), &config.layout)
+            && self.fs.is_symlink(&link_path)
+            && self
+                .fs
+                .read_link(&link_path)
+                .is_ok_and(|target| target == old_path)
+        {
+            self.fs.remove(&link_path)?;
+            self.fs.create_symlink(&new_path, &link_path)?;
+            self.ui.print(&format!(
+                "Linked: {} -> {}",
+                link_path.display(),
+                new_path.display()
+            ));
+        }
+
+        Ok(new_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use std::path::Path;
+
+    const URL: &str = "https://github.com/user/repo";
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+old");
+
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
+
+    #[test]
+    fn test_move_worktree_success() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = MoveWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, URL, "old", "new", false);
+
+        // Assert
+        let new_path = PathBuf::from("/test_root/github.com/user/repo+new");
+        assert_eq!(result.unwrap(), new_path);
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+old")));
+        assert!(mock_fs.is_git_repository(&new_path));
+        assert_eq!(mock_git.get_repaired(), vec![new_path]);
+        assert!(mock_git.get_renamed_branches().is_empty());
+    }
+
+    #[test]
+    fn test_move_worktree_renames_branch_and_link() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_fs.add_symlink(
+            "/test_root/github.com/user/repo",
+            "/test_root/github.com/user/repo+old",
+        );
+        let usecase = MoveWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, URL, "old", "new", true);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_git.get_renamed_branches(),
+            vec![("old".to_string(), "new".to_string())]
+        );
+        assert_eq!(
+            mock_fs
+                .read_link(Path::new("/test_root/github.com/user/repo"))
+                .unwrap(),
+            PathBuf::from("/test_root/github.com/user/repo+new")
+        );
+    }
+
+    #[test]
+    fn test_move_worktree_destination_exists() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = MoveWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, URL, "old", "main", true);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::AlreadyExists(_))));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+old")));
+        assert!(mock_git.get_renamed_branches().is_empty());
+    }
+
+    #[test]
+    fn test_move_worktree_source_missing() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = MoveWorktreeUseCase::new(mock_git, mock_fs, mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, URL, "missing", "new", false);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotFound(_))));
+    }
+}
@@ -0,0 +1,301 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use crate::configs::Config;
+use crate::core::ports::{Clock, FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner, cleanup, is_bare_repository};
+use crate::errors::GrmError;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+/// Parse an age such as `90d`, `2w` or `6m`
+///
+/// A month counts as 30 days.
+pub fn parse_age(value: &str) -> Result<Duration, GrmError> {
+    let invalid = |reason: &str| GrmError::InvalidFormat {
+        format: value.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let days: u64 = match value.chars().last() {
+        Some('d') => 1,
+        Some('w') => 7,
+        Some('m') => 30,
+        _ => {
+            return Err(invalid(
+                "expected a number of days, weeks or months, e.g. `90d`, `2w` or `6m`",
+            ));
+        }
+    };
+    // The unit is a single ASCII character
+    let count: u64 = value[..value.len() - 1]
+        .parse()
+        .map_err(|_| invalid("expected a whole number before the unit"))?;
+
+    count
+        .checked_mul(days * DAY_SECS)
+        .map(Duration::from_secs)
+        .ok_or_else(|| invalid("too long"))
+}
+
+pub struct PruneRepositoriesUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+    clock: Arc<dyn Clock>,
+}
+
+impl PruneRepositoriesUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        Self { git, fs, ui, clock }
+    }
+
+    /// Remove the repositories under the root which were not touched for longer than `since`
+    ///
+    /// A repository counts as touched by its last commit or by a change of its
+    /// directory, e.g. when it was cloned, whichever is later. Worktrees with
+    /// uncommitted changes are always kept.
+    ///
+    /// # Arguments
+    /// * `since` - How long a repository may stay untouched
+    /// * `force` - Remove without asking for confirmation
+    ///
+    /// # Returns
+    /// The removed repository paths
+    pub fn execute(
+        &self,
+        config: &Config,
+        since: Duration,
+        force: bool,
+    ) -> Result<Vec<PathBuf>, GrmError> {
+        let root = config.root();
+        if !self.fs.exists(root) {
+            self.ui.print("No repositories to prune");
+            return Ok(Vec::new());
+        }
+
+        let scanner = RepoScanner::new(Arc::clone(&self.fs))
+            .with_max_depth(config.layout.depth())
+            .with_layout(config.layout.clone());
+        let mut repos = scanner.scan_repositories(root)?;
+        repos.sort();
+
+        let now = self.clock.now();
+        let mut stale = Vec::new();
+        for repo in repos {
+            let Some(touched) = self.last_touched(&repo) else {
+                self.ui.print_warning(&format!(
+                    "Skipping {}: cannot tell when it was last touched",
+                    repo.display()
+                ));
+                continue;
+            };
+            let age = now.duration_since(touched).unwrap_or_default();
+            if age <= since {
+                continue;
+            }
+
+            let dirty = !is_bare_repository(self.fs.as_ref(), &repo)
+                && self.git.is_dirty(&repo).unwrap_or(true);
+            if dirty {
+                self.ui.print_warning(&format!(
+                    "Kept {} which has uncommitted changes",
+                    repo.display()
+                ));
+                continue;
+            }
+
+            stale.push((repo, age.as_secs() / DAY_SECS));
+        }
+
+        if stale.is_empty() {
+            self.ui.print("No repositories to prune");
+            return Ok(Vec::new());
+        }
+
+        if !force {
+            self.ui.print("The following repositories will be removed:");
+            for (repo, days) in &stale {
+                self.ui.print(&format!(
+                    "  - {} (untouched for {days} days)",
+                    repo.display()
+                ));
+            }
+            self.ui.print("");
+
+            if !self
+                .ui
+                .confirm("Do you want to continue?")
+                .map_err(|e| GrmError::from_prompt(e, "--force"))?
+            {
+                return Err(GrmError::UserCancelled);
+            }
+        }
+
+        let mut removed = Vec::new();
+        for (repo, _) in stale {
+            self.fs.remove(&repo)?;
+            self.ui.print(&format!("Removed: {}", repo.display()));
+
+            // Drop the default-branch link rather than leaving it dangling
+            if let Ok(info) = RepoInfo::from_path_with(root, &repo, &config.layout)
+                && let Some(link_path) = info.build_repo_path_plain(root, &config.layout)
+                && self.fs.is_symlink(&link_path)
+                && self
+                    .fs
+                    .read_link(&link_path)
+                    .is_ok_and(|target| target == repo)
+            {
+                self.fs.remove(&link_path)?;
+            }
+            removed.push(repo);
+        }
+
+        // Deepest first, as a `repo+feature/foobar` worktree leaves `repo+feature` behind
+        let mut removed_by_depth = removed.iter().collect::<Vec<_>>();
+        removed_by_depth.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+        for repo in removed_by_depth {
+            cleanup::remove_empty_parents(self.fs.as_ref(), root, repo);
+        }
+
+        self.ui.print(&format!(
+            "\nSuccessfully removed {} repository(ies).",
+            removed.len()
+        ));
+
+        Ok(removed)
+    }
+
+    /// The later of the last commit and the last change of the directory
+    fn last_touched(&self, repo: &Path) -> Option<SystemTime> {
+        let committed = self.git.last_commit_timestamp(repo).ok();
+        let modified = self.fs.modified(repo).ok();
+        committed.max(modified)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{
+        MockClock, MockFileSystem, MockGitRepository, MockUserInteraction,
+    };
+
+    const DAY: Duration = Duration::from_secs(DAY_SECS);
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Arc<MockClock>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let clock = Arc::new(MockClock::new());
+        let now = clock.now();
+
+        mock_fs
+            .create_dir(Path::new("/test_root/github.com/user"))
+            .unwrap();
+        mock_fs
+            .create_dir(Path::new("/test_root/gitlab.com/old"))
+            .unwrap();
+        // Last commit long ago, cloned long ago
+        mock_fs.add_git_repo("/test_root/gitlab.com/old/repo+main");
+        mock_git.set_last_commit("/test_root/gitlab.com/old/repo+main", now - 200 * DAY);
+        // Last commit long ago, but cloned last week
+        mock_fs.add_git_repo("/test_root/github.com/user/archive+main");
+        mock_git.set_last_commit("/test_root/github.com/user/archive+main", now - 400 * DAY);
+        mock_fs.set_modified("/test_root/github.com/user/archive+main", now - 7 * DAY);
+        // Committed to yesterday
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_git.set_last_commit("/test_root/github.com/user/repo+main", now - DAY);
+
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            clock,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
+
+    #[test]
+    fn test_parse_age() {
+        // Arrange
+        let valid = ["90d", "2w", "6m"];
+        let invalid = ["90", "d", "-1d", "1y", "1.5w", ""];
+
+        // Act
+        let parsed = valid.map(parse_age);
+
+        // Assert
+        assert_eq!(parsed.map(Result::unwrap), [90 * DAY, 14 * DAY, 180 * DAY]);
+        for value in invalid {
+            assert!(
+                matches!(parse_age(value), Err(GrmError::InvalidFormat { .. })),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_prune_repositories_with_confirmation() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, clock, config) = setup();
+        mock_ui.set_confirm(true);
+        let usecase =
+            PruneRepositoriesUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone(), clock);
+
+        // Act
+        let result = usecase.execute(&config, 90 * DAY, false);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            vec![PathBuf::from("/test_root/gitlab.com/old/repo+main")]
+        );
+        assert!(
+            mock_ui.has_printed("  - /test_root/gitlab.com/old/repo+main (untouched for 200 days)")
+        );
+        assert!(!mock_fs.exists(Path::new("/test_root/gitlab.com")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/archive+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+    }
+
+    #[test]
+    fn test_prune_repositories_keeps_dirty_and_cancels() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, clock, config) = setup();
+        mock_git.set_dirty("/test_root/gitlab.com/old/repo+main");
+        let usecase = PruneRepositoriesUseCase::new(
+            mock_git.clone(),
+            mock_fs.clone(),
+            mock_ui.clone(),
+            clock.clone(),
+        );
+
+        // Act
+        let dirty = usecase.execute(&config, 90 * DAY, true);
+        let cancelled = usecase.execute(&config, 5 * DAY, false);
+
+        // Assert
+        assert!(dirty.unwrap().is_empty());
+        assert!(
+            mock_ui
+                .get_warning_messages()
+                .iter()
+                .any(|m| m.starts_with("Kept /test_root/gitlab.com/old/repo+main"))
+        );
+        assert!(matches!(cancelled, Err(GrmError::UserCancelled)));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/archive+main")));
+    }
+}
@@ -0,0 +1,209 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, GitError, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner, is_bare_repository};
+use crate::errors::GrmError;
+
+pub struct PullUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl PullUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Fast-forward every worktree of the repository at `url` to its upstream
+    ///
+    /// Worktrees which cannot be fast-forwarded are reported and skipped.
+    /// Other failures are reported and the remaining worktrees are still pulled.
+    ///
+    /// # Returns
+    /// `GrmError::PullFailed` if any worktree failed for another reason than diverging
+    pub fn execute(&self, config: &Config, url: &str) -> Result<(), GrmError> {
+        let root = config.root();
+        let url = RepoInfo::expand_url(url, config.default_host.as_deref());
+        let repo_info = RepoInfo::from_url_with(&url, config.normalize_host)?;
+
+        let scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
+        let mut worktrees = scanner.scan_worktrees(root, &repo_info)?;
+        // A bare clone has no checked out branch to update
+        worktrees.retain(|worktree| !is_bare_repository(self.fs.as_ref(), worktree));
+        worktrees.sort();
+
+        if worktrees.is_empty() {
+            let searched_path = root.join(&repo_info.host).join(&repo_info.user);
+            return Err(GrmError::UnmanagedRepository {
+                url,
+                searched_path: searched_path.display().to_string(),
+            });
+        }
+
+        let total = worktrees.len();
+        let mut pulled = 0;
+        let mut skipped = 0;
+        let mut failed = 0;
+        for worktree in &worktrees {
+            let relative = worktree
+                .strip_prefix(root)
+                .unwrap_or(worktree)
+                .display()
+                .to_string();
+
+            match self.git.pull(worktree) {
+                Ok(()) => {
+                    pulled += 1;
+                    self.ui.print(&format!("Pulled: {relative}"));
+                }
+                Err(GitError::NotFastForward(_)) => {
+                    skipped += 1;
+                    self.ui.print_warning(&format!(
+                        "Skipped {relative}: the branch has diverged and cannot be fast-forwarded"
+                    ));
+                }
+                Err(e) => {
+                    failed += 1;
+                    self.ui
+                        .print_error(&format!("Failed to pull {relative}: {e}"));
+                }
+            }
+        }
+
+        self.ui.print(&format!(
+            "Pulled {pulled} of {total} worktrees ({skipped} skipped, {failed} failed)"
+        ));
+
+        if failed > 0 {
+            return Err(GrmError::PullFailed { failed, total });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use std::path::PathBuf;
+
+    const URL: &str = "https://github.com/user/repo";
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let git = Arc::new(MockGitRepository::new());
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        fs.add_git_repo("/test_root/github.com/user/other+main");
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (git, fs, ui, config)
+    }
+
+    #[test]
+    fn test_pull_each_worktree() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        fs.add_bare_repo("/test_root/github.com/user/repo.git");
+        let usecase = PullUseCase::new(git.clone(), fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            git.get_pulled(),
+            vec![
+                PathBuf::from("/test_root/github.com/user/repo+feature"),
+                PathBuf::from("/test_root/github.com/user/repo+main"),
+            ]
+        );
+        assert!(ui.has_printed("Pulled: github.com/user/repo+main"));
+        assert!(ui.has_printed("Pulled 2 of 2 worktrees (0 skipped, 0 failed)"));
+    }
+
+    #[test]
+    fn test_pull_skips_diverged_worktree() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        git.set_diverged("/test_root/github.com/user/repo+feature");
+        let usecase = PullUseCase::new(git.clone(), fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            git.get_pulled(),
+            vec![PathBuf::from("/test_root/github.com/user/repo+main")]
+        );
+        assert!(
+            ui.get_warning_messages()
+                .iter()
+                .any(|m| m.starts_with("Skipped github.com/user/repo+feature:"))
+        );
+        assert!(ui.has_printed("Pulled 1 of 2 worktrees (1 skipped, 0 failed)"));
+    }
+
+    #[test]
+    fn test_pull_failure_continues_and_fails() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        git.inject_error(GitError::Failed {
+            status: 1,
+            stderr: "There is no tracking information for the current branch.".to_string(),
+        });
+        let usecase = PullUseCase::new(git.clone(), fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, URL);
+
+        // Assert
+        assert!(matches!(
+            result,
+            Err(GrmError::PullFailed {
+                failed: 1,
+                total: 2
+            })
+        ));
+        assert_eq!(git.get_pulled().len(), 1);
+        assert!(
+            ui.get_error_messages()
+                .iter()
+                .any(|m| m.starts_with("Failed to pull github.com/user/repo+feature:"))
+        );
+    }
+
+    #[test]
+    fn test_pull_unmanaged_repository() {
+        // Arrange
+        let (git, fs, ui, config) = setup();
+        let usecase = PullUseCase::new(git, fs, ui);
+
+        // Act
+        let result = usecase.execute(&config, "https://github.com/user/missing");
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UnmanagedRepository { .. })));
+    }
+}
@@ -1,51 +1,191 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::configs::Config;
-use crate::core::ports::{FileSystem, UserInteraction};
-use crate::core::{RepoInfo, RepoScanner};
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner, cleanup, is_bare_repository};
 use crate::errors::GrmError;
 
+/// Outcome of removing the worktrees of a repository
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemoveOutcome {
+    /// Number of worktrees removed
+    pub removed: usize,
+    /// Worktrees which matched but were left in place, e.g. kept for uncommitted changes
+    pub skipped: Vec<PathBuf>,
+}
+
 pub struct RemoveRepositoryUseCase {
+    git: Arc<dyn GitRepository>,
     fs: Arc<dyn FileSystem>,
     ui: Arc<dyn UserInteraction>,
 }
 
 impl RemoveRepositoryUseCase {
-    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
-        Self { fs, ui }
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
     }
 
-    pub fn execute(&self, config: &Config, url: &str, force: bool) -> Result<(), GrmError> {
+    /// Remove all worktrees of a managed repository
+    ///
+    /// # Arguments
+    /// * `target` - Repository URL, or a path (e.g. `.`) inside a managed worktree
+    /// * `branch` - Remove only the worktree of this branch
+    /// * `force` - Remove without asking for confirmation, even worktrees with uncommitted changes
+    /// * `dry_run` - Only print the worktrees which would be removed
+    /// * `interactive` - Choose a single worktree to remove when several match
+    ///
+    /// # Returns
+    /// What was removed, nothing for `dry_run`
+    pub fn execute(
+        &self,
+        config: &Config,
+        target: &str,
+        branch: Option<&str>,
+        force: bool,
+        dry_run: bool,
+        interactive: bool,
+    ) -> Result<RemoveOutcome, GrmError> {
         let root = config.root();
-        let repo_info = RepoInfo::from_url(url)?;
-        let scanner = RepoScanner::new(Arc::clone(&self.fs));
+        let repo_info = match self.resolve_local_path(target) {
+            Some(path) => self.repo_info_from_path(config, &path)?,
+            None => RepoInfo::from_url_with(target, config.normalize_host)?,
+        };
+        let scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
 
-        let matching_repos = scanner.scan_worktrees(root, &repo_info)?;
+        let mut matching_repos = scanner.scan_worktrees(root, &repo_info)?;
+        matching_repos.sort();
 
         if matching_repos.is_empty() {
             let searched_path = root.join(&repo_info.host).join(&repo_info.user);
             return Err(GrmError::UnmanagedRepository {
-                url: url.to_string(),
+                url: target.to_string(),
                 searched_path: searched_path.display().to_string(),
             });
         }
 
-        if !self.prompt_confirmation(&matching_repos, force)? {
+        let mut kept_repos = Vec::new();
+        if let Some(branch) = branch {
+            RepoInfo::validate_branch_name(branch)?;
+            let (selected, others) = matching_repos
+                .into_iter()
+                .partition::<Vec<_>, _>(|repo| branch_of(config, repo).as_deref() == Some(branch));
+            if selected.is_empty() {
+                let mut branches = others
+                    .iter()
+                    .filter_map(|repo| branch_of(config, repo))
+                    .collect::<Vec<_>>();
+                branches.sort();
+                return Err(GrmError::NotFound(format!(
+                    "No worktree of {target} for branch {branch}. Existing worktrees: {}",
+                    branches.join(", ")
+                )));
+            }
+            matching_repos = selected;
+            kept_repos = others;
+        }
+
+        if interactive && matching_repos.len() > 1 {
+            let options = matching_repos
+                .iter()
+                .map(|repo| repo.display().to_string())
+                .collect::<Vec<_>>();
+            let Some(index) = self.ui.select("Select a worktree to remove", &options)? else {
+                return Err(GrmError::UserCancelled);
+            };
+            let selected = matching_repos.remove(index);
+            kept_repos = std::mem::replace(&mut matching_repos, vec![selected]);
+        }
+
+        if dry_run {
+            for repo in &matching_repos {
+                self.ui.print(&format!("Would remove: {}", repo.display()));
+            }
+            return Ok(RemoveOutcome::default());
+        }
+
+        if !self.prompt_confirmation(&repo_info, &matching_repos, force)? {
             return Err(GrmError::UserCancelled);
         }
 
-        self.remove_repositories(&matching_repos)?;
+        let (removed, skipped) = self.remove_repositories(&matching_repos, force)?;
+        kept_repos.extend(skipped.iter().cloned());
+
+        // Keep the default-branch link if it points to a worktree that is not removed
+        if let Some(link_path) = repo_info.build_repo_path_plain(root, &config.layout)
+            && self.fs.is_symlink(&link_path)
+            && !self
+                .fs
+                .read_link(&link_path)
+                .is_ok_and(|target| kept_repos.contains(&target))
+        {
+            self.fs.remove(&link_path)?;
+            self.ui
+                .print(&format!("Removed link: {}", link_path.display()));
+        }
+
+        // Deepest first, so that the `repo+feature` directory left by a
+        // `repo+feature/foobar` worktree is pruned before its parents are tried
+        let mut removed_by_depth = removed.iter().collect::<Vec<_>>();
+        removed_by_depth.sort_by_key(|path| std::cmp::Reverse(path.components().count()));
+        for repo in removed_by_depth {
+            for dir in cleanup::remove_empty_parents(self.fs.as_ref(), root, repo) {
+                self.ui
+                    .print(&format!("Removed empty directory: {}", dir.display()));
+            }
+        }
 
         self.ui.print(&format!(
             "\nSuccessfully removed {} repository(ies).",
-            matching_repos.len()
+            removed.len()
         ));
 
-        Ok(())
+        Ok(RemoveOutcome {
+            removed: removed.len(),
+            skipped,
+        })
+    }
+
+    /// Returns the absolute path if `target` refers to an existing directory
+    fn resolve_local_path(&self, target: &str) -> Option<PathBuf> {
+        let current_dir = self.fs.current_dir().ok()?;
+        if target == "." {
+            return Some(current_dir);
+        }
+
+        let path = self.fs.normalize(Path::new(target), &current_dir).ok()?;
+        self.fs.exists(&path).then_some(path)
+    }
+
+    fn repo_info_from_path(&self, config: &Config, path: &Path) -> Result<RepoInfo, GrmError> {
+        let root = config.root();
+        let unmanaged = || GrmError::UnmanagedRepository {
+            url: path.display().to_string(),
+            searched_path: root.display().to_string(),
+        };
+
+        let repo_root = self
+            .git
+            .get_repository_root_at(path)
+            .map_err(|_| unmanaged())?;
+
+        RepoInfo::from_path_resolved(self.fs.as_ref(), root, &repo_root, &config.layout)
+            .map_err(|_| unmanaged())
     }
 
-    fn prompt_confirmation(&self, repositories: &[PathBuf], force: bool) -> Result<bool, GrmError> {
+    /// Ask the user before deleting
+    ///
+    /// Removing several worktrees at once requires typing the repository name.
+    fn prompt_confirmation(
+        &self,
+        repo_info: &RepoInfo,
+        repositories: &[PathBuf],
+        force: bool,
+    ) -> Result<bool, GrmError> {
         if force {
             return Ok(true);
         }
@@ -56,32 +196,96 @@ impl RemoveRepositoryUseCase {
         }
         self.ui.print("");
 
+        if repositories.len() > 1 {
+            let answer = self
+                .ui
+                .input(
+                    &format!("Type the repository name ({}) to confirm", repo_info.repo),
+                    None,
+                )
+                .map_err(|e| GrmError::from_prompt(e, "--force"))?;
+            return Ok(answer == repo_info.repo);
+        }
+
         self.ui
-            .confirm("Do you want to continue?")
-            .map_err(GrmError::from)
+            .confirm_strict("Do you want to continue?")
+            .map_err(|e| GrmError::from_prompt(e, "--force"))
     }
 
-    fn remove_repositories(&self, repositories: &[PathBuf]) -> Result<(), GrmError> {
+    /// Remove `repositories`, asking again for each one with uncommitted changes unless `force`
+    ///
+    /// # Returns
+    /// The removed paths and the skipped ones
+    fn remove_repositories(
+        &self,
+        repositories: &[PathBuf],
+        force: bool,
+    ) -> Result<(Vec<PathBuf>, Vec<PathBuf>), GrmError> {
+        let mut removed = Vec::new();
+        let mut skipped = Vec::new();
         for repo in repositories {
             if self.fs.is_symlink(repo) {
-                self.ui.print_error(&format!(
-                    "Warning: Skipping symlink: {} (unexpected, should have been filtered)",
+                self.ui.print_warning(&format!(
+                    "Skipping symlink: {} (unexpected, should have been filtered)",
                     repo.display()
                 ));
+                skipped.push(repo.clone());
+                continue;
+            }
+
+            if !force && !self.confirm_dirty(repo)? {
+                self.ui.print_warning(&format!(
+                    "Kept {} which has uncommitted changes",
+                    repo.display()
+                ));
+                skipped.push(repo.clone());
                 continue;
             }
 
             self.fs.remove(repo)?;
             self.ui.print(&format!("Removed: {}", repo.display()));
+            removed.push(repo.clone());
         }
-        Ok(())
+        Ok((removed, skipped))
     }
+
+    /// Whether `repo` may be removed, asking the user if it has uncommitted changes
+    fn confirm_dirty(&self, repo: &Path) -> Result<bool, GrmError> {
+        // A bare clone has no working tree to hold changes
+        if is_bare_repository(self.fs.as_ref(), repo) {
+            return Ok(true);
+        }
+
+        // A worktree git cannot inspect may hold changes as well
+        let message = match self.git.is_dirty(repo) {
+            Ok(false) => return Ok(true),
+            Ok(true) => format!(
+                "{} has uncommitted changes. Remove it anyway?",
+                repo.display()
+            ),
+            Err(e) => format!(
+                "Could not check {} for uncommitted changes ({e}). Remove it anyway?",
+                repo.display()
+            ),
+        };
+
+        self.ui
+            .confirm_strict(&message)
+            .map_err(|e| GrmError::from_prompt(e, "--force"))
+    }
+}
+
+/// Branch of the worktree at `repo`, `None` for bare clones and plain `{repo}` clones
+fn branch_of(config: &Config, repo: &Path) -> Option<String> {
+    RepoInfo::from_path_with(config.root(), repo, &config.layout)
+        .ok()
+        .and_then(|info| info.branch)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
 
     #[test]
     fn test_remove_repository_with_confirmation() {
@@ -95,14 +299,23 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
         mock_ui.set_confirm(true);
 
-        let usecase = RemoveRepositoryUseCase::new(mock_fs.clone(), mock_ui.clone());
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "https://github.com/user/repo", false);
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
 
         // Assert
         assert!(result.is_ok());
@@ -115,6 +328,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remove_repository_without_branch_suffix() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo")));
+    }
+
+    #[test]
+    fn test_remove_repository_bare_clone() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs
+            .create_dir(Path::new("/test_root/github.com/user"))
+            .unwrap();
+        mock_fs.add_bare_repo("/test_root/github.com/user/repo.git");
+        mock_fs.add_bare_repo("/test_root/github.com/user/repository.git");
+        let mock_git = Arc::new(MockGitRepository::new());
+        mock_git.inject_error(crate::core::ports::GitError::Execution(
+            "this operation must be run in a work tree".to_string(),
+        ));
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_confirm(true);
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo.git")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repository.git")));
+        assert!(mock_ui.has_printed("Removed: /test_root/github.com/user/repo.git"));
+    }
+
     #[test]
     fn test_remove_repository_force() {
         // Arrange
@@ -126,14 +409,23 @@ mod tests {
 
         let mock_ui = Arc::new(MockUserInteraction::new());
 
-        let usecase = RemoveRepositoryUseCase::new(mock_fs.clone(), mock_ui.clone());
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "https://github.com/user/repo", true);
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
 
         // Assert
         assert!(result.is_ok());
@@ -146,6 +438,172 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_remove_repository_host_case_insensitive() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/User");
+        mock_fs.add_git_repo("/test_root/github.com/User/Repo+main");
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            Arc::new(MockUserInteraction::new()),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://www.GitHub.com/User/Repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/User/Repo+main")));
+    }
+
+    #[test]
+    fn test_remove_repository_drops_default_link() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_symlink(
+            "/test_root/github.com/user/repo",
+            "/test_root/github.com/user/repo+main",
+        );
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(!mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo").as_ref()));
+        assert!(mock_ui.has_printed("Removed link"));
+    }
+
+    #[test]
+    fn test_remove_repository_single_branch() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_dir("/test_root/github.com/user/repo+feature");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature/foobar");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+develop");
+        mock_fs.add_symlink(
+            "/test_root/github.com/user/repo",
+            "/test_root/github.com/user/repo+main",
+        );
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_confirm(true);
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let removed = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            Some("feature/foobar"),
+            false,
+            false,
+            false,
+        );
+        let missing = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            Some("release"),
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert_eq!(removed.unwrap().removed, 1);
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+develop")));
+        assert!(mock_fs.is_symlink(Path::new("/test_root/github.com/user/repo")));
+        assert!(matches!(
+            missing,
+            Err(GrmError::NotFound(ref message))
+                if message.ends_with("Existing worktrees: develop, main")
+        ));
+    }
+
+    #[test]
+    fn test_remove_repository_prunes_empty_parents() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/git.example.org");
+        mock_fs.add_dir("/test_root/git.example.org/olduser");
+        mock_fs.add_git_repo("/test_root/git.example.org/olduser/repo+main");
+        mock_fs.add_dir("/test_root/github.com");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://git.example.org/olduser/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(!mock_fs.exists(PathBuf::from("/test_root/git.example.org").as_ref()));
+        assert!(mock_fs.exists(PathBuf::from("/test_root/github.com").as_ref()));
+        assert!(mock_fs.exists(PathBuf::from("/test_root").as_ref()));
+    }
+
     #[test]
     fn test_remove_repository_user_cancelled() {
         // Arrange
@@ -158,20 +616,79 @@ mod tests {
         let mock_ui = Arc::new(MockUserInteraction::new());
         mock_ui.set_confirm(false);
 
-        let usecase = RemoveRepositoryUseCase::new(mock_fs.clone(), mock_ui.clone());
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "https://github.com/user/repo", false);
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
 
         // Assert
         assert!(matches!(result, Err(GrmError::UserCancelled)));
         assert!(mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo+main").as_ref()));
     }
 
+    #[test]
+    fn test_remove_repository_non_interactive_requires_force() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_non_interactive();
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let prompted = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
+        let prompted_exists =
+            mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo+main").as_ref());
+        let forced = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(matches!(
+            prompted,
+            Err(GrmError::ConfirmationRequired { ref flag }) if flag == "--force"
+        ));
+        assert!(prompted.unwrap_err().to_string().contains("Pass --force"));
+        assert!(prompted_exists);
+        assert_eq!(forced.unwrap().removed, 1);
+    }
+
     #[test]
     fn test_remove_repository_not_found() {
         // Arrange
@@ -182,19 +699,72 @@ mod tests {
 
         let mock_ui = Arc::new(MockUserInteraction::new());
 
-        let usecase = RemoveRepositoryUseCase::new(mock_fs.clone(), mock_ui.clone());
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "https://github.com/user/nonexistent", false);
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/nonexistent",
+            None,
+            false,
+            false,
+            false,
+        );
 
         // Assert
         assert!(matches!(result, Err(GrmError::UnmanagedRepository { .. })));
     }
 
+    #[test]
+    fn test_remove_repository_dry_run() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            true,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(mock_ui.get_input_prompts().is_empty());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "Would remove: /test_root/github.com/user/repo+feature".to_string(),
+                "Would remove: /test_root/github.com/user/repo+main".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn test_remove_multiple_worktrees() {
         // Arrange
@@ -207,19 +777,34 @@ mod tests {
         mock_fs.add_git_repo("/test_root/github.com/user/repo+dev");
 
         let mock_ui = Arc::new(MockUserInteraction::new());
-        mock_ui.set_confirm(true);
+        mock_ui.push_input("repo");
 
-        let usecase = RemoveRepositoryUseCase::new(mock_fs.clone(), mock_ui.clone());
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "https://github.com/user/repo", false);
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
 
         // Assert
-        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            RemoveOutcome {
+                removed: 3,
+                skipped: Vec::new(),
+            }
+        );
         assert!(!mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo+main").as_ref()));
         assert!(!mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo+feature").as_ref()));
         assert!(!mock_fs.exists(PathBuf::from("/test_root/github.com/user/repo+dev").as_ref()));
@@ -230,4 +815,356 @@ mod tests {
                 .any(|m| m.contains("Successfully removed 3 repository"))
         );
     }
+
+    #[test]
+    fn test_remove_repository_by_current_dir() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        mock_fs.add_dir("/test_root/github.com/user/repo+feature/src");
+        mock_fs.set_current_dir("/test_root/github.com/user/repo+feature/src");
+        mock_git.set_repo_root_at(
+            "/test_root/github.com/user/repo+feature/src",
+            "/test_root/github.com/user/repo+feature",
+        );
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.push_input("repo");
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, ".", None, false, false, false);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(mock_ui.has_printed("Successfully removed 2 repository"));
+    }
+
+    #[test]
+    fn test_remove_repository_by_path() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/other+main");
+        mock_git.set_repo_root_at(
+            "/test_root/github.com/user/repo+main",
+            "/test_root/github.com/user/repo+main",
+        );
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "/test_root/github.com/user/repo+main",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/other+main")));
+    }
+
+    #[test]
+    fn test_remove_repository_path_outside_root() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_git_repo("/elsewhere/repo");
+        mock_fs.set_current_dir("/elsewhere/repo");
+        mock_git.set_repo_root_at("/elsewhere/repo", "/elsewhere/repo");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, ".", None, true, false, false);
+
+        // Assert
+        match result {
+            Err(GrmError::UnmanagedRepository { url, .. }) => {
+                assert_eq!(url, "/elsewhere/repo");
+            }
+            other => panic!("Expected UnmanagedRepository error, got {other:?}"),
+        }
+        assert!(mock_fs.exists(Path::new("/elsewhere/repo")));
+    }
+
+    #[test]
+    fn test_remove_multiple_worktrees_wrong_name_cancels() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_confirm(true);
+        mock_ui.push_input("other");
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UserCancelled)));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert_eq!(
+            mock_ui.get_input_prompts(),
+            vec!["Type the repository name (repo) to confirm".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_remove_repository_interactive_selects_one() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+dev");
+        mock_fs.add_symlink(
+            "/test_root/github.com/user/repo",
+            "/test_root/github.com/user/repo+main",
+        );
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_select(1);
+        mock_ui.set_confirm(true);
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            true,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+dev")));
+        assert!(mock_fs.is_symlink(Path::new("/test_root/github.com/user/repo")));
+        assert!(mock_ui.get_input_prompts().is_empty());
+        assert!(mock_ui.has_printed("Successfully removed 1 repository"));
+    }
+
+    #[test]
+    fn test_remove_repository_interactive_cancel() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_select_cancel();
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            true,
+        );
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UserCancelled)));
+        assert_eq!(result.unwrap_err().exit_code(), 5);
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+    }
+
+    #[test]
+    fn test_remove_repository_interactive_dry_run() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.set_select(0);
+
+        let usecase = RemoveRepositoryUseCase::new(
+            Arc::new(MockGitRepository::new()),
+            mock_fs.clone(),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            true,
+            true,
+        );
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec!["Would remove: /test_root/github.com/user/repo+feature".to_string()]
+        );
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+    }
+
+    #[test]
+    fn test_remove_repository_keeps_dirty_worktree() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        let mock_git = Arc::new(MockGitRepository::new());
+        mock_git.set_dirty("/test_root/github.com/user/repo+feature");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        mock_ui.push_input("repo");
+        mock_ui.set_confirm(false);
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            false,
+            false,
+            false,
+        );
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            RemoveOutcome {
+                removed: 1,
+                skipped: vec![PathBuf::from("/test_root/github.com/user/repo+feature")],
+            }
+        );
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+        assert_eq!(
+            mock_ui.get_warning_messages(),
+            vec![
+                "Kept /test_root/github.com/user/repo+feature which has uncommitted changes"
+                    .to_string()
+            ]
+        );
+        assert!(mock_ui.get_error_messages().is_empty());
+        assert!(mock_ui.has_printed("Successfully removed 1 repository"));
+    }
+
+    #[test]
+    fn test_remove_repository_force_removes_dirty_worktree() {
+        // Arrange
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+
+        let mock_git = Arc::new(MockGitRepository::new());
+        mock_git.set_dirty("/test_root/github.com/user/repo+main");
+
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let usecase = RemoveRepositoryUseCase::new(mock_git, mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
+    }
 }
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, UserInteraction};
+use crate::core::{RepoQuery, RepoScanner};
+use crate::errors::GrmError;
+
+pub struct ResolvePathUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl ResolvePathUseCase {
+    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
+        Self { fs, ui }
+    }
+
+    /// Print the path of the single managed repository matching `query`
+    ///
+    /// Fails without prompting when no repository or several repositories match.
+    pub fn execute(&self, config: &Config, query: &str) -> Result<PathBuf, GrmError> {
+        let candidates = find_candidates(&self.fs, config, query)?;
+
+        if candidates.len() > 1 {
+            return Err(ambiguous(config, query, &candidates));
+        }
+
+        let path = candidates
+            .into_iter()
+            .next()
+            .ok_or_else(|| GrmError::NotFound(format!("No repository matches {query}")))?;
+
+        self.ui.print_result(&path.display().to_string());
+
+        Ok(path)
+    }
+}
+
+/// Scan the managed root for repositories matching `query`
+pub(crate) fn find_candidates(
+    fs: &Arc<dyn FileSystem>,
+    config: &Config,
+    query: &str,
+) -> Result<Vec<PathBuf>, GrmError> {
+    let root = config.root();
+    if !fs.exists(root) {
+        return Ok(Vec::new());
+    }
+
+    let scanner = RepoScanner::new(Arc::clone(fs)).with_layout(config.layout.clone());
+    let candidates = scanner.scan_matching(root, &RepoQuery::parse(query))?;
+
+    Ok(candidates)
+}
+
+fn ambiguous(config: &Config, query: &str, candidates: &[PathBuf]) -> GrmError {
+    let candidates = candidates
+        .iter()
+        .map(|path| {
+            let relative = path.strip_prefix(config.root()).unwrap_or(path);
+            format!("  - {}", relative.display())
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    GrmError::AmbiguousRepository {
+        query: query.to_string(),
+        candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+
+    fn setup() -> (Arc<MockFileSystem>, Arc<MockUserInteraction>, Config) {
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        fs.add_git_repo("/test_root/github.com/user/other+main");
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (fs, ui, config)
+    }
+
+    #[test]
+    fn test_resolve_single_match() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ResolvePathUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "other");
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            PathBuf::from("/test_root/github.com/user/other+main")
+        );
+        assert_eq!(
+            ui.get_printed_messages(),
+            vec!["/test_root/github.com/user/other+main".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_resolve_with_branch() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ResolvePathUseCase::new(fs, ui);
+
+        // Act
+        let result = usecase.execute(&config, "repo+feature");
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            PathBuf::from("/test_root/github.com/user/repo+feature")
+        );
+    }
+
+    #[test]
+    fn test_resolve_ambiguous_fails() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ResolvePathUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "repo");
+
+        // Assert
+        match result {
+            Err(GrmError::AmbiguousRepository { candidates, .. }) => {
+                assert!(candidates.contains("github.com/user/repo+main"));
+                assert!(candidates.contains("github.com/user/repo+feature"));
+            }
+            other => panic!("Expected AmbiguousRepository error, got {other:?}"),
+        }
+        assert!(ui.get_printed_messages().is_empty());
+    }
+
+    #[test]
+    fn test_resolve_not_found() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ResolvePathUseCase::new(fs, ui);
+
+        // Act
+        let result = usecase.execute(&config, "missing");
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotFound(_))));
+    }
+}
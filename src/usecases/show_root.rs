@@ -1,26 +1,57 @@
 use std::sync::Arc;
 
-use crate::core::ports::UserInteraction;
 use crate::configs::Config;
+use crate::core::ports::{FileSystem, UserInteraction};
+use crate::errors::GrmError;
 
 pub struct ShowRootUseCase {
+    fs: Arc<dyn FileSystem>,
     ui: Arc<dyn UserInteraction>,
 }
 
 impl ShowRootUseCase {
-    pub fn new(ui: Arc<dyn UserInteraction>) -> Self {
-        Self { ui }
+    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
+        Self { fs, ui }
     }
 
-    pub fn execute(&self, config: &Config) {
-        self.ui.print(&config.root().display().to_string());
+    /// Print the root directory
+    ///
+    /// # Arguments
+    /// * `create` - Create the root directory if it is missing
+    /// * `verbose` - Also report which config source supplied the root
+    pub fn execute(&self, config: &Config, create: bool, verbose: bool) -> Result<(), GrmError> {
+        let root = config.root();
+
+        if verbose {
+            self.ui
+                .print_error(&format!("Root configured by {}", config.source));
+        }
+
+        if create {
+            // Status goes to stderr so that `cd "$(grm root --create)"` keeps working
+            if self.fs.exists(root) {
+                self.ui.print_error(&format!(
+                    "Root directory already exists: {}",
+                    root.display()
+                ));
+            } else {
+                self.fs.create_dir(root)?;
+                self.ui
+                    .print_error(&format!("Created root directory: {}", root.display()));
+            }
+        }
+
+        self.ui.print_result(&root.display().to_string());
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::test_helpers::MockUserInteraction;
+    use crate::adapters::QuietInteraction;
+    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+    use crate::configs::ConfigSource;
     use std::path::PathBuf;
 
     #[test]
@@ -28,39 +59,125 @@ mod tests {
         // 目的: ルートディレクトリの出力
         // 検証: 正しいパスが出力される
 
+        let fs = Arc::new(MockFileSystem::new());
         let ui = Arc::new(MockUserInteraction::new());
-        let usecase = ShowRootUseCase::new(ui.clone());
+        let usecase = ShowRootUseCase::new(fs, ui.clone());
 
         let root = PathBuf::from("/home/testuser/grm");
-        let config = Config { root: root.clone() };
+        let config = Config::new(root.clone());
 
-        usecase.execute(&config);
+        usecase.execute(&config, false, false).unwrap();
 
         let messages = ui.get_printed_messages();
         assert_eq!(messages.len(), 1);
         assert_eq!(messages[0], root.display().to_string());
     }
 
+    #[test]
+    fn test_execute_prints_root_when_quiet() {
+        // 目的: --quiet 時のルートの出力
+        // 検証: 結果であるルートは QuietInteraction を通しても出力される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let usecase = ShowRootUseCase::new(fs, Arc::new(QuietInteraction::new(ui.clone())));
+
+        let root = PathBuf::from("/home/testuser/grm");
+        let config = Config::new(root.clone());
+
+        usecase.execute(&config, false, false).unwrap();
+
+        assert_eq!(ui.get_printed_messages(), vec![root.display().to_string()]);
+    }
+
     #[test]
     fn test_execute_with_different_roots() {
         // 目的: 異なるルートでの動作
         // 検証: 設定に応じたルートが出力される
 
+        let fs = Arc::new(MockFileSystem::new());
         let ui = Arc::new(MockUserInteraction::new());
-        let usecase = ShowRootUseCase::new(ui.clone());
+        let usecase = ShowRootUseCase::new(fs, ui.clone());
 
         let root1 = PathBuf::from("/custom/path1");
-        let config1 = Config { root: root1.clone() };
-        usecase.execute(&config1);
+        let config1 = Config::new(root1.clone());
+        usecase.execute(&config1, false, false).unwrap();
 
         let root2 = PathBuf::from("/custom/path2");
-        let config2 = Config { root: root2.clone() };
-        usecase.execute(&config2);
+        let config2 = Config::new(root2.clone());
+        usecase.execute(&config2, false, false).unwrap();
 
         let messages = ui.get_printed_messages();
         assert_eq!(messages.len(), 2);
         assert_eq!(messages[0], root1.display().to_string());
         assert_eq!(messages[1], root2.display().to_string());
     }
-}
 
+    #[test]
+    fn test_execute_create_missing_root() {
+        // 目的: --create 指定時にルートが存在しない場合の動作
+        // 検証: ルートが作成され、標準出力にはパスのみが出力される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let usecase = ShowRootUseCase::new(fs.clone(), ui.clone());
+
+        let root = PathBuf::from("/home/testuser/grm");
+        let config = Config::new(root.clone());
+
+        usecase.execute(&config, true, false).unwrap();
+
+        assert!(fs.is_dir(&root));
+        assert_eq!(ui.get_printed_messages(), vec![root.display().to_string()]);
+        assert_eq!(
+            ui.get_error_messages(),
+            vec!["Created root directory: /home/testuser/grm".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_execute_create_existing_root() {
+        // 目的: --create 指定時にルートが既に存在する場合の動作
+        // 検証: 既存であることが通知される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let usecase = ShowRootUseCase::new(fs.clone(), ui.clone());
+
+        let root = PathBuf::from("/home/testuser/grm");
+        fs.add_dir(&root);
+        let config = Config::new(root.clone());
+
+        usecase.execute(&config, true, false).unwrap();
+
+        assert_eq!(ui.get_printed_messages(), vec![root.display().to_string()]);
+        assert_eq!(
+            ui.get_error_messages(),
+            vec!["Root directory already exists: /home/testuser/grm".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_execute_verbose_reports_source() {
+        // 目的: --verbose 指定時の設定元の出力
+        // 検証: 設定元が標準エラーに出力され、標準出力にはパスのみが出力される
+
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+        let usecase = ShowRootUseCase::new(fs, ui.clone());
+
+        let root = PathBuf::from("/home/testuser/grm");
+        let config = Config {
+            source: ConfigSource::Grmrc,
+            ..Config::new(root.clone())
+        };
+
+        usecase.execute(&config, false, true).unwrap();
+
+        assert_eq!(ui.get_printed_messages(), vec![root.display().to_string()]);
+        assert_eq!(
+            ui.get_error_messages(),
+            vec!["Root configured by ~/.grmrc".to_string()]
+        );
+    }
+}
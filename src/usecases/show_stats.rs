@@ -0,0 +1,357 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, UserInteraction};
+use crate::core::{RepoEntry, RepoScanner};
+use crate::errors::GrmError;
+
+const LARGEST_REPOSITORIES: usize = 5;
+
+/// Output format of `grm stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsFormat {
+    Text,
+    Json,
+}
+
+/// Aggregated statistics of the managed root
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub repositories: usize,
+    pub worktrees: usize,
+    /// Number of repositories per host
+    pub hosts: BTreeMap<String, usize>,
+    pub total_size: Option<u64>,
+    pub shared_size: Option<u64>,
+    /// Largest repositories (all worktrees combined), largest first
+    pub largest: Vec<RepoSize>,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct RepoSize {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Aggregate scanned repositories into ``Stats``
+///
+/// # Arguments
+/// * `entries` - Repositories found under the root
+/// * `sizes` - Size of each worktree, or `None` if sizes were not computed
+/// * `shared_size` - Size of the shared storage, or `None` if not computed
+pub(crate) fn aggregate(
+    entries: &[RepoEntry],
+    sizes: Option<&HashMap<PathBuf, u64>>,
+    shared_size: Option<u64>,
+) -> Stats {
+    let mut hosts: BTreeMap<String, usize> = BTreeMap::new();
+    let mut repo_sizes: BTreeMap<String, u64> = BTreeMap::new();
+    let mut worktrees = 0;
+
+    for entry in entries {
+        let Some(info) = &entry.info else {
+            continue;
+        };
+        worktrees += 1;
+
+        let name = format!("{}/{}/{}", info.host, info.user, info.repo);
+        if !repo_sizes.contains_key(&name) {
+            *hosts.entry(info.host.clone()).or_default() += 1;
+        }
+
+        let size = sizes.and_then(|s| s.get(&entry.path)).copied().unwrap_or(0);
+        *repo_sizes.entry(name).or_default() += size;
+    }
+
+    let total_size = sizes.map(|_| repo_sizes.values().sum());
+
+    let mut largest = Vec::new();
+    if sizes.is_some() {
+        largest = repo_sizes
+            .iter()
+            .map(|(name, size)| RepoSize {
+                name: name.clone(),
+                size: *size,
+            })
+            .collect::<Vec<_>>();
+        largest.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        largest.truncate(LARGEST_REPOSITORIES);
+    }
+
+    Stats {
+        repositories: repo_sizes.len(),
+        worktrees,
+        hosts,
+        total_size,
+        shared_size,
+        largest,
+    }
+}
+
+pub struct ShowStatsUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl ShowStatsUseCase {
+    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
+        Self { fs, ui }
+    }
+
+    pub fn execute(
+        &self,
+        config: &Config,
+        format: StatsFormat,
+        with_size: bool,
+    ) -> Result<Stats, GrmError> {
+        let root = config.root();
+        let scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
+
+        let entries = if self.fs.exists(root) {
+            scanner.scan_entries(root)?
+        } else {
+            Vec::new()
+        };
+
+        let (sizes, shared_size) = if with_size {
+            let (sizes, shared_size) = self.measure(root, &entries)?;
+            (Some(sizes), Some(shared_size))
+        } else {
+            (None, None)
+        };
+
+        let stats = aggregate(&entries, sizes.as_ref(), shared_size);
+
+        match format {
+            StatsFormat::Text => self.print_text(&stats),
+            StatsFormat::Json => {
+                let json = serde_json::to_string_pretty(&stats)
+                    .map_err(|e| GrmError::Io(std::io::Error::other(e)))?;
+                self.ui.print_result(&json);
+            }
+        }
+
+        Ok(stats)
+    }
+
+    fn measure(
+        &self,
+        root: &Path,
+        entries: &[RepoEntry],
+    ) -> Result<(HashMap<PathBuf, u64>, u64), GrmError> {
+        let mut sizes = HashMap::new();
+        for entry in entries.iter().filter(|e| e.info.is_some()) {
+            sizes.insert(entry.path.clone(), self.fs.size(&entry.path)?);
+        }
+
+        let shared_root = root.join(".shared");
+        let shared_size = if self.fs.exists(&shared_root) {
+            self.fs.size(&shared_root)?
+        } else {
+            0
+        };
+
+        Ok((sizes, shared_size))
+    }
+
+    fn print_text(&self, stats: &Stats) {
+        let mut summary = vec![
+            ("Repositories", stats.repositories.to_string()),
+            ("Worktrees", stats.worktrees.to_string()),
+        ];
+        if let Some(size) = stats.total_size {
+            summary.push(("Total size", format_size(size)));
+        }
+        if let Some(size) = stats.shared_size {
+            summary.push(("Shared size", format_size(size)));
+        }
+        self.print_table(&summary, "");
+
+        if !stats.hosts.is_empty() {
+            self.ui.print_result("");
+            self.ui.print_result("Repositories per host:");
+            let rows = stats
+                .hosts
+                .iter()
+                .map(|(host, count)| (host.as_str(), count.to_string()))
+                .collect::<Vec<_>>();
+            self.print_table(&rows, "  ");
+        }
+
+        if !stats.largest.is_empty() {
+            self.ui.print_result("");
+            self.ui.print_result("Largest repositories:");
+            let rows = stats
+                .largest
+                .iter()
+                .map(|repo| (repo.name.as_str(), format_size(repo.size)))
+                .collect::<Vec<_>>();
+            self.print_table(&rows, "  ");
+        }
+    }
+
+    fn print_table(&self, rows: &[(&str, String)], indent: &str) {
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (label, value) in rows {
+            self.ui
+                .print_result(&format!("{indent}{label:<width$}  {value}"));
+        }
+    }
+}
+
+/// Format a byte count in binary units (e.g. `1.5 MiB`)
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+    use crate::core::RepoInfo;
+
+    fn entry(path: &str, host: &str, repo: &str, branch: &str) -> RepoEntry {
+        RepoEntry {
+            path: PathBuf::from(path),
+            info: Some(RepoInfo::new(
+                host.to_string(),
+                "user".to_string(),
+                repo.to_string(),
+                Some(branch.to_string()),
+            )),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_counts() {
+        let entries = vec![
+            entry("/r/github.com/user/a+main", "github.com", "a", "main"),
+            entry("/r/github.com/user/a+dev", "github.com", "a", "dev"),
+            entry("/r/github.com/user/b+main", "github.com", "b", "main"),
+            entry("/r/gitlab.com/user/c+main", "gitlab.com", "c", "main"),
+            RepoEntry {
+                path: PathBuf::from("/r/stray"),
+                info: None,
+            },
+        ];
+
+        let stats = aggregate(&entries, None, None);
+
+        assert_eq!(stats.repositories, 3);
+        assert_eq!(stats.worktrees, 4);
+        assert_eq!(stats.hosts.get("github.com"), Some(&2));
+        assert_eq!(stats.hosts.get("gitlab.com"), Some(&1));
+        assert_eq!(stats.total_size, None);
+        assert!(stats.largest.is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_sizes_and_largest() {
+        let mut entries = Vec::new();
+        let mut sizes = HashMap::new();
+        for (i, name) in ["a", "b", "c", "d", "e", "f"].iter().enumerate() {
+            let path = format!("/r/github.com/user/{name}+main");
+            entries.push(entry(&path, "github.com", name, "main"));
+            sizes.insert(PathBuf::from(path), (i as u64 + 1) * 100);
+        }
+        entries.push(entry("/r/github.com/user/a+dev", "github.com", "a", "dev"));
+        sizes.insert(PathBuf::from("/r/github.com/user/a+dev"), 1000);
+
+        let stats = aggregate(&entries, Some(&sizes), Some(42));
+
+        assert_eq!(stats.total_size, Some(3100));
+        assert_eq!(stats.shared_size, Some(42));
+        assert_eq!(stats.largest.len(), LARGEST_REPOSITORIES);
+        assert_eq!(stats.largest[0].name, "github.com/user/a");
+        assert_eq!(stats.largest[0].size, 1100);
+        assert_eq!(stats.largest[1].name, "github.com/user/f");
+        assert!(stats.largest.iter().all(|r| r.name != "github.com/user/b"));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    fn setup() -> (Arc<MockFileSystem>, Arc<MockUserInteraction>, Config) {
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        fs.add_file_with_size("/test_root/github.com/user/repo+main/a.txt", 2048);
+        fs.add_git_repo("/test_root/github.com/user/repo+dev");
+        fs.add_dir("/test_root/.shared");
+        fs.add_file_with_size("/test_root/.shared/b.txt", 10);
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        (fs, ui, config)
+    }
+
+    #[test]
+    fn test_execute_text() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ShowStatsUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, StatsFormat::Text, true);
+
+        // Assert
+        let stats = result.unwrap();
+        assert_eq!(stats.repositories, 1);
+        assert_eq!(stats.worktrees, 2);
+        assert_eq!(stats.total_size, Some(2048));
+        assert_eq!(stats.shared_size, Some(10));
+
+        let messages = ui.get_printed_messages();
+        assert!(messages.contains(&"Repositories  1".to_string()));
+        assert!(messages.contains(&"Total size    2.0 KiB".to_string()));
+        assert!(messages.contains(&"  github.com  1".to_string()));
+        assert!(messages.contains(&"  github.com/user/repo  2.0 KiB".to_string()));
+    }
+
+    #[test]
+    fn test_execute_json_without_size() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = ShowStatsUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, StatsFormat::Json, false);
+
+        // Assert
+        assert!(result.is_ok());
+        let messages = ui.get_printed_messages();
+        assert_eq!(messages.len(), 1);
+        let json: serde_json::Value = serde_json::from_str(&messages[0]).unwrap();
+        assert_eq!(json["repositories"], 1);
+        assert_eq!(json["worktrees"], 2);
+        assert_eq!(json["hosts"]["github.com"], 1);
+        assert!(json["total_size"].is_null());
+    }
+}
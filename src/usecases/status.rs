@@ -0,0 +1,178 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoScanner, is_bare_repository};
+use crate::errors::GrmError;
+
+pub struct StatusUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl StatusUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Print the worktrees under the root that have uncommitted changes
+    ///
+    /// Worktrees whose status cannot be read are reported as warnings and skipped.
+    ///
+    /// # Arguments
+    /// * `all` - Also print clean worktrees
+    ///
+    /// # Returns
+    /// The number of dirty worktrees
+    pub fn execute(&self, config: &Config, all: bool) -> Result<usize, GrmError> {
+        let root = config.root();
+        if !self.fs.exists(root) {
+            return Ok(0);
+        }
+
+        let scanner = RepoScanner::new(Arc::clone(&self.fs))
+            .with_max_depth(config.layout.depth())
+            .with_layout(config.layout.clone());
+        let mut repos = scanner.scan_repositories(root)?;
+        // Bare clones have no working tree to be dirty
+        repos.retain(|repo| !is_bare_repository(self.fs.as_ref(), repo));
+        repos.sort();
+
+        let mut dirty = 0;
+        for repo in repos {
+            let relative = repo
+                .strip_prefix(root)
+                .unwrap_or(&repo)
+                .display()
+                .to_string();
+
+            match self.git.is_dirty(&repo) {
+                Ok(true) => {
+                    dirty += 1;
+                    self.ui.print_result(&format!("dirty  {relative}"));
+                }
+                Ok(false) if all => self.ui.print_result(&format!("clean  {relative}")),
+                Ok(false) => {}
+                Err(e) => self
+                    .ui
+                    .print_warning(&format!("Failed to get status of {relative}: {e}")),
+            }
+        }
+
+        if dirty == 0 && !all {
+            self.ui.print("All repositories are clean");
+        }
+
+        Ok(dirty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::ports::GitError;
+    use std::path::PathBuf;
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
+
+    #[test]
+    fn test_status_prints_dirty_only() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.set_dirty("/test_root/github.com/user/repo+feature");
+        let usecase = StatusUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false);
+
+        // Assert
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec!["dirty  github.com/user/repo+feature".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_status_all() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.set_dirty("/test_root/github.com/user/repo+main");
+        let usecase = StatusUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, true);
+
+        // Assert
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "clean  github.com/user/repo+feature".to_string(),
+                "dirty  github.com/user/repo+main".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_all_clean() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = StatusUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false);
+
+        // Assert
+        assert_eq!(result.unwrap(), 0);
+        assert!(mock_ui.has_printed("All repositories are clean"));
+    }
+
+    #[test]
+    fn test_status_git_error_is_warning() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.inject_error(GitError::Execution("not a git repository".to_string()));
+        let usecase = StatusUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false);
+
+        // Assert
+        assert_eq!(result.unwrap(), 0);
+        assert!(
+            mock_ui
+                .get_warning_messages()
+                .iter()
+                .any(|m| m.starts_with("Failed to get status of github.com/user/repo+"))
+        );
+    }
+}
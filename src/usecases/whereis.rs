@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+
+pub struct WhereisUseCase {
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl WhereisUseCase {
+    pub fn new(fs: Arc<dyn FileSystem>, ui: Arc<dyn UserInteraction>) -> Self {
+        Self { fs, ui }
+    }
+
+    /// Print the managed path of the repository at `url`
+    ///
+    /// With `branch`, only the worktree of that branch is printed; without,
+    /// every worktree of the repository is printed, one per line.
+    ///
+    /// # Returns
+    /// The printed paths, sorted
+    pub fn execute(
+        &self,
+        config: &Config,
+        url: &str,
+        branch: Option<&str>,
+    ) -> Result<Vec<PathBuf>, GrmError> {
+        let root = config.root();
+        let repo_info = RepoInfo::from_url_with(url, config.normalize_host)?;
+
+        let paths = if let Some(branch) = branch {
+            RepoInfo::validate_branch_name(branch)?;
+            let path = repo_info.build_repo_path_with(root, branch, &config.layout);
+            if self.fs.exists(&path) {
+                vec![path]
+            } else {
+                return Err(GrmError::NotFound(format!(
+                    "No worktree of {url} for branch {branch}\nRun `grm clone {url} -b {branch}` to clone it"
+                )));
+            }
+        } else {
+            let mut paths = if self.fs.exists(root) {
+                RepoScanner::new(Arc::clone(&self.fs))
+                    .with_layout(config.layout.clone())
+                    .scan_worktrees(root, &repo_info)?
+            } else {
+                Vec::new()
+            };
+            if paths.is_empty() {
+                return Err(GrmError::NotFound(format!(
+                    "No managed repository for {url}\nRun `grm clone {url}` to clone it"
+                )));
+            }
+            paths.sort();
+            paths
+        };
+
+        for path in &paths {
+            self.ui.print_result(&path.display().to_string());
+        }
+
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockUserInteraction};
+
+    fn setup() -> (Arc<MockFileSystem>, Arc<MockUserInteraction>, Config) {
+        let fs = Arc::new(MockFileSystem::new());
+        let ui = Arc::new(MockUserInteraction::new());
+
+        fs.add_dir("/test_root");
+        fs.add_dir("/test_root/github.com");
+        fs.add_dir("/test_root/github.com/user");
+        fs.add_git_repo("/test_root/github.com/user/repo+main");
+        fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        fs.add_git_repo("/test_root/github.com/user/repository+main");
+
+        (fs, ui, Config::new(PathBuf::from("/test_root")))
+    }
+
+    #[test]
+    fn test_whereis_with_branch() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = WhereisUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "github.com/user/repo", Some("main"));
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            vec![PathBuf::from("/test_root/github.com/user/repo+main")]
+        );
+        assert_eq!(
+            ui.get_printed_messages(),
+            vec!["/test_root/github.com/user/repo+main".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_whereis_without_branch_lists_worktrees() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = WhereisUseCase::new(fs, ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "https://github.com/user/repo.git", None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            ui.get_printed_messages(),
+            vec![
+                "/test_root/github.com/user/repo+feature".to_string(),
+                "/test_root/github.com/user/repo+main".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_whereis_not_found_suggests_clone() {
+        // Arrange
+        let (fs, ui, config) = setup();
+        let usecase = WhereisUseCase::new(fs, ui.clone());
+
+        // Act
+        let missing_branch = usecase.execute(&config, "github.com/user/repo", Some("develop"));
+        let missing_repo = usecase.execute(&config, "github.com/user/other", None);
+
+        // Assert
+        assert!(matches!(
+            missing_branch,
+            Err(GrmError::NotFound(ref message))
+                if message.contains("grm clone github.com/user/repo -b develop")
+        ));
+        assert!(matches!(
+            missing_repo,
+            Err(GrmError::NotFound(ref message)) if message.contains("grm clone github.com/user/other")
+        ));
+        assert!(ui.get_printed_messages().is_empty());
+    }
+}
@@ -29,19 +29,57 @@ impl IsolateFilesUseCase {
             .git
             .get_repository_root()
             .map_err(|_| GrmError::NotInManagedRepository)?;
-        let repo_info = RepoInfo::from_path(config.root(), &repo_root)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )?;
 
         let resource = SharedResource::new(
             repo_info.clone(),
             Arc::clone(&self.fs),
             config.root().to_path_buf(),
-        );
+        )
+        .with_layout(config.layout.clone());
 
         resource.isolate(&repo_root, &relative_path)?;
 
         self.ui.print(&format!("Isolated {path_str}"));
         Ok(())
     }
+
+    /// Isolate every shared file and directory linked into the current worktree
+    ///
+    /// Symlinks which do not point into shared storage are left alone.
+    ///
+    /// # Returns
+    /// The number of isolated paths
+    pub fn execute_all(&self, config: &Config) -> Result<usize, GrmError> {
+        let repo_root = self
+            .git
+            .get_repository_root()
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )?;
+
+        let resource =
+            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf())
+                .with_layout(config.layout.clone());
+
+        let isolated = resource.isolate_all(&repo_root)?;
+        for path in &isolated {
+            self.ui.print(&format!("Isolated {}", path.display()));
+        }
+        self.ui
+            .print(&format!("Isolated {} shared path(s)", isolated.len()));
+
+        Ok(isolated.len())
+    }
 }
 
 #[cfg(test)]
@@ -74,13 +112,11 @@ mod tests {
         // Setup: File is a symlink to shared storage
         let shared_file = PathBuf::from("/test_root/.shared/github.com/user/repo/test.txt");
         mock_fs.add_file(&shared_file);
-        mock_fs.add_symlink(&repo_root.join("test.txt"), &shared_file);
+        mock_fs.add_symlink(repo_root.join("test.txt"), &shared_file);
 
         let usecase = IsolateFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "test.txt");
@@ -119,9 +155,7 @@ mod tests {
 
         let usecase = IsolateFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "nonexistent.txt");
@@ -130,6 +164,44 @@ mod tests {
         assert!(matches!(result, Err(GrmError::NotFound(_))));
     }
 
+    #[test]
+    fn test_isolate_all() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        let shared_root = PathBuf::from("/test_root/.shared/github.com/user/repo");
+        mock_git.set_repo_root(&repo_root);
+
+        mock_fs.create_dir(&repo_root).unwrap();
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.create_dir(&shared_root).unwrap();
+        mock_fs.add_file(shared_root.join(".env"));
+        mock_fs.add_file(shared_root.join("config.json"));
+        mock_fs.add_symlink(repo_root.join(".env"), shared_root.join(".env"));
+        mock_fs.add_symlink(
+            repo_root.join("config.json"),
+            shared_root.join("config.json"),
+        );
+        mock_fs.add_symlink(repo_root.join("docs"), "/srv/docs");
+
+        let usecase = IsolateFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute_all(&config);
+
+        // Assert
+        assert_eq!(result.unwrap(), 2);
+        assert!(!mock_fs.is_symlink(&repo_root.join(".env")));
+        assert!(!mock_fs.is_symlink(&repo_root.join("config.json")));
+        assert!(mock_fs.is_symlink(&repo_root.join("docs")));
+        assert!(mock_ui.has_printed("Isolated 2 shared path(s)"));
+    }
+
     #[test]
     fn test_isolate_not_in_repo() {
         // Arrange
@@ -141,9 +213,7 @@ mod tests {
 
         let usecase = IsolateFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "test.txt");
@@ -152,4 +222,3 @@ mod tests {
         assert!(matches!(result, Err(GrmError::NotInManagedRepository)));
     }
 }
-
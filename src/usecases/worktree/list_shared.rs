@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::RepoInfo;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::shared_resource::SharedResource;
+use crate::errors::GrmError;
+
+pub struct ListSharedUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl ListSharedUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Print the shared files of the current repository, tagged with their group
+    pub fn execute(&self, config: &Config) -> Result<(), GrmError> {
+        let repo_root = self
+            .git
+            .get_repository_root()
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )?;
+
+        let resource =
+            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf())
+                .with_layout(config.layout.clone());
+
+        for (path, group) in resource.list()? {
+            match group {
+                Some(group) => self
+                    .ui
+                    .print_result(&format!("{} [{group}]", path.display())),
+                None => self.ui.print_result(&path.display().to_string()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_list_shared_with_group_tags() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.add_dir("/test_root/.shared");
+        mock_fs.add_dir("/test_root/.shared/github.com");
+        mock_fs.add_dir("/test_root/.shared/github.com/user");
+        mock_fs.add_dir("/test_root/.shared/github.com/user/repo");
+        mock_fs.add_file("/test_root/.shared/github.com/user/repo/.env.app");
+        mock_fs.add_file("/test_root/.shared/github.com/user/repo/config.json");
+        mock_fs.add_file_with_content(
+            "/test_root/.shared/github.com/user/repo/.grm-manifest.toml",
+            "[groups]\n\".env.app\" = \"app\"\n",
+        );
+
+        let usecase = ListSharedUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![".env.app [app]".to_string(), "config.json".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_list_shared_without_storage() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = ListSharedUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config);
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(mock_ui.get_printed_messages().is_empty());
+    }
+}
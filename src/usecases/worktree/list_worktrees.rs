@@ -0,0 +1,237 @@
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+
+pub struct ListWorktreesUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl ListWorktreesUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Print the worktrees of the current repository, marking the current one with `*`
+    ///
+    /// # Arguments
+    /// * `full_path` - Print absolute paths instead of paths relative to the root
+    /// * `managed_only` - Leave out worktrees git knows about outside the managed layout, e.g. in `/tmp`
+    pub fn execute(
+        &self,
+        config: &Config,
+        full_path: bool,
+        managed_only: bool,
+    ) -> Result<(), GrmError> {
+        let repo_root = self
+            .git
+            .get_repository_root()
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+
+        let mut worktrees = self.git.worktree_details(&repo_root)?;
+        if managed_only {
+            let repo_info = RepoInfo::from_path_resolved(
+                self.fs.as_ref(),
+                config.root(),
+                &repo_root,
+                &config.layout,
+            )
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+            let managed = RepoScanner::new(Arc::clone(&self.fs))
+                .with_layout(config.layout.clone())
+                .scan_worktrees(config.root(), &repo_info)?;
+            worktrees.retain(|worktree| managed.contains(&worktree.path));
+        }
+
+        for worktree in worktrees {
+            let marker = if worktree.path == repo_root { "*" } else { " " };
+            let path = if full_path {
+                worktree.path.as_path()
+            } else {
+                worktree
+                    .path
+                    .strip_prefix(config.root())
+                    .unwrap_or(&worktree.path)
+            };
+            self.ui
+                .print_result(&format!("{marker} {}", path.display()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::ports::{WorktreeBranch, WorktreeInfo};
+    use std::path::{Path, PathBuf};
+
+    fn setup() -> (Arc<MockGitRepository>, Arc<MockUserInteraction>) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        mock_git.set_repo_root("/test_root/github.com/user/repo+feature");
+        for branch in ["main", "feature"] {
+            mock_git.add_worktree_detail(WorktreeInfo {
+                path: PathBuf::from(format!("/test_root/github.com/user/repo+{branch}")),
+                branch: Some(branch.to_string()),
+                ..WorktreeInfo::default()
+            });
+        }
+
+        (mock_git, mock_ui)
+    }
+
+    #[test]
+    fn test_list_worktrees_marks_current() {
+        // Arrange
+        let (mock_git, mock_ui) = setup();
+        let usecase = ListWorktreesUseCase::new(
+            mock_git.clone(),
+            Arc::new(MockFileSystem::new()),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, false, false);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "  github.com/user/repo+main".to_string(),
+                "* github.com/user/repo+feature".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_worktrees_full_path() {
+        // Arrange
+        let (mock_git, mock_ui) = setup();
+        let usecase = ListWorktreesUseCase::new(
+            mock_git.clone(),
+            Arc::new(MockFileSystem::new()),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, true, false);
+
+        // Assert
+        assert!(result.is_ok());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "  /test_root/github.com/user/repo+main".to_string(),
+                "* /test_root/github.com/user/repo+feature".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_worktrees_added_through_port() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git
+            .add_worktree(
+                &repo_root,
+                Path::new("/test_root/github.com/user/repo+feature"),
+                "feature",
+                WorktreeBranch::New,
+            )
+            .unwrap();
+        let usecase = ListWorktreesUseCase::new(
+            mock_git.clone(),
+            Arc::new(MockFileSystem::new()),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, false, false);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "* github.com/user/repo+main".to_string(),
+                "  github.com/user/repo+feature".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_worktrees_outside_repository() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = ListWorktreesUseCase::new(
+            mock_git.clone(),
+            Arc::new(MockFileSystem::new()),
+            mock_ui.clone(),
+        );
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, false, false);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotInManagedRepository)));
+    }
+
+    #[test]
+    fn test_list_worktrees_managed_only() {
+        // Arrange
+        let (mock_git, mock_ui) = setup();
+        mock_git.add_worktree_detail(WorktreeInfo {
+            path: PathBuf::from("/tmp/repo-hotfix"),
+            branch: Some("hotfix".to_string()),
+            ..WorktreeInfo::default()
+        });
+        let mock_fs = Arc::new(MockFileSystem::new());
+        mock_fs
+            .create_dir(std::path::Path::new("/test_root/github.com/user"))
+            .unwrap();
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+main");
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        mock_fs.add_git_repo("/tmp/repo-hotfix");
+        let usecase = ListWorktreesUseCase::new(mock_git.clone(), mock_fs, mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, false, true);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![
+                "  github.com/user/repo+main".to_string(),
+                "* github.com/user/repo+feature".to_string(),
+            ]
+        );
+    }
+}
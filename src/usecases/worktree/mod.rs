@@ -1,11 +1,19 @@
 pub mod isolate_files;
+pub mod list_shared;
+pub mod list_worktrees;
+pub mod prune_worktrees;
 pub mod remove_worktree;
 pub mod share_files;
 pub mod split_worktree;
+pub mod switch_worktree;
 pub mod unshare_files;
 
 pub use isolate_files::IsolateFilesUseCase;
+pub use list_shared::ListSharedUseCase;
+pub use list_worktrees::ListWorktreesUseCase;
+pub use prune_worktrees::PruneWorktreesUseCase;
 pub use remove_worktree::RemoveWorktreeUseCase;
 pub use share_files::ShareFilesUseCase;
 pub use split_worktree::SplitWorktreeUseCase;
+pub use switch_worktree::SwitchWorktreeUseCase;
 pub use unshare_files::UnshareFilesUseCase;
@@ -0,0 +1,238 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::RepoInfo;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::errors::GrmError;
+use crate::usecases::RemoveWorktreeUseCase;
+
+pub struct PruneWorktreesUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl PruneWorktreesUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Remove the worktrees whose branch no longer exists locally or on the remote
+    ///
+    /// The main worktree, the current worktree and locked worktrees are kept.
+    ///
+    /// # Arguments
+    /// * `force` - Remove without asking for confirmation
+    /// * `remote` - Remote asked whether a branch still exists, `origin` if `None`
+    ///
+    /// # Returns
+    /// The removed worktree paths
+    pub fn execute(
+        &self,
+        config: &Config,
+        force: bool,
+        remote: Option<&str>,
+    ) -> Result<Vec<PathBuf>, GrmError> {
+        let repo_root = self
+            .git
+            .get_repository_root()
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+        let remote_url = self
+            .git
+            .get_remote_url(&repo_root, remote)
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+
+        let mut stale = Vec::new();
+        // git lists the main worktree first, and it cannot be removed as a worktree
+        for worktree in self.git.worktree_details(&repo_root)?.into_iter().skip(1) {
+            if worktree.path == repo_root || worktree.locked {
+                continue;
+            }
+
+            let Some(branch) = RepoInfo::from_path_resolved(
+                self.fs.as_ref(),
+                config.root(),
+                &worktree.path,
+                &config.layout,
+            )
+            .ok()
+            .and_then(|info| info.branch) else {
+                continue;
+            };
+
+            let gone = !self.git.local_branch_exists(&repo_root, &branch)?
+                || !self.git.remote_branch_exists(&remote_url, &branch)?;
+            if gone {
+                stale.push((branch, worktree.path));
+            }
+        }
+
+        if stale.is_empty() {
+            self.ui.print("No worktrees to prune");
+            return Ok(Vec::new());
+        }
+
+        if !force {
+            self.ui.print("The following worktrees will be removed:");
+            for (_, path) in &stale {
+                self.ui.print(&format!("  - {}", path.display()));
+            }
+            self.ui.print("");
+
+            if !self
+                .ui
+                .confirm("Do you want to continue?")
+                .map_err(|e| GrmError::from_prompt(e, "--force"))?
+            {
+                return Err(GrmError::UserCancelled);
+            }
+        }
+
+        let remove_worktree = RemoveWorktreeUseCase::new(
+            Arc::clone(&self.git),
+            Arc::clone(&self.fs),
+            Arc::clone(&self.ui),
+        );
+        let mut removed = Vec::new();
+        for (branch, path) in stale {
+            remove_worktree.execute(config, &branch, remote, false)?;
+            removed.push(path);
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::ports::WorktreeInfo;
+
+    const REMOTE_URL: &str = "https://github.com/user/repo";
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, REMOTE_URL);
+        mock_fs.add_dir("/test_root/github.com/user");
+
+        for branch in ["main", "merged", "active"] {
+            let path = PathBuf::from(format!("/test_root/github.com/user/repo+{branch}"));
+            mock_fs.add_git_repo(&path);
+            mock_git.add_local_branch(branch);
+            mock_git.add_worktree_detail(WorktreeInfo {
+                path,
+                branch: Some(branch.to_string()),
+                ..WorktreeInfo::default()
+            });
+        }
+        mock_git.add_remote_branch(REMOTE_URL, "main");
+        mock_git.add_remote_branch(REMOTE_URL, "active");
+
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
+
+    #[test]
+    fn test_prune_removes_worktrees_of_gone_branches() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_ui.set_confirm(true);
+        let usecase = PruneWorktreesUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false, None);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            vec![PathBuf::from("/test_root/github.com/user/repo+merged")]
+        );
+        assert!(mock_ui.has_printed("  - /test_root/github.com/user/repo+merged"));
+        assert!(mock_ui.has_printed("Removed worktree: /test_root/github.com/user/repo+merged"));
+    }
+
+    #[test]
+    fn test_prune_cancelled() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_ui.set_confirm(false);
+        let usecase = PruneWorktreesUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false, None);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::UserCancelled)));
+        assert!(
+            !mock_ui
+                .get_printed_messages()
+                .iter()
+                .any(|m| m.starts_with("Removed worktree"))
+        );
+    }
+
+    #[test]
+    fn test_prune_force_skips_prompt_and_keeps_locked() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let locked = PathBuf::from("/test_root/github.com/user/repo+locked");
+        mock_fs.add_git_repo(&locked);
+        mock_git.add_worktree_detail(WorktreeInfo {
+            path: locked,
+            branch: Some("locked".to_string()),
+            locked: true,
+            ..WorktreeInfo::default()
+        });
+        let usecase = PruneWorktreesUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, true, None);
+
+        // Assert
+        assert_eq!(
+            result.unwrap(),
+            vec![PathBuf::from("/test_root/github.com/user/repo+merged")]
+        );
+        assert!(
+            !mock_ui
+                .get_printed_messages()
+                .iter()
+                .any(|m| m.contains("will be removed"))
+        );
+    }
+
+    #[test]
+    fn test_prune_nothing_to_remove() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.add_remote_branch(REMOTE_URL, "merged");
+        let usecase = PruneWorktreesUseCase::new(mock_git, mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, false, None);
+
+        // Assert
+        assert!(result.unwrap().is_empty());
+        assert!(mock_ui.has_printed("No worktrees to prune"));
+    }
+}
@@ -1,40 +1,75 @@
 use std::sync::Arc;
 
-use crate::core::ports::{GitRepository, UserInteraction};
 use crate::configs::Config;
-use crate::core::RepoInfo;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, cleanup};
 use crate::errors::GrmError;
 
 pub struct RemoveWorktreeUseCase {
     git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
     ui: Arc<dyn UserInteraction>,
 }
 
 impl RemoveWorktreeUseCase {
-    pub fn new(git: Arc<dyn GitRepository>, ui: Arc<dyn UserInteraction>) -> Self {
-        Self { git, ui }
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
     }
 
-    pub fn execute(&self, config: &Config, branch: &str) -> Result<(), GrmError> {
+    /// Remove the worktree of `branch`
+    ///
+    /// # Arguments
+    /// * `remote` - Remote whose URL locates the repository under the root, `origin` if `None`
+    /// * `delete_branch` - Also delete the local branch once the worktree is removed
+    pub fn execute(
+        &self,
+        config: &Config,
+        branch: &str,
+        remote: Option<&str>,
+        delete_branch: bool,
+    ) -> Result<(), GrmError> {
         let repo_root = self
             .git
             .get_repository_root()
             .map_err(|_| GrmError::NotInManagedRepository)?;
         let remote_url = self
             .git
-            .get_remote_url(&repo_root)
+            .get_remote_url(&repo_root, remote)
             .map_err(|_| GrmError::NotInManagedRepository)?;
-        let repo_info = RepoInfo::from_url(&remote_url)?;
+        let repo_info = RepoInfo::from_url_with(&remote_url, config.normalize_host)?;
 
-        let worktree_path = repo_info.build_repo_path(config.root(), branch);
+        let worktree_path = repo_info.build_repo_path_with(config.root(), branch, &config.layout);
 
-        if !worktree_path.exists() {
+        if !self.fs.exists(&worktree_path) {
             return Err(GrmError::NotFound(format!(
                 "Worktree does not exist: {}",
                 worktree_path.display()
             )));
         }
 
+        // git refuses to delete it anyway, but only after the worktree is gone
+        if delete_branch && self.git.get_current_branch(&repo_root)? == branch {
+            return Err(GrmError::InvalidBranch {
+                branch: branch.to_string(),
+                reason: "it is checked out in the current worktree".to_string(),
+            });
+        }
+
+        let locked = self
+            .git
+            .worktree_details(&repo_root)?
+            .iter()
+            .any(|wt| wt.path == worktree_path && wt.locked);
+        if locked {
+            return Err(GrmError::WorktreeLocked(
+                worktree_path.display().to_string(),
+            ));
+        }
+
         self.git
             .remove_worktree(&worktree_path)
             .map_err(GrmError::Git)?;
@@ -42,6 +77,29 @@ impl RemoveWorktreeUseCase {
         self.ui
             .print(&format!("Removed worktree: {}", worktree_path.display()));
 
+        // Drop the default-branch link rather than leaving it dangling
+        if let Some(link_path) = repo_info.build_repo_path_plain(config.root(), &config.layout)
+            && self.fs.is_symlink(&link_path)
+            && self
+                .fs
+                .read_link(&link_path)
+                .is_ok_and(|target| target == worktree_path)
+        {
+            self.fs.remove(&link_path)?;
+            self.ui
+                .print(&format!("Removed link: {}", link_path.display()));
+        }
+
+        for dir in cleanup::remove_empty_parents(self.fs.as_ref(), config.root(), &worktree_path) {
+            self.ui
+                .print(&format!("Removed empty directory: {}", dir.display()));
+        }
+
+        if delete_branch {
+            self.git.delete_branch(&repo_root, branch)?;
+            self.ui.print(&format!("Deleted branch: {branch}"));
+        }
+
         Ok(())
     }
 }
@@ -49,66 +107,154 @@ impl RemoveWorktreeUseCase {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::test_helpers::{MockGitRepository, MockUserInteraction};
-    use std::path::PathBuf;
-    use std::fs;
-
-    #[test]
-    fn test_remove_worktree_success() {
-        // Arrange
-        let temp_dir = tempfile::tempdir().unwrap();
-        let test_root = temp_dir.path().to_path_buf();
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::ports::{WorktreeBranch, WorktreeInfo};
+    use std::path::{Path, PathBuf};
 
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
         let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
         let mock_ui = Arc::new(MockUserInteraction::new());
 
-        let repo_root = test_root.join("github.com/user/repo+main");
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
         mock_git.set_repo_root(&repo_root);
         mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
 
-        let worktree_path = test_root.join("github.com/user/repo+feature");
-        fs::create_dir_all(&worktree_path).unwrap();
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
 
-        let usecase = RemoveWorktreeUseCase::new(mock_git.clone(), mock_ui.clone());
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
 
-        let config = Config {
-            root: test_root.clone(),
-        };
+    #[test]
+    fn test_remove_worktree_success() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = RemoveWorktreeUseCase::new(mock_git, mock_fs, mock_ui.clone());
 
         // Act
-        let result = usecase.execute(&config, "feature");
+        let result = usecase.execute(&config, "feature", None, false);
 
         // Assert
         assert!(result.is_ok());
         let messages = mock_ui.get_printed_messages();
-        assert!(messages
-            .iter()
-            .any(|m| m.contains("Removed worktree")));
+        assert!(messages.iter().any(|m| m.contains("Removed worktree")));
     }
 
     #[test]
     fn test_remove_worktree_not_exists() {
         // Arrange
-        let temp_dir = tempfile::tempdir().unwrap();
-        let test_root = temp_dir.path().to_path_buf();
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = RemoveWorktreeUseCase::new(mock_git, mock_fs, mock_ui);
 
-        let mock_git = Arc::new(MockGitRepository::new());
-        let mock_ui = Arc::new(MockUserInteraction::new());
+        // Act
+        let result = usecase.execute(&config, "nonexistent", None, false);
 
-        let repo_root = test_root.join("github.com/user/repo+main");
-        mock_git.set_repo_root(&repo_root);
-        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotFound(_))));
+    }
 
-        let usecase = RemoveWorktreeUseCase::new(mock_git.clone(), mock_ui.clone());
+    #[test]
+    fn test_remove_worktree_drops_default_link() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let link = PathBuf::from("/test_root/github.com/user/repo");
+        mock_fs.add_symlink(&link, "/test_root/github.com/user/repo+feature");
+        let usecase = RemoveWorktreeUseCase::new(mock_git, mock_fs.clone(), mock_ui);
 
-        let config = Config {
-            root: test_root.clone(),
-        };
+        // Act
+        let result = usecase.execute(&config, "feature", None, false);
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(!mock_fs.exists(&link));
+    }
+
+    #[test]
+    fn test_remove_worktree_keeps_link_to_other_worktree() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let link = PathBuf::from("/test_root/github.com/user/repo");
+        mock_fs.add_symlink(&link, "/test_root/github.com/user/repo+main");
+        let usecase = RemoveWorktreeUseCase::new(mock_git, mock_fs.clone(), mock_ui);
 
         // Act
-        let result = usecase.execute(&config, "nonexistent");
+        let result = usecase.execute(&config, "feature", None, false);
 
         // Assert
-        assert!(matches!(result, Err(GrmError::NotFound(_))));
+        assert!(result.is_ok());
+        assert!(mock_fs.is_symlink(&link));
+    }
+
+    #[test]
+    fn test_remove_worktree_refuses_locked() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let worktree_path = PathBuf::from("/test_root/github.com/user/repo+feature");
+        mock_git
+            .add_worktree(
+                Path::new("/"),
+                &worktree_path,
+                "feature",
+                WorktreeBranch::Existing,
+            )
+            .unwrap();
+        mock_git.add_worktree_detail(WorktreeInfo {
+            path: worktree_path.clone(),
+            branch: Some("feature".to_string()),
+            locked: true,
+            ..WorktreeInfo::default()
+        });
+        let usecase = RemoveWorktreeUseCase::new(mock_git.clone(), mock_fs, mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, "feature", None, false);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::WorktreeLocked(_))));
+        assert_eq!(mock_git.get_worktrees(), vec![worktree_path]);
+    }
+
+    #[test]
+    fn test_remove_worktree_delete_branch() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.set_current_branch("/test_root/github.com/user/repo+main", "main");
+        let usecase = RemoveWorktreeUseCase::new(mock_git.clone(), mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "feature", None, true);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(mock_git.get_deleted_branches(), vec!["feature".to_string()]);
+        assert!(mock_ui.has_printed("Deleted branch: feature"));
+    }
+
+    #[test]
+    fn test_remove_worktree_delete_branch_refuses_current_branch() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        mock_git.set_current_branch("/test_root/github.com/user/repo+main", "main");
+        let usecase = RemoveWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, "main", None, true);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::InvalidBranch { .. })));
+        assert!(mock_git.get_deleted_branches().is_empty());
+        assert!(mock_fs.exists(Path::new("/test_root/github.com/user/repo+main")));
     }
 }
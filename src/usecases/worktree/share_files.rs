@@ -1,10 +1,12 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use glob::{MatchOptions, Pattern};
+
 use crate::configs::Config;
 use crate::core::RepoInfo;
 use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
-use crate::core::shared_resource::SharedResource;
+use crate::core::shared_resource::{CopyMode, SharedResource};
 use crate::errors::GrmError;
 
 pub struct ShareFilesUseCase {
@@ -22,16 +24,36 @@ impl ShareFilesUseCase {
         Self { git, fs, ui }
     }
 
-    pub fn execute(&self, config: &Config, path_str: &str) -> Result<(), GrmError> {
+    /// Share a file or directory across all worktrees
+    ///
+    /// # Arguments
+    /// * `path_str` - Absolute path, or path relative to the current directory
+    /// * `allow_sensitive` - Share even if the path matches the `share_deny` patterns
+    /// * `group` - Only link into worktrees assigned to this share group
+    /// * `mode` - Whether the original is moved or copied into shared storage
+    pub fn execute(
+        &self,
+        config: &Config,
+        path_str: &str,
+        allow_sensitive: bool,
+        group: Option<&str>,
+        mode: CopyMode,
+    ) -> Result<(), GrmError> {
         let repo_root = self
             .git
             .get_repository_root()
             .map_err(|_| GrmError::NotInManagedRepository)?;
-        let repo_info = RepoInfo::from_path(config.root(), &repo_root)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )?;
 
         let relative_path = PathBuf::from(path_str);
         let current_dir = self.fs.current_dir()?;
-        let absolute_source_path = current_dir.join(&relative_path);
+        // Resolved like `SharedResource::share` does: absolute paths are kept, `..` is applied
+        let absolute_source_path = self.fs.normalize(&relative_path, &current_dir)?;
 
         if !self.fs.exists(&absolute_source_path) {
             return Err(GrmError::NotFound(format!(
@@ -39,10 +61,15 @@ impl ShareFilesUseCase {
             )));
         }
 
+        if !allow_sensitive {
+            self.check_deny_list(config, &repo_root, &absolute_source_path)?;
+        }
+
         let resource =
-            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf());
+            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf())
+                .with_layout(config.layout.clone());
 
-        let conflicts = resource.conflicts(&repo_root, &relative_path)?;
+        let conflicts = resource.conflicts(&repo_root, &relative_path, group)?;
         if !conflicts.is_empty() {
             self.ui.print("The following files will be overwritten:");
             for conflict in &conflicts {
@@ -54,12 +81,73 @@ impl ShareFilesUseCase {
             }
         }
 
-        resource.share(&repo_root, &relative_path)?;
+        resource.share(&repo_root, &relative_path, group, mode)?;
 
         self.ui
             .print(&format!("Shared {path_str} across worktrees"));
         Ok(())
     }
+
+    /// Refuse to share paths matching the `share_deny` patterns
+    ///
+    /// For directories, every file inside is checked.
+    fn check_deny_list(
+        &self,
+        config: &Config,
+        repo_root: &Path,
+        path: &Path,
+    ) -> Result<(), GrmError> {
+        if config.share_deny.is_empty() {
+            return Ok(());
+        }
+
+        let patterns = config
+            .share_deny
+            .iter()
+            .map(|pattern| {
+                Pattern::new(pattern).map_err(|e| GrmError::InvalidFormat {
+                    format: pattern.clone(),
+                    reason: e.msg.to_string(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut queue = vec![path.to_path_buf()];
+        while let Some(current) = queue.pop() {
+            let relative = current.strip_prefix(repo_root).unwrap_or(&current);
+            if let Some(pattern) = patterns
+                .iter()
+                .find(|pattern| deny_pattern_matches(pattern, relative))
+            {
+                return Err(GrmError::SensitivePath {
+                    path: relative.display().to_string(),
+                    pattern: pattern.as_str().to_string(),
+                });
+            }
+
+            if self.fs.is_dir(&current) && !self.fs.is_symlink(&current) {
+                queue.extend(self.fs.read_dir(&current)?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Patterns without `/` match the file name, others the repository-relative path
+fn deny_pattern_matches(pattern: &Pattern, relative: &Path) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::default()
+    };
+
+    if pattern.as_str().contains('/') {
+        return pattern.matches_path_with(relative, options);
+    }
+
+    relative
+        .file_name()
+        .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options))
 }
 
 #[cfg(test)]
@@ -88,20 +176,18 @@ mod tests {
 
         // Set current directory to repo root
         mock_fs.set_current_dir(&repo_root);
-        mock_fs.add_file(&repo_root.join("test.txt"));
+        mock_fs.add_file(repo_root.join("test.txt"));
 
         let usecase = ShareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "test.txt");
+        let result = usecase.execute(&config, "test.txt", false, None, CopyMode::Move);
 
         // Assert
         if let Err(ref e) = result {
-            eprintln!("Error: {:?}", e);
+            eprintln!("Error: {e:?}");
         }
         assert!(result.is_ok(), "Failed with error: {:?}", result.err());
         let messages = mock_ui.get_printed_messages();
@@ -138,21 +224,19 @@ mod tests {
         // Setup: File is already shared (exists in shared storage and is a symlink in repo+main)
         let shared_file = PathBuf::from("/test_root/.shared/github.com/user/repo/test.txt");
         mock_fs.add_file(&shared_file);
-        mock_fs.add_symlink(&repo_root.join("test.txt"), &shared_file);
+        mock_fs.add_symlink(repo_root.join("test.txt"), &shared_file);
 
         // Add conflicting worktree with a real file (not a symlink)
         let worktree = PathBuf::from("/test_root/github.com/user/repo+feature");
         mock_fs.add_git_repo(&worktree);
-        mock_fs.add_file(&worktree.join("test.txt"));
+        mock_fs.add_file(worktree.join("test.txt"));
 
         let usecase = ShareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "test.txt");
+        let result = usecase.execute(&config, "test.txt", false, None, CopyMode::Move);
 
         // Assert
         assert!(result.is_ok());
@@ -190,21 +274,19 @@ mod tests {
         // Setup: File is already shared (exists in shared storage and is a symlink in repo+main)
         let shared_file = PathBuf::from("/test_root/.shared/github.com/user/repo/test.txt");
         mock_fs.add_file(&shared_file);
-        mock_fs.add_symlink(&repo_root.join("test.txt"), &shared_file);
+        mock_fs.add_symlink(repo_root.join("test.txt"), &shared_file);
 
         // Add conflicting worktree with a real file (not a symlink)
         let worktree = PathBuf::from("/test_root/github.com/user/repo+feature");
         mock_fs.add_git_repo(&worktree);
-        mock_fs.add_file(&worktree.join("test.txt"));
+        mock_fs.add_file(worktree.join("test.txt"));
 
         let usecase = ShareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "test.txt");
+        let result = usecase.execute(&config, "test.txt", false, None, CopyMode::Move);
 
         // Assert
         assert!(matches!(result, Err(GrmError::UserCancelled)));
@@ -230,14 +312,188 @@ mod tests {
 
         let usecase = ShareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "nonexistent.txt");
+        let result = usecase.execute(&config, "nonexistent.txt", false, None, CopyMode::Move);
 
         // Assert
         assert!(matches!(result, Err(GrmError::NotFound(_))));
     }
+
+    fn setup_sensitive() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        PathBuf,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.set_current_dir(&repo_root);
+
+        (mock_git, mock_fs, mock_ui, repo_root)
+    }
+
+    #[test]
+    fn test_share_sensitive_file_refused() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, repo_root) = setup_sensitive();
+        mock_fs.add_file(repo_root.join("server.pem"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "server.pem", false, None, CopyMode::Move);
+
+        // Assert
+        match result {
+            Err(GrmError::SensitivePath { path, pattern }) => {
+                assert_eq!(path, "server.pem");
+                assert_eq!(pattern, "*.pem");
+            }
+            other => panic!("Expected SensitivePath error, got {other:?}"),
+        }
+        assert!(!mock_fs.is_symlink(&repo_root.join("server.pem")));
+        assert!(!mock_fs.exists(Path::new("/test_root/.shared")));
+    }
+
+    #[test]
+    fn test_share_directory_with_nested_sensitive_file_refused() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, repo_root) = setup_sensitive();
+        mock_fs.add_dir(repo_root.join("config"));
+        mock_fs.add_file(repo_root.join("config/app.toml"));
+        mock_fs.add_dir(repo_root.join("config/ssh"));
+        mock_fs.add_file(repo_root.join("config/ssh/id_rsa"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "config", false, None, CopyMode::Move);
+
+        // Assert
+        match result {
+            Err(GrmError::SensitivePath { path, .. }) => {
+                assert_eq!(path, "config/ssh/id_rsa");
+            }
+            other => panic!("Expected SensitivePath error, got {other:?}"),
+        }
+        assert!(mock_fs.is_dir(&repo_root.join("config")));
+        assert!(!mock_fs.is_symlink(&repo_root.join("config")));
+    }
+
+    #[test]
+    fn test_share_sensitive_file_with_override() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, repo_root) = setup_sensitive();
+        mock_fs.add_file(repo_root.join("server.pem"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "server.pem", true, None, CopyMode::Move);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_fs.is_symlink(&repo_root.join("server.pem")));
+    }
+
+    #[test]
+    fn test_share_with_custom_deny_list() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, repo_root) = setup_sensitive();
+        mock_fs.add_file(repo_root.join("server.pem"));
+        mock_fs.add_file(repo_root.join(".env"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+        let mut config = Config::new(PathBuf::from("/test_root"));
+        config.share_deny = vec![".env".to_string()];
+
+        // Act & Assert
+        assert!(matches!(
+            usecase.execute(&config, ".env", false, None, CopyMode::Move),
+            Err(GrmError::SensitivePath { .. })
+        ));
+        assert!(
+            usecase
+                .execute(&config, "server.pem", false, None, CopyMode::Move)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_share_deny_character_class() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, repo_root) = setup_sensitive();
+        mock_fs.add_file(repo_root.join("id_dsa.pub"));
+        mock_fs.add_file(repo_root.join("id_ecdsa"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+        let mut config = Config::new(PathBuf::from("/test_root"));
+        config.share_deny = vec!["id_[rd]sa*".to_string()];
+
+        // Act & Assert
+        match usecase.execute(&config, "id_dsa.pub", false, None, CopyMode::Move) {
+            Err(GrmError::SensitivePath { pattern, .. }) => assert_eq!(pattern, "id_[rd]sa*"),
+            other => panic!("Expected SensitivePath error, got {other:?}"),
+        }
+        assert!(
+            usecase
+                .execute(&config, "id_ecdsa", false, None, CopyMode::Move)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_share_file_by_absolute_path() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature");
+        mock_fs.add_dir(repo_root.join("src"));
+        mock_fs.add_file(repo_root.join(".env"));
+
+        // The current directory is a subdirectory, so joining would look for src/.env
+        mock_fs.set_current_dir(repo_root.join("src"));
+
+        let usecase = ShareFilesUseCase::new(mock_git, mock_fs.clone(), mock_ui);
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "/test_root/github.com/user/repo+main/.env",
+            false,
+            None,
+            CopyMode::Move,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_fs.exists(Path::new("/test_root/.shared/github.com/user/repo/.env")));
+        assert!(mock_fs.is_symlink(&repo_root.join(".env")));
+        assert!(mock_fs.is_symlink(Path::new("/test_root/github.com/user/repo+feature/.env")));
+    }
 }
@@ -1,9 +1,12 @@
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use crate::configs::Config;
-use crate::core::RepoInfo;
-use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::ports::{
+    DEFAULT_REMOTE, FileSystem, GitRepository, UserInteraction, WorktreeBranch,
+};
 use crate::core::shared_resource::SharedResource;
+use crate::core::{RepoInfo, RepoMarker};
 use crate::errors::GrmError;
 
 pub struct SplitWorktreeUseCase {
@@ -21,18 +24,70 @@ impl SplitWorktreeUseCase {
         Self { git, fs, ui }
     }
 
-    pub fn execute(&self, config: &Config, branch: &str) -> Result<(), GrmError> {
+    /// Add a worktree for `branch` and print its path
+    ///
+    /// # Arguments
+    /// * `groups` - Share groups to assign to the new worktree
+    /// * `fetch` - Fetch the remotes first, so newly pushed branches are found locally
+    /// * `remote` - Remote whose URL locates the repository under the root, `origin` if `None`
+    /// * `at` - Check out this tag or commit with a detached `HEAD`, `branch` only names the worktree
+    pub fn execute(
+        &self,
+        config: &Config,
+        branch: &str,
+        groups: &[String],
+        fetch: bool,
+        remote: Option<&str>,
+        at: Option<&str>,
+    ) -> Result<(), GrmError> {
         let repo_root = self
             .git
             .get_repository_root()
             .map_err(|_| GrmError::NotInManagedRepository)?;
         let remote_url = self
             .git
-            .get_remote_url(&repo_root)
+            .get_remote_url(&repo_root, remote)
             .map_err(|_| GrmError::NotInManagedRepository)?;
-        let repo_info = RepoInfo::from_url(&remote_url)?;
 
-        let dest_path = repo_info.build_repo_path(config.root(), branch);
+        if fetch {
+            self.git.fetch(&repo_root)?;
+        }
+
+        let dest_path = self.split(config, &repo_root, remote, &remote_url, branch, groups, at)?;
+
+        self.ui.print(&dest_path.display().to_string());
+
+        Ok(())
+    }
+
+    /// Add a worktree for `branch` next to the repository at `repo_root`
+    ///
+    /// Shared resources of the repository are mounted into the new worktree,
+    /// limited to ungrouped resources and those of `groups`.
+    /// With `at`, the worktree is named after `branch` but detached at `at`.
+    /// A branch which exists only on the remote is checked out as a local branch tracking it.
+    ///
+    /// # Arguments
+    /// * `remote` - Name of the remote at `remote_url`, `origin` if `None`
+    ///
+    /// # Returns
+    /// * `Ok(PathBuf)` - Path of the created worktree
+    // The remote is needed by name to track it, and by URL to locate the repository
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn split(
+        &self,
+        config: &Config,
+        repo_root: &Path,
+        remote: Option<&str>,
+        remote_url: &str,
+        branch: &str,
+        groups: &[String],
+        at: Option<&str>,
+    ) -> Result<PathBuf, GrmError> {
+        let repo_info = RepoInfo::from_url_with(remote_url, config.normalize_host)?;
+        RepoInfo::validate_branch_name(branch)?;
+
+        let dest_path = repo_info.build_repo_path_with(config.root(), branch, &config.layout);
 
         if self.fs.exists(&dest_path) {
             return Err(GrmError::AlreadyExists(dest_path.display().to_string()));
@@ -42,18 +97,47 @@ impl SplitWorktreeUseCase {
             self.fs.create_dir(parent)?;
         }
 
-        let already_exists = self.git.local_branch_exists(branch)?
-            || self.git.remote_branch_exists(&remote_url, branch)?;
-
-        self.git.add_worktree(&dest_path, branch, !already_exists)?;
+        if let Some(commitish) = at {
+            self.git
+                .add_worktree_detached(repo_root, &dest_path, commitish)?;
+        } else {
+            let source = if self.git.local_branch_exists(repo_root, branch)? {
+                WorktreeBranch::Existing
+            } else if self.git.remote_branch_exists(remote_url, branch)? {
+                WorktreeBranch::Track(remote.unwrap_or(DEFAULT_REMOTE).to_string())
+            } else {
+                WorktreeBranch::New
+            };
+
+            self.git
+                .add_worktree(repo_root, &dest_path, branch, source)?;
+        }
 
-        self.ui.print(&dest_path.display().to_string());
+        // The new worktree shares the exclude file of the repository it was split from
+        let marker = RepoMarker::new(&repo_info, branch, remote_url);
+        if let Err(e) = marker
+            .save(self.fs.as_ref(), &dest_path)
+            .and_then(|()| RepoMarker::exclude(self.fs.as_ref(), repo_root))
+        {
+            self.ui
+                .print_warning(&format!("Failed to write repository marker: {e}"));
+        }
 
+        let shared_root = repo_info.build_shared_path(config.root(), Path::new(""));
         let shared_resource =
-            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf());
-        shared_resource.mount(&repo_root)?;
+            SharedResource::new(repo_info, Arc::clone(&self.fs), config.root().to_path_buf())
+                .with_layout(config.layout.clone());
+        if !groups.is_empty() {
+            shared_resource.assign_groups(branch, groups)?;
+        }
+        if self.fs.exists(&shared_root) {
+            let linked = shared_resource.mount(&dest_path)?;
+            if linked > 0 {
+                self.ui.print(&format!("Linked {linked} shared file(s)"));
+            }
+        }
 
-        Ok(())
+        Ok(dest_path)
     }
 }
 
@@ -61,6 +145,8 @@ impl SplitWorktreeUseCase {
 mod tests {
     use super::*;
     use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+    use crate::core::share_manifest::ShareManifest;
+    use crate::usecases::{ListOptions, ListRepositoriesUseCase, RemoveRepositoryUseCase};
     use std::path::PathBuf;
 
     #[test]
@@ -85,16 +171,14 @@ mod tests {
 
         let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "feature");
+        let result = usecase.execute(&config, "feature", &[], false, None, None);
 
         // Assert
         if let Err(ref e) = result {
-            eprintln!("Error: {:?}", e);
+            eprintln!("Error: {e:?}");
         }
         assert!(result.is_ok(), "Failed with error: {:?}", result.err());
         let worktrees = mock_git.get_worktrees();
@@ -103,6 +187,8 @@ mod tests {
             worktrees[0],
             PathBuf::from("/test_root/github.com/user/repo+feature")
         );
+        // The shared storage is empty, so nothing is reported
+        assert!(!mock_ui.has_printed("Linked"));
     }
 
     #[test]
@@ -128,12 +214,10 @@ mod tests {
 
         let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "develop");
+        let result = usecase.execute(&config, "develop", &[], false, None, None);
 
         // Assert
         assert!(result.is_ok());
@@ -164,17 +248,40 @@ mod tests {
 
         let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "feature");
+        let result = usecase.execute(&config, "feature", &[], false, None, None);
 
         // Assert
         assert!(matches!(result, Err(GrmError::AlreadyExists(_))));
     }
 
+    #[test]
+    fn test_split_worktree_invalid_branch() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "../escape", &[], false, None, None);
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::InvalidBranch { .. })));
+        assert!(mock_git.get_worktrees().is_empty());
+    }
+
     #[test]
     fn test_split_worktree_not_in_repo() {
         // Arrange
@@ -186,12 +293,10 @@ mod tests {
 
         let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "feature");
+        let result = usecase.execute(&config, "feature", &[], false, None, None);
 
         // Assert
         assert!(matches!(result, Err(GrmError::NotInManagedRepository)));
@@ -221,12 +326,10 @@ mod tests {
 
         let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
-        let result = usecase.execute(&config, "release");
+        let result = usecase.execute(&config, "release", &[], false, None, None);
 
         // Assert
         assert!(result.is_ok());
@@ -236,5 +339,317 @@ mod tests {
             worktrees[0],
             PathBuf::from("/test_root/github.com/user/repo+release")
         );
+        assert_eq!(
+            mock_git.get_tracked_worktrees(),
+            vec![(
+                PathBuf::from("/test_root/github.com/user/repo+release"),
+                "origin/release".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_split_worktree_mounts_shared_into_new_worktree() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.add_dir("/test_root/.shared");
+        mock_fs.add_dir("/test_root/.shared/github.com");
+        mock_fs.add_dir("/test_root/.shared/github.com/user");
+        mock_fs.add_dir("/test_root/.shared/github.com/user/repo");
+        mock_fs.add_file("/test_root/.shared/github.com/user/repo/.env");
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "feature", &[], false, None, None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_fs.is_symlink(Path::new("/test_root/github.com/user/repo+feature/.env")));
+        assert!(!mock_fs.is_symlink(&repo_root.join(".env")));
+        assert!(mock_ui.has_printed("Linked 1 shared file(s)"));
+    }
+
+    #[test]
+    fn test_split_worktree_with_group_records_assignment() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(
+            &config,
+            "feature-x",
+            &["app".to_string()],
+            false,
+            None,
+            None,
+        );
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        let manifest = ShareManifest::load(
+            mock_fs.as_ref(),
+            Path::new("/test_root/.shared/github.com/user/repo/.grm-manifest.toml"),
+        )
+        .unwrap();
+        assert!(manifest.has_group(Some("feature-x"), "app"));
+    }
+
+    #[test]
+    fn test_split_worktree_with_fetch() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+        mock_git.add_remote_branch("https://github.com/user/repo", "pushed");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "pushed", &[], true, None, None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(mock_git.get_fetched(), vec![repo_root]);
+        assert!(
+            mock_git
+                .get_worktrees()
+                .contains(&PathBuf::from("/test_root/github.com/user/repo+pushed"))
+        );
+    }
+
+    #[test]
+    fn test_split_worktree_without_shared_storage() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "feature", &[], false, None, None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(mock_git.get_worktrees().len(), 1);
+    }
+
+    #[test]
+    fn test_split_worktree_named_remote() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/canonical/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/fork/repo");
+        mock_git.set_named_remote_url(&repo_root, "upstream", "https://github.com/canonical/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/canonical");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "feature", &[], false, Some("upstream"), None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_git.get_worktrees(),
+            vec![PathBuf::from(
+                "/test_root/github.com/canonical/repo+feature"
+            )]
+        );
+        assert!(mock_git.get_tracked_worktrees().is_empty());
+    }
+
+    #[test]
+    fn test_split_worktree_named_remote_tracks_its_branch() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/canonical/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/fork/repo");
+        mock_git.set_named_remote_url(&repo_root, "upstream", "https://github.com/canonical/repo");
+        mock_git.add_remote_branch("https://github.com/canonical/repo", "release");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/canonical");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs, mock_ui);
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "release", &[], false, Some("upstream"), None);
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert_eq!(
+            mock_git.get_tracked_worktrees(),
+            vec![(
+                PathBuf::from("/test_root/github.com/canonical/repo+release"),
+                "upstream/release".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_split_worktree_detached_at_ref() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let usecase = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "v1.0", &[], false, None, Some("v1.0.0"));
+
+        // Assert
+        assert!(result.is_ok(), "Failed with error: {:?}", result.err());
+        assert!(mock_git.get_worktrees().is_empty());
+        assert_eq!(
+            mock_git.get_detached_worktrees(),
+            vec![(
+                PathBuf::from("/test_root/github.com/user/repo+v1.0"),
+                "v1.0.0".to_string()
+            )]
+        );
+        assert!(mock_ui.has_printed("/test_root/github.com/user/repo+v1.0"));
+    }
+
+    #[test]
+    fn test_split_list_remove_slashed_branch() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+
+        let config = Config::new(PathBuf::from("/test_root"));
+        let split = SplitWorktreeUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+        let list = ListRepositoriesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+        let remove =
+            RemoveRepositoryUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        // Act
+        let split_result = split.execute(&config, "feature/foobar", &[], false, None, None);
+        // `git worktree add` leaves a `.git` file in the new worktree
+        let worktree = PathBuf::from("/test_root/github.com/user/repo+feature/foobar");
+        mock_fs.add_file(worktree.join(".git"));
+        let list_result = list.execute(&config, &ListOptions::default());
+        let listed = mock_ui.get_printed_messages();
+        let remove_result = remove.execute(
+            &config,
+            "https://github.com/user/repo",
+            None,
+            true,
+            false,
+            false,
+        );
+
+        // Assert
+        assert!(
+            split_result.is_ok(),
+            "Failed with error: {:?}",
+            split_result.err()
+        );
+        assert_eq!(mock_git.get_worktrees(), vec![worktree.clone()]);
+
+        assert!(
+            list_result.is_ok(),
+            "Failed with error: {:?}",
+            list_result.err()
+        );
+        assert!(listed.contains(&"github.com/user/repo+feature/foobar".to_string()));
+        assert!(listed.contains(&"github.com/user/repo+main".to_string()));
+        assert!(!listed.contains(&"github.com/user/repo+feature".to_string()));
+
+        assert_eq!(remove_result.unwrap().removed, 2);
+        assert!(!mock_fs.exists(&worktree));
+        assert!(!mock_fs.exists(Path::new("/test_root/github.com/user/repo+feature")));
+        assert!(mock_fs.exists(Path::new("/test_root")));
     }
 }
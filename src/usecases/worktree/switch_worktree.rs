@@ -0,0 +1,209 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::configs::Config;
+use crate::core::ports::{FileSystem, GitRepository, UserInteraction};
+use crate::core::{RepoInfo, RepoScanner};
+use crate::errors::GrmError;
+use crate::usecases::SplitWorktreeUseCase;
+
+pub struct SwitchWorktreeUseCase {
+    git: Arc<dyn GitRepository>,
+    fs: Arc<dyn FileSystem>,
+    ui: Arc<dyn UserInteraction>,
+}
+
+impl SwitchWorktreeUseCase {
+    pub fn new(
+        git: Arc<dyn GitRepository>,
+        fs: Arc<dyn FileSystem>,
+        ui: Arc<dyn UserInteraction>,
+    ) -> Self {
+        Self { git, fs, ui }
+    }
+
+    /// Print the path of the sibling worktree for `branch`
+    ///
+    /// # Arguments
+    /// * `create` - Split a new worktree if it does not exist yet
+    /// * `remote` - Remote whose URL locates a created worktree, `origin` if `None`
+    pub fn execute(
+        &self,
+        config: &Config,
+        branch: &str,
+        create: bool,
+        remote: Option<&str>,
+    ) -> Result<PathBuf, GrmError> {
+        let repo_root = self
+            .git
+            .get_repository_root()
+            .map_err(|_| GrmError::NotInManagedRepository)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )
+        .map_err(|_| GrmError::NotInManagedRepository)?;
+
+        let mut dest_path = repo_info.build_repo_path_with(config.root(), branch, &config.layout);
+
+        if !self.fs.exists(&dest_path) {
+            if !create {
+                return Err(self.unknown_branch(config, &repo_info, branch)?);
+            }
+
+            let remote_url = self
+                .git
+                .get_remote_url(&repo_root, remote)
+                .map_err(|_| GrmError::NotInManagedRepository)?;
+            let splitter = SplitWorktreeUseCase::new(
+                Arc::clone(&self.git),
+                Arc::clone(&self.fs),
+                Arc::clone(&self.ui),
+            );
+            dest_path =
+                splitter.split(config, &repo_root, remote, &remote_url, branch, &[], None)?;
+        }
+
+        self.ui.print_result(&dest_path.display().to_string());
+
+        Ok(dest_path)
+    }
+
+    fn unknown_branch(
+        &self,
+        config: &Config,
+        repo_info: &RepoInfo,
+        branch: &str,
+    ) -> Result<GrmError, GrmError> {
+        let scanner = RepoScanner::new(Arc::clone(&self.fs)).with_layout(config.layout.clone());
+        let mut branches = scanner
+            .scan_worktrees(config.root(), repo_info)?
+            .iter()
+            .filter_map(|path| RepoInfo::from_path_with(config.root(), path, &config.layout).ok())
+            .filter_map(|info| info.branch)
+            .collect::<Vec<_>>();
+        branches.sort();
+
+        Ok(GrmError::NotFound(format!(
+            "No worktree for branch {branch}. Existing worktrees: {}",
+            branches.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::test_helpers::{MockFileSystem, MockGitRepository, MockUserInteraction};
+
+    fn setup() -> (
+        Arc<MockGitRepository>,
+        Arc<MockFileSystem>,
+        Arc<MockUserInteraction>,
+        Config,
+    ) {
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+        mock_git.set_remote_url(&repo_root, "https://github.com/user/repo");
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.add_git_repo("/test_root/github.com/user/repo+feature-x");
+        mock_fs.add_git_repo("/test_root/github.com/user/other+dev");
+
+        (
+            mock_git,
+            mock_fs,
+            mock_ui,
+            Config::new(PathBuf::from("/test_root")),
+        )
+    }
+
+    #[test]
+    fn test_switch_existing_worktree() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = SwitchWorktreeUseCase::new(mock_git.clone(), mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "feature-x", false, None);
+
+        // Assert
+        let path = result.unwrap();
+        assert_eq!(
+            path,
+            PathBuf::from("/test_root/github.com/user/repo+feature-x")
+        );
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![path.display().to_string()]
+        );
+        assert!(mock_git.get_worktrees().is_empty());
+    }
+
+    #[test]
+    fn test_switch_unknown_branch_lists_worktrees() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = SwitchWorktreeUseCase::new(mock_git, mock_fs, mock_ui);
+
+        // Act
+        let result = usecase.execute(&config, "missing", false, None);
+
+        // Assert
+        match result {
+            Err(GrmError::NotFound(msg)) => {
+                assert!(msg.contains("feature-x, main"), "unexpected message: {msg}");
+                assert!(!msg.contains("dev"));
+            }
+            other => panic!("Expected NotFound error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_switch_create_missing_worktree() {
+        // Arrange
+        let (mock_git, mock_fs, mock_ui, config) = setup();
+        let usecase = SwitchWorktreeUseCase::new(mock_git.clone(), mock_fs, mock_ui.clone());
+
+        // Act
+        let result = usecase.execute(&config, "new", true, None);
+
+        // Assert
+        let path = result.unwrap();
+        assert_eq!(path, PathBuf::from("/test_root/github.com/user/repo+new"));
+        assert_eq!(mock_git.get_worktrees(), vec![path.clone()]);
+        assert_eq!(
+            mock_ui.get_printed_messages(),
+            vec![path.display().to_string()]
+        );
+    }
+
+    #[test]
+    fn test_switch_not_in_repo() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+        let usecase = SwitchWorktreeUseCase::new(mock_git, mock_fs, mock_ui);
+
+        // Act
+        let result = usecase.execute(
+            &Config::new(PathBuf::from("/test_root")),
+            "main",
+            false,
+            None,
+        );
+
+        // Assert
+        assert!(matches!(result, Err(GrmError::NotInManagedRepository)));
+    }
+}
@@ -29,20 +29,40 @@ impl UnshareFilesUseCase {
             .git
             .get_repository_root()
             .map_err(|_| GrmError::NotInManagedRepository)?;
-        let repo_info = RepoInfo::from_path(config.root(), &repo_root)?;
+        let repo_info = RepoInfo::from_path_resolved(
+            self.fs.as_ref(),
+            config.root(),
+            &repo_root,
+            &config.layout,
+        )?;
         let resource = SharedResource::new(
             repo_info.clone(),
             Arc::clone(&self.fs),
             config.root().to_path_buf(),
-        );
+        )
+        .with_layout(config.layout.clone());
 
         match resource.unshare(&repo_root, &relative_path) {
-            Ok(removed_count) => {
-                if removed_count == 0 {
+            Ok(result) => {
+                if result.removed == 0 {
                     self.ui.print("No shared files found to unshare.");
                 } else {
                     self.ui.print(&format!(
-                        "Unshared {removed_count} file(s) from all worktrees.",
+                        "Unshared {} file(s) from all worktrees.",
+                        result.removed
+                    ));
+                }
+
+                for worktree in &result.diverged {
+                    self.ui.print_warning(&format!(
+                        "{} has its own copy of {path_str}, which was left as is",
+                        worktree.display()
+                    ));
+                }
+                for worktree in &result.foreign {
+                    self.ui.print_warning(&format!(
+                        "{} links {path_str} outside the shared storage, which was left as is",
+                        worktree.display()
                     ));
                 }
 
@@ -83,17 +103,15 @@ mod tests {
         // Setup: Shared file with symlinks in multiple worktrees
         let shared_file = PathBuf::from("/test_root/.shared/github.com/user/repo/test.txt");
         mock_fs.add_file(&shared_file);
-        mock_fs.add_symlink(&repo_root.join("test.txt"), &shared_file);
+        mock_fs.add_symlink(repo_root.join("test.txt"), &shared_file);
 
         let worktree = PathBuf::from("/test_root/github.com/user/repo+feature");
         mock_fs.add_git_repo(&worktree);
-        mock_fs.add_symlink(&worktree.join("test.txt"), &shared_file);
+        mock_fs.add_symlink(worktree.join("test.txt"), &shared_file);
 
         let usecase = UnshareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "test.txt");
@@ -101,9 +119,54 @@ mod tests {
         // Assert
         assert!(result.is_ok());
         let messages = mock_ui.get_printed_messages();
-        assert!(messages
-            .iter()
-            .any(|m| m.contains("Unshared 2 file(s) from all worktrees")));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("Unshared 2 file(s) from all worktrees"))
+        );
+    }
+
+    #[test]
+    fn test_unshare_warns_about_diverged_copy() {
+        // Arrange
+        let mock_git = Arc::new(MockGitRepository::new());
+        let mock_fs = Arc::new(MockFileSystem::new());
+        let mock_ui = Arc::new(MockUserInteraction::new());
+
+        let repo_root = PathBuf::from("/test_root/github.com/user/repo+main");
+        mock_git.set_repo_root(&repo_root);
+
+        mock_fs.add_dir("/test_root");
+        mock_fs.add_dir("/test_root/github.com");
+        mock_fs.add_dir("/test_root/github.com/user");
+        mock_fs.add_git_repo(&repo_root);
+        mock_fs.set_current_dir(&repo_root);
+
+        let shared_file = PathBuf::from("/test_root/.shared/github.com/user/repo/test.txt");
+        mock_fs.add_file(&shared_file);
+        mock_fs.add_symlink(repo_root.join("test.txt"), &shared_file);
+
+        let worktree = PathBuf::from("/test_root/github.com/user/repo+feature");
+        mock_fs.add_git_repo(&worktree);
+        mock_fs.add_file(worktree.join("test.txt"));
+
+        let usecase = UnshareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
+
+        let config = Config::new(PathBuf::from("/test_root"));
+
+        // Act
+        let result = usecase.execute(&config, "test.txt");
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(mock_ui.has_printed("Unshared 1 file(s) from all worktrees."));
+        assert_eq!(
+            mock_ui.get_warning_messages(),
+            vec![
+                "/test_root/github.com/user/repo+feature has its own copy of test.txt, which was left as is"
+                    .to_string()
+            ]
+        );
     }
 
     #[test]
@@ -130,9 +193,7 @@ mod tests {
 
         let usecase = UnshareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "nonexistent.txt");
@@ -140,9 +201,11 @@ mod tests {
         // Assert
         assert!(result.is_ok());
         let messages = mock_ui.get_printed_messages();
-        assert!(messages
-            .iter()
-            .any(|m| m.contains("No shared files found to unshare")));
+        assert!(
+            messages
+                .iter()
+                .any(|m| m.contains("No shared files found to unshare"))
+        );
     }
 
     #[test]
@@ -156,9 +219,7 @@ mod tests {
 
         let usecase = UnshareFilesUseCase::new(mock_git.clone(), mock_fs.clone(), mock_ui.clone());
 
-        let config = Config {
-            root: PathBuf::from("/test_root"),
-        };
+        let config = Config::new(PathBuf::from("/test_root"));
 
         // Act
         let result = usecase.execute(&config, "test.txt");
@@ -167,4 +228,3 @@ mod tests {
         assert!(matches!(result, Err(GrmError::NotInManagedRepository)));
     }
 }
-